@@ -1,6 +1,10 @@
+import { MissionVisibility } from './mission'
+
 export interface AddMission {
     name: string
     description?: string
     max_members?: number
+    visibility?: MissionVisibility
+    tags?: string[]
     status?: string
 }
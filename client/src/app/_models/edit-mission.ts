@@ -1,6 +1,12 @@
+import { FileType, MissionVisibility } from './mission'
+
 export interface EditMission {
     name?: string
     description?: string
     max_members?: number
+    visibility?: MissionVisibility
+    max_upload_mb?: number
+    allowed_file_types?: FileType[]
+    tags?: string[]
     status?: string
 }
@@ -1,8 +1,22 @@
 export interface MissionFilter {
     name?: string
     status?: MissionStatus
+    chief_id?: number
+    min_member_count?: number
+    max_member_count?: number
+    has_free_slots?: boolean
+    created_after?: string
+    created_before?: string
+    ends_after?: string
+    ends_before?: string
+    tags?: string[]
+    sort?: MissionSort
+    cursor?: string
+    limit?: number
 }
 
+export type MissionSort = 'Newest' | 'MostMembers' | 'FreeSlots' | 'EndingSoon'
+
 export type MissionStatus =
     'Open' |
     'InProgress' |
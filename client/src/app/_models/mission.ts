@@ -1,3 +1,9 @@
+export type MissionVisibility = 'Public' | 'RequestToJoin' | 'InviteOnly'
+
+export type FileType =
+    'Png' | 'Jpeg' | 'Gif' | 'Webp' | 'Pdf' | 'Doc' | 'Docx' | 'Xls' | 'Xlsx' | 'Ppt' | 'Pptx' |
+    'Zip' | 'Tar' | 'Gzip' | 'SevenZip' | 'Mp4' | 'Webm' | 'Mp3' | 'Text' | 'Markdown' | 'Csv'
+
 export interface Mission {
     id: number,
     name: string,
@@ -7,7 +13,19 @@ export interface Mission {
     chief_display_name: string,
     member_count: number,
     max_members: number,
+    visibility: MissionVisibility,
+    max_upload_mb: number,
+    allowed_file_types: FileType[],
+    end_date?: Date,
+    tags: string[],
     created_at: Date,
     updated_at: Date,
     is_joined: boolean
-}
\ No newline at end of file
+}
+
+export interface MissionPage {
+    items: Mission[],
+    next_cursor?: string,
+    limit: number,
+    total: number
+}
@@ -3,7 +3,7 @@ import { environment } from '../../environments/environment'
 import { HttpClient } from '@angular/common/http'
 import { MissionFilter } from '../_models/mission-filter'
 import { firstValueFrom } from 'rxjs'
-import { Mission } from '../_models/mission'
+import { FileType, Mission, MissionPage } from '../_models/mission'
 import { AddMission } from '../_models/add-mission'
 import { EditMission } from '../_models/edit-mission'
 
@@ -16,11 +16,10 @@ export class MissionService {
 
   filter: MissionFilter = {}
 
-  async getByFilter(filter: MissionFilter): Promise<Mission[]> {
+  async getPageByFilter(filter: MissionFilter): Promise<MissionPage> {
     const queryString = this.createQueryString(filter)
     const url = this._api_url + '/view/filter?' + queryString
-    const missions = await firstValueFrom(this._http.get<Mission[]>(url))
-    return missions
+    return await firstValueFrom(this._http.get<MissionPage>(url))
   }
 
   async getById(id: number): Promise<Mission> {
@@ -82,7 +81,7 @@ export class MissionService {
     await firstValueFrom(this._http.delete(url))
   }
 
-  async updateSettings(missionId: number, settings: { max_members: number }): Promise<void> {
+  async updateSettings(missionId: number, settings: { max_members: number, max_upload_mb?: number, allowed_file_types?: FileType[] }): Promise<void> {
     const url = this._api_url + '/v1/missions/' + missionId + '/settings'
     await firstValueFrom(this._http.post(url, settings))
   }
@@ -118,13 +117,19 @@ export class MissionService {
     return await firstValueFrom(this._http.patch<any>(url, task))
   }
 
+  async claimTask(missionId: number, taskId: number): Promise<any> {
+    const url = this._api_url + '/v1/missions/' + missionId + '/tasks/' + taskId + '/claim'
+    return await firstValueFrom(this._http.post<any>(url, {}))
+  }
+
   async deleteTask(missionId: number, taskId: number): Promise<void> {
     const url = this._api_url + '/v1/missions/' + missionId + '/tasks/' + taskId
     await firstValueFrom(this._http.delete(url))
   }
 
   private createQueryString(filter: MissionFilter): string {
-    this.filter = filter
+    // Remember the search, not the position in its results
+    this.filter = { ...filter, cursor: undefined }
     const params: string[] = []
 
     if (filter.name && filter.name.trim()) {
@@ -133,6 +138,35 @@ export class MissionService {
     if (filter.status) {
       params.push(`status=${filter.status}`)
     }
+    if (filter.chief_id !== undefined) {
+      params.push(`chief_id=${filter.chief_id}`)
+    }
+    if (filter.min_member_count !== undefined) {
+      params.push(`min_member_count=${filter.min_member_count}`)
+    }
+    if (filter.max_member_count !== undefined) {
+      params.push(`max_member_count=${filter.max_member_count}`)
+    }
+    if (filter.has_free_slots !== undefined) {
+      params.push(`has_free_slots=${filter.has_free_slots}`)
+    }
+    for (const key of ['created_after', 'created_before', 'ends_after', 'ends_before'] as const) {
+      if (filter[key]) {
+        params.push(`${key}=${encodeURIComponent(filter[key])}`)
+      }
+    }
+    if (filter.tags && filter.tags.length) {
+      params.push(`tags=${encodeURIComponent(filter.tags.join(','))}`)
+    }
+    if (filter.sort) {
+      params.push(`sort=${filter.sort}`)
+    }
+    if (filter.cursor) {
+      params.push(`cursor=${filter.cursor}`)
+    }
+    if (filter.limit) {
+      params.push(`limit=${filter.limit}`)
+    }
 
     return params.join('&')
   }
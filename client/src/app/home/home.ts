@@ -56,10 +56,11 @@ export class Home implements OnInit {
       this.activeMissions.set(unique.slice(0, 5));
 
       // Fetch Featured/Open Missions
-      const allOpen = await this.missionService.getByFilter({ status: 'Open' });
+      // The first page of open missions is enough to pick a few from
+      const openPage = await this.missionService.getPageByFilter({ status: 'Open' });
 
       // Filter out missions owned by the user
-      const filtered = allOpen.filter(m => m.chief_id !== currentUserId);
+      const filtered = openPage.items.filter(m => m.chief_id !== currentUserId);
 
       this.featuredMissions.set(filtered.slice(0, 4));
     } catch (error) {
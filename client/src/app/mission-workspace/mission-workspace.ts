@@ -61,7 +61,7 @@ export class MissionWorkspace implements OnInit {
     isUserChief = signal<boolean>(false);
     isSubmitting = signal<boolean>(false);
 
-    availableRoles = ['Member', 'Tactician', 'Combatant', 'Support', 'Sniper', 'Tank', 'Healer'];
+    availableRoles = ['Officer', 'Member', 'Observer'];
 
     async ngOnInit() {
         const id = this._route.snapshot.paramMap.get('id');
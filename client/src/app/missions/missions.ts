@@ -48,6 +48,9 @@ export class Missions {
   private _missionsSubject = new BehaviorSubject<Mission[]>([])
   readonly missions$ = this._missionsSubject.asObservable()
 
+  nextCursor?: string
+  isLoadingMore = false
+
   isSignin: any;
   currentUserId: any;
 
@@ -59,14 +62,35 @@ export class Missions {
   }
 
   async onSubmit() {
-    let missions = await this._mission.getByFilter(this.filter)
+    this.filter = { ...this.filter, cursor: undefined }
+    const page = await this._mission.getPageByFilter(this.filter)
+
+    this.nextCursor = page.next_cursor
+    this._missionsSubject.next(this.visible(page.items))
+  }
+
+  async loadMore() {
+    if (!this.nextCursor || this.isLoadingMore) return
+
+    this.isLoadingMore = true
+    try {
+      const page = await this._mission.getPageByFilter({ ...this.filter, cursor: this.nextCursor })
 
-    if (this.isSignin()) {
-      const userId = this.currentUserId()
-      missions = missions.filter(m => m.chief_id !== userId && !m.is_joined)
+      this.nextCursor = page.next_cursor
+        this._missionsSubject.next([...this._missionsSubject.value, ...this.visible(page.items)])
+    } catch (e) {
+      console.error(e)
+      this._snackBar.open('Failed to load more missions', 'Close', { duration: 3000 });
+    } finally {
+      this.isLoadingMore = false
     }
+  }
+
+  private visible(missions: Mission[]): Mission[] {
+    if (!this.isSignin()) return missions
 
-    this._missionsSubject.next(missions)
+    const userId = this.currentUserId()
+    return missions.filter(m => m.chief_id !== userId && !m.is_joined)
   }
 
   openAddMissionDialog() {
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    repositories::mission_viewing::MissionViewingRepository,
+    value_objects::{
+        crew_roles::{CrewRole, Permission},
+        mission_model::MissionModel,
+    },
+};
+
+/// The one place that decides what a brawler may do inside a mission.
+pub struct MissionAuthorization<T>
+where
+    T: MissionViewingRepository + Send + Sync,
+{
+    mission_viewing_repository: Arc<T>,
+}
+
+impl<T> MissionAuthorization<T>
+where
+    T: MissionViewingRepository + Send + Sync,
+{
+    pub fn new(mission_viewing_repository: Arc<T>) -> Self {
+        Self {
+            mission_viewing_repository,
+        }
+    }
+
+    pub async fn role_in(
+        &self,
+        mission: &MissionModel,
+        brawler_id: i32,
+    ) -> DomainResult<Option<CrewRole>> {
+        if mission.chief_id == brawler_id {
+            return Ok(Some(CrewRole::Chief));
+        }
+
+        let role = self
+            .mission_viewing_repository
+            .member_role(mission.id, brawler_id)
+            .await?;
+
+        Ok(role.map(|role| role.parse()).transpose()?)
+    }
+
+    /// Loads the mission and fails with Forbidden unless the brawler's role grants `permission`.
+    pub async fn require(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        permission: Permission,
+    ) -> DomainResult<(MissionModel, CrewRole)> {
+        let mission = self
+            .mission_viewing_repository
+            .view_detail(mission_id, Some(brawler_id))
+            .await?;
+
+        match self.role_in(&mission, brawler_id).await? {
+            Some(role) if role.has(permission) => Ok((mission, role)),
+            Some(role) => Err(DomainError::forbidden(format!(
+                "{} role is not allowed to {}",
+                role,
+                Self::describe(permission)
+            ))),
+            None => Err(DomainError::forbidden("You are not a member of this mission")),
+        }
+    }
+
+    /// Like `require`, but answers instead of failing.
+    pub async fn allows(
+        &self,
+        mission: &MissionModel,
+        brawler_id: i32,
+        permission: Permission,
+    ) -> DomainResult<bool> {
+        let role = self.role_in(mission, brawler_id).await?;
+        Ok(role.is_some_and(|role| role.has(permission)))
+    }
+
+    fn describe(permission: Permission) -> &'static str {
+        match permission {
+            Permission::ViewWorkspace => "view this mission",
+            Permission::SubmitWork => "submit work",
+            Permission::ManageTasks => "manage tasks",
+            Permission::ReviewSubmissions => "review submissions",
+            Permission::KickMembers => "kick members",
+            Permission::ManageAdmissions => "manage invitations and join requests",
+            Permission::ManageRoles => "change crew roles",
+            Permission::EditSettings => "edit mission settings",
+            Permission::ChangeStatus => "change mission stages",
+            Permission::DeleteMission => "delete this mission",
+            Permission::TransferChieftainship => "hand over chieftainship",
+            Permission::ManageWebhooks => "manage webhooks",
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+use tokio::sync::broadcast;
+
+use crate::domain::value_objects::mission_events::{MissionEvent, MissionEventEnvelope};
+
+// Events a slow subscriber may fall behind by before it is told to resync
+const CHANNEL_CAPACITY: usize = 128;
+// Background consumers of every mission see far more traffic than a single stream
+const ALL_MISSIONS_CAPACITY: usize = 1024;
+
+/// In-process fan-out of workspace events, one channel per mission with
+/// listeners plus one carrying every mission. Use cases publish after their
+/// changes are stored; nothing is kept for subscribers that connect later.
+pub struct MissionEventHub {
+    channels: Mutex<HashMap<i32, broadcast::Sender<MissionEventEnvelope>>>,
+    all_missions: broadcast::Sender<MissionEventEnvelope>,
+}
+
+impl Default for MissionEventHub {
+    fn default() -> Self {
+        Self {
+            channels: Mutex::new(HashMap::new()),
+            all_missions: broadcast::channel(ALL_MISSIONS_CAPACITY).0,
+        }
+    }
+}
+
+impl MissionEventHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn publish(&self, mission_id: i32, event: MissionEvent) {
+        let envelope = MissionEventEnvelope::new(mission_id, event);
+        // Nobody listening to every mission is not an error
+        let _ = self.all_missions.send(envelope.clone());
+
+        let mut channels = self.channels.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(sender) = channels.get(&mission_id) else {
+            return;
+        };
+        // Sending only fails once every subscriber is gone
+        if sender.send(envelope).is_err() {
+            channels.remove(&mission_id);
+        }
+    }
+
+    /// Events of every mission, for background consumers such as webhook delivery.
+    pub fn subscribe_all(&self) -> broadcast::Receiver<MissionEventEnvelope> {
+        self.all_missions.subscribe()
+    }
+
+    pub fn subscribe(&self, mission_id: i32) -> broadcast::Receiver<MissionEventEnvelope> {
+        let mut channels = self.channels.lock().unwrap_or_else(PoisonError::into_inner);
+        channels.retain(|_, sender| sender.receiver_count() > 0);
+        channels
+            .entry(mission_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+}
@@ -0,0 +1,4 @@
+pub mod mission_authorization;
+pub mod mission_events;
+pub mod notifier;
+pub mod task_workflow;
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    entities::notifications::AddNotificationEntity,
+    repositories::notifications::NotificationRepository,
+    value_objects::notification_kinds::NotificationKinds,
+};
+
+/// Sends in-app notifications on behalf of use cases. The action that caused a
+/// notification has already been stored, so a failed delivery is only logged.
+pub struct Notifier<T>
+where
+    T: NotificationRepository + Send + Sync,
+{
+    notification_repository: Arc<T>,
+}
+
+impl<T> Notifier<T>
+where
+    T: NotificationRepository + Send + Sync,
+{
+    pub fn new(notification_repository: Arc<T>) -> Self {
+        Self { notification_repository }
+    }
+
+    pub async fn send(
+        &self,
+        recipients: Vec<i32>,
+        kind: NotificationKinds,
+        mission_id: Option<i32>,
+        message: String,
+    ) {
+        let notices = recipients
+            .into_iter()
+            .map(|brawler_id| AddNotificationEntity {
+                brawler_id,
+                kind: kind.to_string(),
+                mission_id,
+                message: message.clone(),
+            })
+            .collect();
+
+        if let Err(e) = self.notification_repository.notify(notices).await {
+            tracing::warn!("Failed to send {} notifications: {:?}", kind, e);
+        }
+    }
+}
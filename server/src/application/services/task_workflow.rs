@@ -0,0 +1,219 @@
+use std::sync::Arc;
+
+use chrono::Local;
+
+use crate::{
+    application::services::mission_events::MissionEventHub,
+    domain::{
+        entities::tasks::UpdateTaskEntity,
+        errors::{DomainError, DomainResult},
+        repositories::tasks::TaskRepository,
+        value_objects::{
+            crew_roles::{CrewRole, Permission},
+            mission_events::MissionEvent,
+            task_model::TaskModel,
+            task_statuses::TaskStatuses,
+        },
+    },
+};
+
+/// Rules every task status change obeys, whoever makes it.
+pub struct TaskWorkflow<T>
+where
+    T: TaskRepository + Send + Sync,
+{
+    task_repository: Arc<T>,
+    events: Arc<MissionEventHub>,
+}
+
+impl<T> TaskWorkflow<T>
+where
+    T: TaskRepository + Send + Sync,
+{
+    pub fn new(task_repository: Arc<T>, events: Arc<MissionEventHub>) -> Self {
+        Self {
+            task_repository,
+            events,
+        }
+    }
+
+    /// Managers may make any workflow move, reviewers judge work under review and
+    /// assignees move their own task through the rest.
+    pub async fn ensure_may_transition(
+        &self,
+        task: &TaskModel,
+        role: CrewRole,
+        user_id: i32,
+        next: TaskStatuses,
+    ) -> DomainResult<()> {
+        let current: TaskStatuses = task.status.parse()?;
+        if !current.can_transition_to(next) {
+            return Err(DomainError::conflict(format!(
+                "Can not move a task from {} to {}",
+                current, next
+            )));
+        }
+
+        if current.is_review_decision(next) {
+            if !role.has(Permission::ManageTasks) && !role.has(Permission::ReviewSubmissions) {
+                return Err(DomainError::forbidden("Only a reviewer can decide on work under review"));
+            }
+        } else {
+            Self::ensure_assignee(task, role, user_id)?;
+        }
+
+        self.ensure_can_move(task, next).await
+    }
+
+    /// Where handing in work leaves the task: under review, going through In Progress
+    /// when it hadn't been started or came back rejected. `None` when a new version goes
+    /// to a task that is already under review.
+    pub async fn submission_move(
+        &self,
+        task: &TaskModel,
+        role: CrewRole,
+        user_id: i32,
+    ) -> DomainResult<Option<TaskStatuses>> {
+        match task.status.parse()? {
+            TaskStatuses::Review => {
+                Self::ensure_assignee(task, role, user_id)?;
+                return Ok(None);
+            }
+            TaskStatuses::Pending | TaskStatuses::Rejected => {
+                self.ensure_may_transition(task, role, user_id, TaskStatuses::InProgress)
+                    .await?;
+                let started = TaskModel {
+                    status: TaskStatuses::InProgress.to_string(),
+                    ..task.clone()
+                };
+                self.ensure_may_transition(&started, role, user_id, TaskStatuses::Review)
+                    .await?;
+            }
+            _ => {
+                self.ensure_may_transition(task, role, user_id, TaskStatuses::Review)
+                    .await?;
+            }
+        }
+        Ok(Some(TaskStatuses::Review))
+    }
+
+    /// Work on a task is handed in and taken back by its assignee, or by a manager.
+    pub fn ensure_assignee(task: &TaskModel, role: CrewRole, user_id: i32) -> DomainResult<()> {
+        if role.has(Permission::ManageTasks) {
+            return Ok(());
+        }
+        if task.member_id != Some(user_id) || !role.has(Permission::SubmitWork) {
+            return Err(DomainError::forbidden("Only the assignee can move this task"));
+        }
+        Ok(())
+    }
+
+    pub async fn ensure_can_move(&self, task: &TaskModel, status: TaskStatuses) -> DomainResult<()> {
+        if status.needs_blockers_done() {
+            let blockers = self.task_repository.get_open_blockers(task.id).await?;
+            if !blockers.is_empty() {
+                let titles: Vec<&str> = blockers.iter().map(|t| t.title.as_str()).collect();
+                return Err(DomainError::conflict(format!(
+                    "Task is blocked by: {}",
+                    titles.join(", ")
+                )));
+            }
+        }
+
+        if status.is_done() {
+            let subtasks = self.task_repository.get_subtasks(task.id).await?;
+            if subtasks.iter().any(|subtask| subtask.status != status.to_string()) {
+                return Err(DomainError::conflict(
+                    "Finish every subtask before completing this task",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Keeps a parent in step with its subtasks. Roll-up only makes moves the transition
+    /// table allows: once the last subtask is done the parent goes to Review for a reviewer
+    /// to sign off, and when a subtask reopens a parent under review or done goes back to
+    /// In Progress. It never completes or rejects a task itself.
+    pub async fn roll_up(&self, task: &TaskModel) -> DomainResult<()> {
+        let Some(parent_task_id) = task.parent_task_id else {
+            return Ok(());
+        };
+        let mut parent = self.task_repository.get_by_id(parent_task_id).await?;
+        let current: TaskStatuses = parent.status.parse()?;
+
+        let subtasks = self.task_repository.get_subtasks(parent_task_id).await?;
+        let all_done = subtasks
+            .iter()
+            .all(|subtask| subtask.status == TaskStatuses::Done.to_string());
+
+        let path: &[TaskStatuses] = match (all_done, current) {
+            (true, TaskStatuses::Pending) => &[TaskStatuses::InProgress, TaskStatuses::Review],
+            (true, TaskStatuses::InProgress) => &[TaskStatuses::Review],
+            (false, TaskStatuses::Review | TaskStatuses::Done) => &[TaskStatuses::InProgress],
+            _ => &[],
+        };
+
+        for &next in path {
+            let from: TaskStatuses = parent.status.parse()?;
+            if !from.can_transition_to(next) {
+                break;
+            }
+            // A blocked parent waits for its blockers even when its subtasks are finished
+            if all_done && self.ensure_can_move(&parent, next).await.is_err() {
+                break;
+            }
+            parent = self.set_status(parent.id, next).await?;
+        }
+
+        // Only a parent leaving Done changes what its own parent sees
+        if current == TaskStatuses::Done && parent.status != TaskStatuses::Done.to_string() {
+            Box::pin(self.roll_up(&parent)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Records that the task gained or lost its submission, moving it to `status` when given.
+    /// Callers check the move with `ensure_may_transition` first.
+    pub async fn record_submission(
+        &self,
+        task_id: i32,
+        has_submission: bool,
+        status: Option<TaskStatuses>,
+    ) -> DomainResult<TaskModel> {
+        let entity = UpdateTaskEntity {
+            title: None,
+            description: None,
+            member_id: None,
+            status: status.map(|status| status.to_string()),
+            priority: None,
+            updated_at: Some(Local::now().naive_local()),
+            has_submission: Some(has_submission),
+        };
+
+        let task = self.task_repository.update(task_id, entity).await?;
+        self.events.publish(task.mission_id, MissionEvent::TaskUpdated { task: task.clone() });
+        if status.is_some() {
+            self.roll_up(&task).await?;
+        }
+        Ok(task)
+    }
+
+    pub async fn set_status(&self, task_id: i32, status: TaskStatuses) -> DomainResult<TaskModel> {
+        let entity = UpdateTaskEntity {
+            title: None,
+            description: None,
+            member_id: None,
+            status: Some(status.to_string()),
+            priority: None,
+            updated_at: Some(Local::now().naive_local()),
+            has_submission: None,
+        };
+
+        let task = self.task_repository.update(task_id, entity).await?;
+        self.events.publish(task.mission_id, MissionEvent::TaskUpdated { task: task.clone() });
+        Ok(task)
+    }
+}
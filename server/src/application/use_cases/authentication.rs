@@ -1,49 +1,110 @@
 ﻿use std::sync::Arc;
 
-use anyhow::Result;
+use chrono::{Duration, Utc};
 
 use crate::{
+    config::{
+        config_loader::{get_jwt_env, get_login_security_env, get_password_env, get_totp_env},
+        config_model::LoginSecurityEnv,
+    },
     domain::{
-        repositories::brawlers::BrawlerRepository,
-        value_objects::brawler_model::RegisterBrawlerModel,       
+        entities::{
+            auth_sessions::AddAuthSessionEntity,
+            brawlers::BrawlerEntity,
+            login_attempts::{AddLoginAttemptEntity, LoginAttemptEntity},
+            password_resets::AddPasswordResetEntity,
+            two_factor::{AddBrawlerTotpEntity, BrawlerTotpEntity},
+        },
+        errors::{DomainError, DomainResult},
+        repositories::{
+            auth_sessions::AuthSessionRepository, brawlers::BrawlerRepository,
+            login_attempts::LoginAttemptRepository, password_resets::PasswordResetRepository,
+            two_factor::TwoFactorRepository,
+        },
+        services::{
+            login_throttle::{FailureRecord, LoginThrottle},
+            mailer::{MailMessage, Mailer},
+        },
+        value_objects::{
+            brawler_model::{ChangePasswordModel, PasswordResetConfirmModel, RegisterBrawlerModel},
+            email_model::normalize_email,
+            login_attempt_filter::LoginAttemptFilter,
+            password::Password,
+        },
     },
     infrastructure::{
         argon2::{self, hash},
-        jwt::{authentication_model::LoginModel, jwt_model::Passport},
+        jwt::{
+            authentication_model::{LoginModel, TwoFactorEnrollmentModel, TwoFactorVerifyModel},
+            generate_opaque_token, hash_opaque_token,
+            jwt_model::{LoginResponse, Passport, TwoFactorChallenge},
+            verify_challenge_token,
+        },
+        totp,
     },
 };
-pub struct AuthenticationUseCase<T>
+pub struct AuthenticationUseCase<T1, T2, T3, T4, T5, T6, T7>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: AuthSessionRepository + Send + Sync,
+    T3: PasswordResetRepository + Send + Sync,
+    T4: Mailer + Send + Sync,
+    T5: LoginAttemptRepository + Send + Sync,
+    T6: LoginThrottle + Send + Sync,
+    T7: TwoFactorRepository + Send + Sync,
 {
-    brawler_repository: Arc<T>,
+    brawler_repository: Arc<T1>,
+    auth_session_repository: Arc<T2>,
+    password_reset_repository: Arc<T3>,
+    mailer: Arc<T4>,
+    login_attempt_repository: Arc<T5>,
+    login_throttle: Arc<T6>,
+    two_factor_repository: Arc<T7>,
 }
-impl<T> AuthenticationUseCase<T>
+impl<T1, T2, T3, T4, T5, T6, T7> AuthenticationUseCase<T1, T2, T3, T4, T5, T6, T7>
 where
-    T: BrawlerRepository + Sync + Send,
+    T1: BrawlerRepository + Sync + Send,
+    T2: AuthSessionRepository + Send + Sync,
+    T3: PasswordResetRepository + Send + Sync,
+    T4: Mailer + Send + Sync,
+    T5: LoginAttemptRepository + Send + Sync,
+    T6: LoginThrottle + Send + Sync,
+    T7: TwoFactorRepository + Send + Sync,
 {
-    pub fn new(brawler_repository: Arc<T>) -> Self {
-        Self { brawler_repository }
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        auth_session_repository: Arc<T2>,
+        password_reset_repository: Arc<T3>,
+        mailer: Arc<T4>,
+        login_attempt_repository: Arc<T5>,
+        login_throttle: Arc<T6>,
+        two_factor_repository: Arc<T7>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            auth_session_repository,
+            password_reset_repository,
+            mailer,
+            login_attempt_repository,
+            login_throttle,
+            two_factor_repository,
+        }
     }
 
-    pub async fn login(&self, login_model: LoginModel) -> Result<Passport> {
-        let username = login_model.username.clone();
-        println!("Login attempt for username: {}", username);
-
-        //find this user in database
-        let user = self.brawler_repository.find_by_username(username).await?;
-        let hashed_password = user.password;
-        println!("User found. Hashed password from DB: {}", hashed_password);
-        println!("Password provided: {}", login_model.password);
-
-        if !argon2::verify(login_model.password, hashed_password)? {
-            println!("Password verification failed!");
-            return Err(anyhow::anyhow!("Invalid Password !!"));   
-        }
-        println!("Password verification successful!");
+    fn session_expiry() -> DomainResult<chrono::NaiveDateTime> {
+        let jwt_env = get_jwt_env()?;
+        Ok((Utc::now() + Duration::days(jwt_env.ttl)).naive_utc())
+    }
 
+    fn passport_for(
+        user: BrawlerEntity,
+        session_id: i32,
+        refresh_token: String,
+    ) -> DomainResult<Passport> {
         let passport = Passport::new(
             user.id,
+            session_id,
+            refresh_token,
             user.display_name,
             user.avatar_url,
             user.bio,
@@ -52,23 +113,539 @@ where
         Ok(passport)
     }
 
+    async fn start_session(&self, user: BrawlerEntity) -> DomainResult<Passport> {
+        let refresh_token = generate_opaque_token();
+        let session = self
+            .auth_session_repository
+            .start(
+                AddAuthSessionEntity {
+                    brawler_id: user.id,
+                    expires_at: Self::session_expiry()?,
+                },
+                hash_opaque_token(&refresh_token),
+            )
+            .await?;
+
+        Self::passport_for(user, session.id, refresh_token)
+    }
+
+    fn locked_until(
+        record: &FailureRecord,
+        security_env: &LoginSecurityEnv,
+    ) -> Option<chrono::NaiveDateTime> {
+        if record.failures < security_env.max_failures {
+            return None;
+        }
+
+        // Every failure past the threshold doubles the lockout, up to the cap
+        let exponent = (record.failures - security_env.max_failures).min(20);
+        let lockout = security_env
+            .lockout_base
+            .saturating_mul(1 << exponent)
+            .min(security_env.lockout_max);
+
+        Some(record.last_failure_at + Duration::seconds(lockout))
+    }
+
+    async fn seconds_locked(
+        &self,
+        keys: &[String],
+        security_env: &LoginSecurityEnv,
+    ) -> DomainResult<Option<i64>> {
+        let now = Utc::now().naive_utc();
+        let mut remaining = None;
+
+        for key in keys {
+            let Some(record) = self.login_throttle.get(key).await? else {
+                continue;
+            };
+            if let Some(until) = Self::locked_until(&record, security_env)
+                && until > now
+            {
+                let seconds = (until - now).num_seconds().max(1);
+                remaining = Some(remaining.map_or(seconds, |r: i64| r.max(seconds)));
+            }
+        }
+
+        Ok(remaining)
+    }
+
+    async fn audit_login(
+        &self,
+        username: &str,
+        brawler_id: Option<i32>,
+        ip_address: &str,
+        reason: Option<&str>,
+    ) {
+        let attempt = AddLoginAttemptEntity {
+            username: username.to_string(),
+            brawler_id,
+            ip_address: ip_address.to_string(),
+            success: reason.is_none(),
+            reason: reason.map(str::to_string),
+        };
+
+        // Losing an audit row is not worth failing the login over
+        if let Err(e) = self.login_attempt_repository.record(attempt).await {
+            tracing::error!("Failed to record login attempt for {}: {:?}", username, e);
+        }
+    }
+
+    pub async fn login(
+        &self,
+        login_model: LoginModel,
+        ip_address: String,
+    ) -> DomainResult<LoginResponse> {
+        let security_env = get_login_security_env()?;
+        let username = login_model.username.trim().to_lowercase();
+        let username_key = format!("user:{}", username);
+        let keys = [username_key.clone(), format!("ip:{}", ip_address)];
+
+        if let Some(seconds) = self.seconds_locked(&keys, &security_env).await? {
+            self.audit_login(&username, None, &ip_address, Some("locked_out"))
+                .await;
+            return Err(DomainError::too_many_requests(format!(
+                "Too many failed login attempts, try again in {} seconds",
+                seconds
+            )));
+        }
+
+        let user = match self
+            .brawler_repository
+            .find_by_username(login_model.username.trim().to_string())
+            .await
+        {
+            Ok(user) => Some(user),
+            Err(e) => match DomainError::from(e) {
+                DomainError::NotFound(_) => None,
+                other => return Err(other),
+            },
+        };
+
+        let (brawler_id, reason) = match &user {
+            Some(user) if argon2::verify(login_model.password.clone(), user.password.clone())? => {
+                (Some(user.id), None)
+            }
+            Some(user) => (Some(user.id), Some("invalid_password")),
+            None => {
+                argon2::verify_dummy(login_model.password);
+                (None, Some("unknown_user"))
+            }
+        };
+
+        self.audit_login(&username, brawler_id, &ip_address, reason)
+            .await;
+
+        match (user, reason) {
+            (Some(user), None) => {
+                self.login_throttle.reset(&username_key).await?;
+
+                // The password alone is not enough once 2FA is on
+                if self.enabled_totp(user.id).await?.is_some() {
+                    return Ok(LoginResponse::Challenge(TwoFactorChallenge::new(user.id)?));
+                }
+
+                Ok(LoginResponse::Passport(self.start_session(user).await?))
+            }
+            _ => {
+                let window = Duration::seconds(security_env.failure_window);
+                for key in &keys {
+                    self.login_throttle.register_failure(key, window).await?;
+                }
+                Err(DomainError::unauthorized("Invalid username or password"))
+            }
+        }
+    }
+
+    async fn enabled_totp(&self, brawler_id: i32) -> DomainResult<Option<BrawlerTotpEntity>> {
+        let totp = self.two_factor_repository.find(brawler_id).await?;
+        Ok(totp.filter(|totp| totp.enabled_at.is_some()))
+    }
+
+    /// Accepts either a TOTP code or an unused recovery code, throttling
+    /// guesses per account the same way password failures are.
+    async fn check_second_factor(&self, totp: &BrawlerTotpEntity, code: &str) -> DomainResult<()> {
+        let security_env = get_login_security_env()?;
+        let key = format!("2fa:{}", totp.brawler_id);
+
+        if let Some(seconds) = self
+            .seconds_locked(std::slice::from_ref(&key), &security_env)
+            .await?
+        {
+            return Err(DomainError::too_many_requests(format!(
+                "Too many invalid two-factor codes, try again in {} seconds",
+                seconds
+            )));
+        }
+
+        let accepted = if totp::is_totp_code(code) {
+            match totp::verify(&totp.secret, code, Utc::now().timestamp())? {
+                Some(step) => {
+                    self.two_factor_repository
+                        .use_step(totp.brawler_id, step)
+                        .await?
+                }
+                None => false,
+            }
+        } else {
+            self.two_factor_repository
+                .consume_recovery_code(
+                    totp.brawler_id,
+                    hash_opaque_token(&totp::normalize_recovery_code(code)),
+                )
+                .await?
+        };
+
+        if !accepted {
+            self.login_throttle
+                .register_failure(&key, Duration::seconds(security_env.failure_window))
+                .await?;
+            return Err(DomainError::unauthorized("Invalid two-factor code"));
+        }
+
+        self.login_throttle.reset(&key).await?;
+        Ok(())
+    }
+
+    pub async fn verify_two_factor(
+        &self,
+        verify_model: TwoFactorVerifyModel,
+        ip_address: String,
+    ) -> DomainResult<Passport> {
+        let invalid_challenge = || DomainError::unauthorized("Invalid or expired challenge");
+
+        let claims = verify_challenge_token(get_jwt_env()?.secret, verify_model.challenge_token)
+            .map_err(|_| invalid_challenge())?;
+        let user_id = claims.sub.parse::<i32>().map_err(|_| invalid_challenge())?;
+
+        let totp = self
+            .enabled_totp(user_id)
+            .await?
+            .ok_or_else(invalid_challenge)?;
+        let user = self.brawler_repository.find_by_id(user_id).await?;
+
+        if let Err(e) = self.check_second_factor(&totp, &verify_model.code).await {
+            if let DomainError::Unauthorized(_) = e {
+                self.audit_login(
+                    &user.username.to_lowercase(),
+                    Some(user.id),
+                    &ip_address,
+                    Some("invalid_second_factor"),
+                )
+                .await;
+            }
+            return Err(e);
+        }
+
+        self.start_session(user).await
+    }
+
+    pub async fn enroll_two_factor(&self, brawler_id: i32) -> DomainResult<TwoFactorEnrollmentModel> {
+        if self.enabled_totp(brawler_id).await?.is_some() {
+            return Err(DomainError::conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+
+        let user = self.brawler_repository.find_by_id(brawler_id).await?;
+        let totp_env = get_totp_env()?;
+        let secret = totp::generate_secret();
+        let recovery_codes = totp::generate_recovery_codes(totp_env.recovery_codes);
+
+        self.two_factor_repository
+            .start_enrollment(
+                AddBrawlerTotpEntity {
+                    brawler_id,
+                    secret: secret.clone(),
+                },
+                recovery_codes.iter().map(|code| hash_opaque_token(code)).collect(),
+            )
+            .await?;
+
+        Ok(TwoFactorEnrollmentModel {
+            otpauth_uri: totp::otpauth_uri(&secret, &totp_env.issuer, &user.username),
+            secret,
+            recovery_codes,
+        })
+    }
+
+    pub async fn activate_two_factor(&self, brawler_id: i32, code: String) -> DomainResult<()> {
+        let totp = self
+            .two_factor_repository
+            .find(brawler_id)
+            .await?
+            .ok_or_else(|| DomainError::validation("Start two-factor enrollment first"))?;
+
+        if totp.enabled_at.is_some() {
+            return Err(DomainError::conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+
+        // Proves the authenticator app was set up before logins start depending on it
+        let step = totp::verify(&totp.secret, &code, Utc::now().timestamp())?
+            .ok_or_else(|| DomainError::validation("Invalid two-factor code"))?;
+
+        self.two_factor_repository.enable(brawler_id, step).await?;
+        Ok(())
+    }
+
+    pub async fn disable_two_factor(&self, brawler_id: i32, code: String) -> DomainResult<()> {
+        let totp = self
+            .enabled_totp(brawler_id)
+            .await?
+            .ok_or_else(|| DomainError::validation("Two-factor authentication is not enabled"))?;
+
+        self.check_second_factor(&totp, &code).await?;
+
+        self.two_factor_repository.disable(brawler_id).await?;
+        Ok(())
+    }
+
+    pub async fn search_login_attempts(
+        &self,
+        requester_id: i32,
+        mut filter: LoginAttemptFilter,
+    ) -> DomainResult<Vec<LoginAttemptEntity>> {
+        let security_env = get_login_security_env()?;
+
+        if !security_env.admin_ids.contains(&requester_id) {
+            if filter.ip_address.is_some() {
+                return Err(DomainError::forbidden(
+                    "Only admins can look up login attempts by IP address",
+                ));
+            }
+
+            let requester = self.brawler_repository.find_by_id(requester_id).await?;
+            match filter.username.as_deref().map(str::trim) {
+                None => filter.username = Some(requester.username),
+                Some(username) if username.eq_ignore_ascii_case(&requester.username) => {}
+                Some(username) => {
+                    // Chiefs may audit their own crew, nobody else
+                    let forbidden =
+                        || DomainError::forbidden("Not allowed to view these login attempts");
+                    let target = self
+                        .brawler_repository
+                        .find_by_username(username.to_string())
+                        .await
+                        .map_err(|e| match DomainError::from(e) {
+                            DomainError::NotFound(_) => forbidden(),
+                            other => other,
+                        })?;
+                    if !self
+                        .brawler_repository
+                        .is_led_by(target.id, requester_id)
+                        .await?
+                    {
+                        return Err(forbidden());
+                    }
+                }
+            }
+        }
+
+        let attempts = self.login_attempt_repository.search(&filter).await?;
+        Ok(attempts)
+    }
+
     pub async fn register(
         &self,
         mut register_brawler_model: RegisterBrawlerModel,
-    ) -> Result<Passport> {
-        let raw_password = register_brawler_model.password.clone();
+    ) -> DomainResult<Passport> {
         println!("Registering user: {}", register_brawler_model.username);
-        println!("Raw password: {}", raw_password);
 
-        let hashed_password = hash(raw_password)?;
-        println!("Hashed password generated: {}", hashed_password);
-
-        register_brawler_model.password = hashed_password;
+        let password = Password::new(register_brawler_model.password, &get_password_env()?)?;
+        register_brawler_model.password = hash(password.into_inner())?;
+        register_brawler_model.email = match register_brawler_model.email.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(email) => Some(normalize_email(email)?),
+        };
 
+        let username = register_brawler_model.username.clone();
         let register_entity = register_brawler_model.to_entity(); 
 
-        let passport = self.brawler_repository.register(register_entity).await?;
+        let brawler = match self.brawler_repository.register(register_entity).await {
+            Ok(brawler) => brawler,
+            Err(e) => {
+                return Err(match DomainError::from(e) {
+                    DomainError::Conflict(_) => self.registration_conflict(username).await,
+                    other => other,
+                });
+            }
+        };
 
-        Ok(passport)
+        self.start_session(brawler).await
+    }
+
+    /// Tells which of the unique fields of a registration was already taken.
+    async fn registration_conflict(&self, username: String) -> DomainError {
+        match self.brawler_repository.find_by_username(username).await {
+            Ok(_) => DomainError::conflict("Username is already taken"),
+            Err(e) => match DomainError::from(e) {
+                DomainError::NotFound(_) => {
+                    DomainError::conflict("Email address is already in use")
+                }
+                other => other,
+            },
+        }
+    }
+
+    pub async fn refresh(&self, refresh_token: String) -> DomainResult<Passport> {
+        let (token, session) = self
+            .auth_session_repository
+            .find_by_token_hash(hash_opaque_token(&refresh_token))
+            .await?
+            .ok_or_else(|| DomainError::unauthorized("Invalid refresh token"))?;
+
+        if session.revoked_at.is_some() {
+            return Err(DomainError::unauthorized("Session has been revoked"));
+        }
+
+        // A used token coming back means it leaked; kill the whole session family
+        if token.used_at.is_some() {
+            tracing::warn!("Refresh token reuse detected on session {}", session.id);
+            self.auth_session_repository.revoke(session.id).await?;
+            return Err(DomainError::unauthorized("Refresh token reuse detected"));
+        }
+
+        if session.expires_at <= Utc::now().naive_utc() {
+            return Err(DomainError::unauthorized("Session has expired"));
+        }
+
+        let next_refresh_token = generate_opaque_token();
+        let rotated = self
+            .auth_session_repository
+            .rotate(
+                token.id,
+                session.id,
+                hash_opaque_token(&next_refresh_token),
+                Self::session_expiry()?,
+            )
+            .await?;
+
+        if !rotated {
+            tracing::warn!("Refresh token reuse detected on session {}", session.id);
+            self.auth_session_repository.revoke(session.id).await?;
+            return Err(DomainError::unauthorized("Refresh token reuse detected"));
+        }
+
+        let user = self.brawler_repository.find_by_id(session.brawler_id).await?;
+
+        Self::passport_for(user, session.id, next_refresh_token)
+    }
+
+    pub async fn logout(&self, refresh_token: String) -> DomainResult<()> {
+        let found = self
+            .auth_session_repository
+            .find_by_token_hash(hash_opaque_token(&refresh_token))
+            .await?;
+
+        if let Some((_, session)) = found {
+            self.auth_session_repository.revoke(session.id).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn logout_all(&self, brawler_id: i32) -> DomainResult<()> {
+        self.auth_session_repository.revoke_all(brawler_id).await?;
+        Ok(())
+    }
+
+    pub async fn change_password(
+        &self,
+        brawler_id: i32,
+        change_password_model: ChangePasswordModel,
+    ) -> DomainResult<Passport> {
+        let user = self.brawler_repository.find_by_id(brawler_id).await?;
+
+        if !argon2::verify(change_password_model.old_password, user.password.clone())? {
+            return Err(DomainError::forbidden("Current password is incorrect"));
+        }
+
+        let password = Password::new(change_password_model.new_password, &get_password_env()?)?;
+        self.brawler_repository
+            .update_password(brawler_id, hash(password.into_inner())?)
+            .await?;
+
+        // Every other device has to log in again with the new password
+        self.auth_session_repository.revoke_all(brawler_id).await?;
+
+        self.start_session(user).await
+    }
+
+    pub async fn request_password_reset(&self, username: String) -> DomainResult<()> {
+        // Unknown usernames are answered the same way so accounts can't be probed
+        let user = match self.brawler_repository.find_by_username(username).await {
+            Ok(user) => user,
+            Err(e) => {
+                return match DomainError::from(e) {
+                    DomainError::NotFound(_) => Ok(()),
+                    other => Err(other),
+                };
+            }
+        };
+
+        // Anyone can type any address at registration, only a verified one may receive the reset
+        let (Some(email), Some(_)) = (user.email, user.email_verified_at) else {
+            tracing::warn!("Password reset requested for brawler {} without a verified email", user.id);
+            return Ok(());
+        };
+
+        let password_env = get_password_env()?;
+        let token = generate_opaque_token();
+        self.password_reset_repository
+            .create(AddPasswordResetEntity {
+                brawler_id: user.id,
+                token_hash: hash_opaque_token(&token),
+                expires_at: (Utc::now() + Duration::minutes(password_env.reset_ttl)).naive_utc(),
+            })
+            .await?;
+
+        self.mailer
+            .send(MailMessage {
+                to: email,
+                subject: "Reset your password".to_string(),
+                body: format!(
+                    "Hi {},\r\n\r\n\
+                     Use this token to reset your password: {}\r\n\
+                     It expires in {} minutes. If you did not ask for a reset, you can ignore this mail.",
+                    user.display_name, token, password_env.reset_ttl
+                ),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn confirm_password_reset(
+        &self,
+        reset_model: PasswordResetConfirmModel,
+    ) -> DomainResult<()> {
+        let invalid_token = || DomainError::validation("Invalid or expired reset token");
+
+        let reset = self
+            .password_reset_repository
+            .find_by_token_hash(hash_opaque_token(&reset_model.token))
+            .await?
+            .ok_or_else(invalid_token)?;
+
+        if reset.used_at.is_some() || reset.expires_at <= Utc::now().naive_utc() {
+            return Err(invalid_token());
+        }
+
+        let password = Password::new(reset_model.new_password, &get_password_env()?)?;
+
+        if !self.password_reset_repository.consume(reset.id).await? {
+            return Err(invalid_token());
+        }
+
+        self.brawler_repository
+            .update_password(reset.brawler_id, hash(password.into_inner())?)
+            .await?;
+        self.auth_session_repository
+            .revoke_all(reset.brawler_id)
+            .await?;
+
+        Ok(())
     }
 }
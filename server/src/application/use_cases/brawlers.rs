@@ -1,58 +1,69 @@
 use crate::{
+    config::config_loader::get_mail_env,
     domain::{
-        repositories::brawlers::BrawlerRepository,
+        entities::email_verifications::AddEmailVerificationEntity,
+        errors::{DomainError, DomainResult},
+        repositories::{
+            brawlers::BrawlerRepository, email_verifications::EmailVerificationRepository,
+        },
+        services::mailer::{MailMessage, Mailer},
         value_objects::{
-            base64_img::Base64Img, brawler_model::RegisterBrawlerModel, uploaded_img::UploadedImg,
+            base64_img::Base64Img, uploaded_img::UploadedImg,
             mission_model::MissionModel, brawler_model::UpdateBrawlerModel,
+            email_model::{EmailSettingsModel, normalize_email},
         },
     },
-    infrastructure::{argon2::hash, cloudinary::UploadImageOptions, jwt::jwt_model::Passport},
+    infrastructure::{
+        cloudinary::UploadImageOptions,
+        jwt::{generate_opaque_token, hash_opaque_token},
+    },
 };
-use anyhow::{Ok, Result};
+use chrono::{Duration, Utc};
 use std::sync::Arc;
 
-pub struct BrawlersUseCase<T>
+pub struct BrawlersUseCase<T1, T2, T3>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: EmailVerificationRepository + Send + Sync,
+    T3: Mailer + Send + Sync,
 {
-    brawler_repository: Arc<T>,
+    brawler_repository: Arc<T1>,
+    email_verification_repository: Arc<T2>,
+    mailer: Arc<T3>,
 }
 
-impl<T> BrawlersUseCase<T>
+impl<T1, T2, T3> BrawlersUseCase<T1, T2, T3>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: EmailVerificationRepository + Send + Sync,
+    T3: Mailer + Send + Sync,
 {
-    pub fn new(brawler_repository: Arc<T>) -> Self {
-        Self { brawler_repository }
-    }
-
-    pub async fn register(
-        &self,
-        mut register_brawler_model: RegisterBrawlerModel,
-    ) -> Result<Passport> {
-        let hashed_password = hash(register_brawler_model.password.clone())?;
-
-        register_brawler_model.password = hashed_password;
-
-        let register_entity = register_brawler_model.to_entity();
-
-        let passport = self.brawler_repository.register(register_entity).await?;
-
-        Ok(passport)
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        email_verification_repository: Arc<T2>,
+        mailer: Arc<T3>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            email_verification_repository,
+            mailer,
+        }
     }
 
     pub async fn upload_base64img(
         &self,
         user_id: i32,
         base64string: String,
-    ) -> Result<UploadedImg> {
+    ) -> DomainResult<UploadedImg> {
         let opt = UploadImageOptions {
             folder: Some("avatar".to_string()),
             public_id: Some(user_id.to_string()),
             transformation: Some("c_scale,w_256".to_string()),
+            delivery_type: None,
         };
 
-        let base64img = Base64Img::new(base64string)?;
+        let base64img =
+            Base64Img::new(base64string).map_err(|e| DomainError::validation(e.to_string()))?;
 
         let uploaded = self
             .brawler_repository
@@ -62,7 +73,7 @@ where
         Ok(uploaded)
     }
 
-    pub async fn get_missions_by_brawler(&self, brawler_id: i32) -> Result<Vec<MissionModel>> {
+    pub async fn get_missions_by_brawler(&self, brawler_id: i32) -> DomainResult<Vec<MissionModel>> {
         let missions = self.brawler_repository.get_missions(brawler_id).await?;
         let brawler = self.brawler_repository.find_by_id(brawler_id).await?;
 
@@ -75,8 +86,110 @@ where
         Ok(mission_models)
     }
 
-    pub async fn update_profile(&self, brawler_id: i32, model: UpdateBrawlerModel) -> Result<()> {
+    pub async fn update_profile(&self, brawler_id: i32, model: UpdateBrawlerModel) -> DomainResult<()> {
+        // A new address only replaces the current one once it is verified
+        if let Some(email) = &model.email {
+            self.change_email(brawler_id, email).await?;
+            if model.display_name.is_none() && model.bio.is_none() {
+                return Ok(());
+            }
+        }
+
         self.brawler_repository.update_profile(brawler_id, model).await?;
         Ok(())
     }
+
+    pub async fn email_settings(&self, brawler_id: i32) -> DomainResult<EmailSettingsModel> {
+        let brawler = self.brawler_repository.find_by_id(brawler_id).await?;
+
+        Ok(EmailSettingsModel {
+            verified: brawler.email.is_some() && brawler.email_verified_at.is_some(),
+            email: brawler.email,
+            digest: brawler.email_digest,
+        })
+    }
+
+    /// Mails a verification token to `email`. Sending the current, unverified
+    /// address again is how a lost token is replaced.
+    pub async fn change_email(&self, brawler_id: i32, email: &str) -> DomainResult<()> {
+        let email = normalize_email(email)?;
+        let brawler = self.brawler_repository.find_by_id(brawler_id).await?;
+        if brawler.email.as_deref() == Some(email.as_str()) && brawler.email_verified_at.is_some() {
+            return Err(DomainError::conflict("This email address is already verified"));
+        }
+
+        let mail_env = get_mail_env()?;
+        let window_start = (Utc::now() - Duration::minutes(mail_env.verification_window)).naive_utc();
+        let requested = self
+            .email_verification_repository
+            .count_since(brawler_id, window_start)
+            .await?;
+        if requested >= mail_env.verification_limit {
+            return Err(DomainError::too_many_requests(
+                "Too many verification emails requested, try again later",
+            ));
+        }
+
+        let token = generate_opaque_token();
+        self.email_verification_repository
+            .create(AddEmailVerificationEntity {
+                brawler_id,
+                email: email.clone(),
+                token_hash: hash_opaque_token(&token),
+                expires_at: (Utc::now() + Duration::minutes(mail_env.verification_ttl)).naive_utc(),
+            })
+            .await?;
+
+        self.mailer
+            .send(MailMessage {
+                to: email,
+                subject: "Verify your email address".to_string(),
+                body: format!(
+                    "Hi {},\r\n\r\n\
+                     Use this token to verify your email address: {}\r\n\
+                     It expires in {} minutes. If you did not add this address, you can ignore this mail.",
+                    brawler.display_name, token, mail_env.verification_ttl
+                ),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn verify_email(&self, token: String) -> DomainResult<()> {
+        let invalid_token = || DomainError::validation("Invalid or expired verification token");
+
+        let verification = self
+            .email_verification_repository
+            .find_by_token_hash(hash_opaque_token(&token))
+            .await?
+            .ok_or_else(invalid_token)?;
+
+        if verification.used_at.is_some() || verification.expires_at <= Utc::now().naive_utc() {
+            return Err(invalid_token());
+        }
+
+        match self.email_verification_repository.confirm(verification.id).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(invalid_token()),
+            Err(e) => match DomainError::from(e) {
+                DomainError::Conflict(_) => Err(DomainError::conflict(
+                    "This email address is already in use",
+                )),
+                other => Err(other),
+            },
+        }
+    }
+
+    pub async fn set_email_digest(&self, brawler_id: i32, enabled: bool) -> DomainResult<EmailSettingsModel> {
+        if enabled && !self.email_settings(brawler_id).await?.verified {
+            return Err(DomainError::validation(
+                "Verify your email address before subscribing to the digest",
+            ));
+        }
+
+        self.brawler_repository.set_email_digest(brawler_id, enabled).await?;
+
+        self.email_settings(brawler_id).await
+    }
 }
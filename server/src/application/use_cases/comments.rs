@@ -0,0 +1,213 @@
+use crate::{
+    application::services::mission_authorization::MissionAuthorization,
+    domain::{
+        entities::comments::{AddCommentEntity, CommentEditEntity, CommentEntity},
+        errors::{DomainError, DomainResult},
+        repositories::{
+            comments::CommentRepository, mission_submissions::MissionSubmissionsRepository,
+            mission_viewing::MissionViewingRepository, tasks::TaskRepository,
+        },
+        value_objects::{
+            comment_model::{
+                CommentTarget, CommentThreadModel, CreateCommentModel, EditCommentModel,
+                MAX_COMMENT_LENGTH, parse_mentions,
+            },
+            crew_roles::Permission,
+        },
+    },
+};
+use std::sync::Arc;
+
+pub struct CommentUseCase<T1, T2, T3, T4>
+where
+    T1: CommentRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: TaskRepository + Send + Sync,
+    T4: MissionSubmissionsRepository + Send + Sync,
+{
+    comment_repository: Arc<T1>,
+    authorization: MissionAuthorization<T2>,
+    task_repository: Arc<T3>,
+    mission_submissions_repository: Arc<T4>,
+}
+
+impl<T1, T2, T3, T4> CommentUseCase<T1, T2, T3, T4>
+where
+    T1: CommentRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: TaskRepository + Send + Sync,
+    T4: MissionSubmissionsRepository + Send + Sync,
+{
+    pub fn new(
+        comment_repository: Arc<T1>,
+        mission_viewing_repository: Arc<T2>,
+        task_repository: Arc<T3>,
+        mission_submissions_repository: Arc<T4>,
+    ) -> Self {
+        Self {
+            comment_repository,
+            authorization: MissionAuthorization::new(mission_viewing_repository),
+            task_repository,
+            mission_submissions_repository,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        mission_id: i32,
+        target: CommentTarget,
+        brawler_id: i32,
+    ) -> DomainResult<Vec<CommentThreadModel>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+        self.target_in_mission(mission_id, target).await?;
+
+        let comments = self.comment_repository.get_by_target(target).await?;
+        Ok(CommentThreadModel::build(comments))
+    }
+
+    pub async fn create(
+        &self,
+        mission_id: i32,
+        target: CommentTarget,
+        brawler_id: i32,
+        model: CreateCommentModel,
+    ) -> DomainResult<CommentEntity> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+        self.target_in_mission(mission_id, target).await?;
+        let body = Self::validate_body(&model.body)?;
+
+        // Replies stay on the same task or submission as the comment they answer
+        if let Some(parent_id) = model.parent_id {
+            let parent = self
+                .comment_repository
+                .get_by_id(parent_id)
+                .await?
+                .filter(|parent| {
+                    parent.task_id == target.task_id()
+                        && parent.submission_id == target.submission_id()
+                })
+                .ok_or_else(|| DomainError::not_found("Comment not found"))?;
+            if parent.deleted_at.is_some() {
+                return Err(DomainError::conflict("Cannot reply to a deleted comment"));
+            }
+        }
+
+        let mentions = self
+            .comment_repository
+            .find_members_by_username(mission_id, parse_mentions(&body))
+            .await?;
+
+        let entity = AddCommentEntity {
+            mission_id,
+            task_id: target.task_id(),
+            submission_id: target.submission_id(),
+            parent_id: model.parent_id,
+            author_id: Some(brawler_id),
+            body,
+        };
+
+        Ok(self.comment_repository.create(entity, mentions).await?)
+    }
+
+    pub async fn edit(
+        &self,
+        mission_id: i32,
+        comment_id: i32,
+        brawler_id: i32,
+        model: EditCommentModel,
+    ) -> DomainResult<CommentEntity> {
+        let comment = self.own_comment(mission_id, comment_id, brawler_id).await?;
+        let body = Self::validate_body(&model.body)?;
+        if body == comment.body {
+            return Ok(comment);
+        }
+
+        let mentions = self
+            .comment_repository
+            .find_members_by_username(mission_id, parse_mentions(&body))
+            .await?;
+
+        Ok(self.comment_repository.edit(comment_id, body, mentions).await?)
+    }
+
+    pub async fn delete(&self, mission_id: i32, comment_id: i32, brawler_id: i32) -> DomainResult<()> {
+        self.own_comment(mission_id, comment_id, brawler_id).await?;
+        self.comment_repository.delete(comment_id).await?;
+        Ok(())
+    }
+
+    pub async fn history(
+        &self,
+        mission_id: i32,
+        comment_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<Vec<CommentEditEntity>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+        self.comment_in_mission(mission_id, comment_id).await?;
+
+        Ok(self.comment_repository.get_edits(comment_id).await?)
+    }
+
+    async fn comment_in_mission(&self, mission_id: i32, comment_id: i32) -> DomainResult<CommentEntity> {
+        self.comment_repository
+            .get_by_id(comment_id)
+            .await?
+            .filter(|comment| comment.mission_id == mission_id)
+            .ok_or_else(|| DomainError::not_found("Comment not found"))
+    }
+
+    /// Only the author may change a comment, and only while still in the mission.
+    async fn own_comment(&self, mission_id: i32, comment_id: i32, brawler_id: i32) -> DomainResult<CommentEntity> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+        let comment = self.comment_in_mission(mission_id, comment_id).await?;
+
+        if comment.author_id != Some(brawler_id) {
+            return Err(DomainError::forbidden("Only the author can change this comment"));
+        }
+        if comment.deleted_at.is_some() {
+            return Err(DomainError::conflict("Comment has been deleted"));
+        }
+        Ok(comment)
+    }
+
+    async fn target_in_mission(&self, mission_id: i32, target: CommentTarget) -> DomainResult<()> {
+        match target {
+            CommentTarget::Task(task_id) => {
+                let task = self.task_repository.get_by_id(task_id).await?;
+                if task.mission_id != mission_id {
+                    return Err(DomainError::not_found("Task not found"));
+                }
+            }
+            CommentTarget::Submission(submission_id) => {
+                self.mission_submissions_repository
+                    .get_by_id(submission_id)
+                    .await?
+                    .filter(|submission| submission.mission_id == mission_id)
+                    .ok_or_else(|| DomainError::not_found("Submission not found"))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_body(body: &str) -> DomainResult<String> {
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(DomainError::validation("Comment cannot be empty"));
+        }
+        if body.chars().count() > MAX_COMMENT_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Comment cannot be longer than {} characters",
+                MAX_COMMENT_LENGTH
+            )));
+        }
+        Ok(body.to_string())
+    }
+}
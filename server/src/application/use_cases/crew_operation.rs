@@ -1,45 +1,84 @@
-use crate::domain::{
-    entities::crew_memberships::CrewMemberShips,
-    repositories::{
-        crew_operation::CrewOperationRepository, mission_viewing::MissionViewingRepository,
-        mission_submissions::MissionSubmissionsRepository,
+use crate::{
+    application::services::{
+        mission_authorization::MissionAuthorization, mission_events::MissionEventHub,
+        notifier::Notifier,
     },
-    value_objects::mission_statuses::MissionStatuses,
+    domain::{
+        entities::{
+            chief_transfers::{AddChiefTransferEntity, ChiefTransferEntity},
+            crew_memberships::CrewMemberShips,
+            mission_invitations::{AddMissionInvitationEntity, MissionInvitationEntity},
+            mission_join_requests::{AddMissionJoinRequestEntity, MissionJoinRequestEntity},
+            mission_waitlist::{AddMissionWaitlistEntity, MissionWaitlistEntity},
+        },
+        errors::{DomainError, DomainResult},
+        repositories::{
+            crew_operation::CrewOperationRepository, mission_viewing::MissionViewingRepository,
+            mission_submissions::MissionSubmissionsRepository,
+            notifications::NotificationRepository,
+        },
+        value_objects::{
+            chief_transfer_model::{ChiefTransferStatuses, TransferChiefModel},
+            crew_roles::{CrewRole, Permission},
+            mission_admission_model::{
+                CreateInvitationModel, JoinRequestFilter, JoinRequestModel, JoinRequestStatuses,
+            },
+            mission_events::MissionEvent,
+            mission_model::MissionModel,
+            mission_statuses::MissionStatuses,
+            mission_visibility::MissionVisibility,
+            notification_kinds::NotificationKinds,
+            waitlist_model::WaitlistStatusModel,
+        },
+    },
+    infrastructure::jwt::generate_opaque_token,
 };
-use anyhow::Result;
-use chrono::Local;
+use chrono::{Duration, Local, Utc};
 use std::sync::Arc;
 
-pub struct CrewOperationUseCase<T1, T2, T3>
+pub struct CrewOperationUseCase<T1, T2, T3, T4>
 where
     T1: CrewOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
     T3: MissionSubmissionsRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     crew_operation_repository: Arc<T1>,
     mission_viewing_repository: Arc<T2>,
     mission_submissions_repository: Arc<T3>,
+    authorization: MissionAuthorization<T2>,
+    events: Arc<MissionEventHub>,
+    notifier: Notifier<T4>,
 }
 
-impl<T1, T2, T3> CrewOperationUseCase<T1, T2, T3>
+impl<T1, T2, T3, T4> CrewOperationUseCase<T1, T2, T3, T4>
 where
     T1: CrewOperationRepository + Send + Sync + 'static,
     T2: MissionViewingRepository + Send + Sync,
     T3: MissionSubmissionsRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
+    const INVITATION_DEFAULT_HOURS: i64 = 72;
+    const INVITATION_MAX_HOURS: i64 = 24 * 30;
+
     pub fn new(
         crew_operation_repository: Arc<T1>,
         mission_viewing_repository: Arc<T2>,
         mission_submissions_repository: Arc<T3>,
+        notification_repository: Arc<T4>,
+        events: Arc<MissionEventHub>,
     ) -> Self {
         Self {
             crew_operation_repository,
+            authorization: MissionAuthorization::new(Arc::clone(&mission_viewing_repository)),
             mission_viewing_repository,
             mission_submissions_repository,
+            events,
+            notifier: Notifier::new(notification_repository),
         }
     }
 
-    pub async fn join(&self, mission_id: i32, brawler_id: i32) -> Result<()> {
+    pub async fn join(&self, mission_id: i32, brawler_id: i32) -> DomainResult<()> {
         let mission = self.mission_viewing_repository.view_detail(mission_id, Some(brawler_id)).await?;
 
         if mission.is_joined {
@@ -47,51 +86,98 @@ where
         }
 
         if mission.chief_id == brawler_id {
-            return Err(anyhow::anyhow!(
-                "The Chief can not join in his own mission as a crew member!!"
+            return Err(DomainError::conflict(
+                "The Chief can not join in his own mission as a crew member!!",
             ));
         }
 
+        match mission.visibility.parse()? {
+            MissionVisibility::Public => {}
+            MissionVisibility::RequestToJoin => {
+                return Err(DomainError::forbidden(
+                    "This mission reviews new members, send a join request instead",
+                ));
+            }
+            MissionVisibility::InviteOnly => {
+                return Err(DomainError::not_found("Mission not found"));
+            }
+        }
+
+        // Anyone already queued gets a freed seat before a newcomer does
+        self.promote_waitlist(&mission).await?;
+        self.ensure_room(&mission).await?;
+
+        self.crew_operation_repository
+            .join(Self::new_member(mission_id, brawler_id))
+            .await?;
+        self.events.publish(mission_id, MissionEvent::MemberJoined { brawler_id });
+
+        Ok(())
+    }
+
+    async fn ensure_room(&self, mission: &MissionModel) -> DomainResult<()> {
         let member_count = self
             .mission_viewing_repository
-            .member_counting(mission_id)
+            .member_counting(mission.id)
             .await?;
 
-        let mission_status_condition = mission.status == MissionStatuses::Open.to_string()
-            || mission.status == MissionStatuses::Failed.to_string();
-        if !mission_status_condition {
-            return Err(anyhow::anyhow!("Mission is not joinable"));
+        let status: MissionStatuses = mission.status.parse()?;
+        if !status.is_joinable() {
+            return Err(DomainError::conflict("Mission is not joinable"));
         }
         let member_count_condition = (member_count as i32) < mission.max_members;
         if !member_count_condition {
-            return Err(anyhow::anyhow!("Mission is full"));
+            return Err(DomainError::conflict(
+                "Mission is full, join the waitlist to get the next free seat",
+            ));
         }
 
-        self.crew_operation_repository
-            .join(CrewMemberShips {
-                mission_id,
-                brawler_id,
-                joined_at: Local::now().naive_local(),
-                role: "Member".to_string(),
-            })
+        Ok(())
+    }
+
+    async fn promote_waitlist(&self, mission: &MissionModel) -> DomainResult<()> {
+        let status: MissionStatuses = mission.status.parse()?;
+        if !status.is_joinable() || mission.visibility != MissionVisibility::Public.to_string() {
+            return Ok(());
+        }
+
+        let promoted = self
+            .crew_operation_repository
+            .promote_from_waitlist(mission.id)
             .await?;
+        if !promoted.is_empty() {
+            tracing::info!(
+                "Promoted {:?} from the waitlist of mission {}",
+                promoted,
+                mission.id
+            );
+        }
+        for brawler_id in promoted {
+            self.events.publish(mission.id, MissionEvent::MemberJoined { brawler_id });
+        }
 
         Ok(())
     }
 
-    pub async fn leave(&self, mission_id: i32, brawler_id: i32) -> Result<()> {
+    fn new_member(mission_id: i32, brawler_id: i32) -> CrewMemberShips {
+        CrewMemberShips {
+            mission_id,
+            brawler_id,
+            joined_at: Local::now().naive_local(),
+            role: CrewRole::Member.to_string(),
+        }
+    }
+
+    pub async fn leave(&self, mission_id: i32, brawler_id: i32) -> DomainResult<()> {
         let mission = self.mission_viewing_repository.view_detail(mission_id, Some(brawler_id)).await?;
 
         if !mission.is_joined {
-            return Err(anyhow::anyhow!("You are not a member of this mission"));
+            return Err(DomainError::forbidden("You are not a member of this mission"));
         }
 
-        let leaving_condition = mission.status == MissionStatuses::Open.to_string()
-            || mission.status == MissionStatuses::InProgress.to_string()
-            || mission.status == MissionStatuses::Failed.to_string();
-
-        if !leaving_condition {
-            return Err(anyhow::anyhow!("Mission is not leavable in its current state"));
+        let status: MissionStatuses = mission.status.parse()?;
+        if !status.is_leavable() {
+            return Err(DomainError::conflict("Mission is not leavable in its current state"));
         }
 
         self.crew_operation_repository
@@ -102,29 +188,73 @@ where
                 role: "".to_string(), // Role doesn't matter for leave
             })
             .await?;
+        self.events.publish(mission_id, MissionEvent::MemberLeft { brawler_id });
+
+        self.promote_waitlist(&mission).await?;
 
         Ok(())
     }
 
-    pub async fn update_role(&self, mission_id: i32, brawler_id: i32, role: String, chief_id: i32) -> Result<()> {
-        let mission = self.mission_viewing_repository.view_detail(mission_id, None).await?;
-        if mission.chief_id != chief_id {
-            return Err(anyhow::anyhow!("Only the Chief can update roles"));
+    pub async fn update_role(&self, mission_id: i32, brawler_id: i32, role: String, chief_id: i32) -> DomainResult<()> {
+        let (mission, _) = self
+            .authorization
+            .require(mission_id, chief_id, Permission::ManageRoles)
+            .await?;
+
+        let role: CrewRole = role
+            .parse()
+            .map_err(|e: anyhow::Error| DomainError::validation(e.to_string()))?;
+        if role == CrewRole::Chief {
+            return Err(DomainError::validation(
+                "Chief is not a crew role; transfer chieftainship instead",
+            ));
         }
 
-        self.crew_operation_repository.update_role(mission_id, brawler_id, role).await?;
+        let current = match self.authorization.role_in(&mission, brawler_id).await? {
+            None => return Err(DomainError::not_found("Brawler is not a member of this mission")),
+            Some(CrewRole::Chief) => {
+                return Err(DomainError::validation("The Chief's role can not be changed"));
+            }
+            Some(current) => current,
+        };
+
+        self.crew_operation_repository
+            .update_role(mission_id, brawler_id, role.to_string())
+            .await?;
+
+        if current != role {
+            self.notifier
+                .send(
+                    vec![brawler_id],
+                    NotificationKinds::RoleChanged,
+                    Some(mission_id),
+                    format!("Your role in \"{}\" is now {}", mission.name, role),
+                )
+                .await;
+        }
         Ok(())
     }
 
-    pub async fn kick(&self, mission_id: i32, brawler_id: i32, chief_id: i32) -> Result<()> {
-        let mission = self.mission_viewing_repository.view_detail(mission_id, None).await?;
-        
-        if mission.chief_id != chief_id {
-            return Err(anyhow::anyhow!("Only the Chief can kick members"));
+    pub async fn kick(&self, mission_id: i32, brawler_id: i32, chief_id: i32) -> DomainResult<()> {
+        let (mission, kicker_role) = self
+            .authorization
+            .require(mission_id, chief_id, Permission::KickMembers)
+            .await?;
+
+        if brawler_id == chief_id {
+            return Err(DomainError::validation("You cannot kick yourself"));
         }
 
-        if mission.chief_id == brawler_id {
-            return Err(anyhow::anyhow!("The Chief cannot kick themselves"));
+        let target_role = self
+            .authorization
+            .role_in(&mission, brawler_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Brawler is not a member of this mission"))?;
+        if !kicker_role.outranks(&target_role) {
+            return Err(DomainError::forbidden(format!(
+                "{} role can not kick a {}",
+                kicker_role, target_role
+            )));
         }
 
         // Cleanup submissions before kicking
@@ -140,7 +270,578 @@ where
                 role: "".to_string(),
             })
             .await?;
+        self.events.publish(mission_id, MissionEvent::MemberKicked { brawler_id });
+        // No mission link, the brawler can no longer open it
+        self.notifier
+            .send(
+                vec![brawler_id],
+                NotificationKinds::RemovedFromMission,
+                None,
+                format!("You were removed from \"{}\"", mission.name),
+            )
+            .await;
+
+        self.promote_waitlist(&mission).await?;
 
         Ok(())
     }
+
+    pub async fn transfer_chief(
+        &self,
+        mission_id: i32,
+        chief_id: i32,
+        transfer_chief_model: TransferChiefModel,
+    ) -> DomainResult<ChiefTransferEntity> {
+        let (mission, _) = self
+            .authorization
+            .require(mission_id, chief_id, Permission::TransferChieftainship)
+            .await?;
+
+        if transfer_chief_model.to_brawler_id == chief_id {
+            return Err(DomainError::validation("You are already the Chief"));
+        }
+        if transfer_chief_model.old_chief_role == Some(CrewRole::Chief) {
+            return Err(DomainError::validation("A mission can only have one Chief"));
+        }
+        if self
+            .authorization
+            .role_in(&mission, transfer_chief_model.to_brawler_id)
+            .await?
+            .is_none()
+        {
+            return Err(DomainError::validation(
+                "The new Chief must already be a member of the crew",
+            ));
+        }
+
+        let transfer = self
+            .crew_operation_repository
+            .create_chief_transfer(AddChiefTransferEntity {
+                mission_id,
+                from_brawler_id: chief_id,
+                to_brawler_id: transfer_chief_model.to_brawler_id,
+                old_chief_role: transfer_chief_model
+                    .old_chief_role
+                    .map(|role| role.to_string()),
+            })
+            .await?;
+
+        if transfer_chief_model.require_acceptance {
+            return Ok(transfer);
+        }
+
+        self.complete_chief_transfer(transfer.id).await
+    }
+
+    async fn pending_transfer(
+        &self,
+        mission_id: i32,
+        transfer_id: i32,
+    ) -> DomainResult<ChiefTransferEntity> {
+        let transfer = self
+            .crew_operation_repository
+            .find_chief_transfer(transfer_id)
+            .await?;
+
+        if transfer.mission_id != mission_id {
+            return Err(DomainError::not_found("Transfer not found"));
+        }
+        if transfer.status != ChiefTransferStatuses::Pending.to_string() {
+            return Err(DomainError::conflict("Transfer is no longer pending"));
+        }
+
+        Ok(transfer)
+    }
+
+    async fn complete_chief_transfer(&self, transfer_id: i32) -> DomainResult<ChiefTransferEntity> {
+        if !self
+            .crew_operation_repository
+            .complete_chief_transfer(transfer_id)
+            .await?
+        {
+            // Whatever made it stale, the offer can't be honoured any more
+            self.crew_operation_repository
+                .resolve_chief_transfer(transfer_id, ChiefTransferStatuses::Cancelled.to_string())
+                .await?;
+            return Err(DomainError::conflict(
+                "The mission or its crew changed, the transfer was cancelled",
+            ));
+        }
+
+        let transfer = self
+            .crew_operation_repository
+            .find_chief_transfer(transfer_id)
+            .await?;
+        Ok(transfer)
+    }
+
+    pub async fn accept_chief_transfer(
+        &self,
+        mission_id: i32,
+        transfer_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<ChiefTransferEntity> {
+        let transfer = self.pending_transfer(mission_id, transfer_id).await?;
+        if transfer.to_brawler_id != brawler_id {
+            return Err(DomainError::forbidden("This transfer was offered to someone else"));
+        }
+
+        self.complete_chief_transfer(transfer_id).await
+    }
+
+    pub async fn decline_chief_transfer(
+        &self,
+        mission_id: i32,
+        transfer_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<()> {
+        let transfer = self.pending_transfer(mission_id, transfer_id).await?;
+        if transfer.to_brawler_id != brawler_id {
+            return Err(DomainError::forbidden("This transfer was offered to someone else"));
+        }
+
+        self.resolve_pending(transfer_id, ChiefTransferStatuses::Declined)
+            .await
+    }
+
+    pub async fn cancel_chief_transfer(
+        &self,
+        mission_id: i32,
+        transfer_id: i32,
+        chief_id: i32,
+    ) -> DomainResult<()> {
+        let transfer = self.pending_transfer(mission_id, transfer_id).await?;
+        if transfer.from_brawler_id != chief_id {
+            return Err(DomainError::forbidden("Only the Chief who offered it can cancel a transfer"));
+        }
+
+        self.resolve_pending(transfer_id, ChiefTransferStatuses::Cancelled)
+            .await
+    }
+
+    async fn resolve_pending(
+        &self,
+        transfer_id: i32,
+        status: ChiefTransferStatuses,
+    ) -> DomainResult<()> {
+        if !self
+            .crew_operation_repository
+            .resolve_chief_transfer(transfer_id, status.to_string())
+            .await?
+        {
+            return Err(DomainError::conflict("Transfer is no longer pending"));
+        }
+        Ok(())
+    }
+
+    pub async fn chief_transfers(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<Vec<ChiefTransferEntity>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+
+        let transfers = self
+            .crew_operation_repository
+            .chief_transfers(mission_id)
+            .await?;
+        Ok(transfers)
+    }
+
+    pub async fn create_invitation(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        create_invitation_model: CreateInvitationModel,
+    ) -> DomainResult<MissionInvitationEntity> {
+        let (mission, _) = self
+            .authorization
+            .require(mission_id, brawler_id, Permission::ManageAdmissions)
+            .await?;
+
+        if let Some(invitee_id) = create_invitation_model.invitee_id
+            && self.authorization.role_in(&mission, invitee_id).await?.is_some()
+        {
+            return Err(DomainError::conflict("Brawler is already in this mission"));
+        }
+        if create_invitation_model.max_uses.is_some_and(|max_uses| max_uses < 1) {
+            return Err(DomainError::validation("max_uses must be at least 1"));
+        }
+
+        let expires_in_hours = create_invitation_model
+            .expires_in_hours
+            .unwrap_or(Self::INVITATION_DEFAULT_HOURS);
+        if !(1..=Self::INVITATION_MAX_HOURS).contains(&expires_in_hours) {
+            return Err(DomainError::validation(format!(
+                "Invitations can last between 1 and {} hours",
+                Self::INVITATION_MAX_HOURS
+            )));
+        }
+
+        let invitation = self
+            .crew_operation_repository
+            .create_invitation(AddMissionInvitationEntity {
+                mission_id,
+                code: generate_opaque_token(),
+                created_by: brawler_id,
+                invitee_id: create_invitation_model.invitee_id,
+                max_uses: create_invitation_model.max_uses,
+                expires_at: Utc::now().naive_utc() + Duration::hours(expires_in_hours),
+            })
+            .await?;
+        Ok(invitation)
+    }
+
+    pub async fn invitations(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<Vec<MissionInvitationEntity>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ManageAdmissions)
+            .await?;
+
+        let invitations = self
+            .crew_operation_repository
+            .invitations(mission_id)
+            .await?;
+        Ok(invitations)
+    }
+
+    pub async fn revoke_invitation(
+        &self,
+        mission_id: i32,
+        invitation_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<()> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ManageAdmissions)
+            .await?;
+
+        if !self
+            .crew_operation_repository
+            .revoke_invitation(mission_id, invitation_id)
+            .await?
+        {
+            return Err(DomainError::not_found("Invitation not found"));
+        }
+        Ok(())
+    }
+
+    /// Joins through an invitation code, whatever the mission's visibility. Returns the mission id.
+    pub async fn join_with_invitation(&self, code: &str, brawler_id: i32) -> DomainResult<i32> {
+        let invitation = self
+            .crew_operation_repository
+            .find_invitation_by_code(code)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Invitation not found"))?;
+
+        if invitation
+            .invitee_id
+            .is_some_and(|invitee_id| invitee_id != brawler_id)
+        {
+            return Err(DomainError::forbidden("This invitation was sent to someone else"));
+        }
+        if !invitation.is_usable(Utc::now().naive_utc()) {
+            return Err(DomainError::conflict("Invitation has expired"));
+        }
+
+        let mission = self
+            .mission_viewing_repository
+            .view_detail(invitation.mission_id, Some(brawler_id))
+            .await?;
+        if mission.is_joined {
+            return Ok(mission.id);
+        }
+        if mission.chief_id == brawler_id {
+            return Err(DomainError::conflict(
+                "The Chief can not join in his own mission as a crew member!!",
+            ));
+        }
+
+        self.ensure_room(&mission).await?;
+
+        if !self
+            .crew_operation_repository
+            .redeem_invitation(invitation.id, Self::new_member(mission.id, brawler_id))
+            .await?
+        {
+            return Err(DomainError::conflict("Invitation has expired"));
+        }
+        self.events.publish(mission.id, MissionEvent::MemberJoined { brawler_id });
+
+        Ok(mission.id)
+    }
+
+    pub async fn request_to_join(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        join_request_model: JoinRequestModel,
+    ) -> DomainResult<MissionJoinRequestEntity> {
+        let mission = self
+            .mission_viewing_repository
+            .view_detail(mission_id, Some(brawler_id))
+            .await?;
+
+        if mission.is_joined || mission.chief_id == brawler_id {
+            return Err(DomainError::conflict("You are already in this mission"));
+        }
+
+        match mission.visibility.parse()? {
+            MissionVisibility::RequestToJoin => {}
+            MissionVisibility::Public => {
+                return Err(DomainError::validation(
+                    "This mission is public, join it directly",
+                ));
+            }
+            MissionVisibility::InviteOnly => {
+                return Err(DomainError::not_found("Mission not found"));
+            }
+        }
+
+        let status: MissionStatuses = mission.status.parse()?;
+        if !status.is_joinable() {
+            return Err(DomainError::conflict("Mission is not joinable"));
+        }
+
+        let request = self
+            .crew_operation_repository
+            .create_join_request(AddMissionJoinRequestEntity {
+                mission_id,
+                brawler_id,
+                message: join_request_model.message,
+            })
+            .await
+            .map_err(DomainError::from)
+            .map_err(|e| match e {
+                DomainError::Conflict(_) => {
+                    DomainError::conflict("You already have a pending request for this mission")
+                }
+                other => other,
+            })?;
+        Ok(request)
+    }
+
+    pub async fn join_requests(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        filter: &JoinRequestFilter,
+    ) -> DomainResult<Vec<MissionJoinRequestEntity>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ManageAdmissions)
+            .await?;
+
+        let requests = self
+            .crew_operation_repository
+            .join_requests(mission_id, filter.status.as_ref().map(|s| s.to_string()))
+            .await?;
+        Ok(requests)
+    }
+
+    async fn pending_join_request(
+        &self,
+        mission_id: i32,
+        request_id: i32,
+    ) -> DomainResult<MissionJoinRequestEntity> {
+        let request = self
+            .crew_operation_repository
+            .find_join_request(request_id)
+            .await?;
+
+        if request.mission_id != mission_id {
+            return Err(DomainError::not_found("Join request not found"));
+        }
+        if request.status != JoinRequestStatuses::Pending.to_string() {
+            return Err(DomainError::conflict("Join request is no longer pending"));
+        }
+
+        Ok(request)
+    }
+
+    pub async fn approve_join_request(
+        &self,
+        mission_id: i32,
+        request_id: i32,
+        reviewer_id: i32,
+    ) -> DomainResult<()> {
+        let (mission, _) = self
+            .authorization
+            .require(mission_id, reviewer_id, Permission::ManageAdmissions)
+            .await?;
+        let request = self.pending_join_request(mission_id, request_id).await?;
+
+        self.ensure_room(&mission).await?;
+
+        if !self
+            .crew_operation_repository
+            .approve_join_request(
+                request_id,
+                reviewer_id,
+                Self::new_member(mission_id, request.brawler_id),
+            )
+            .await?
+        {
+            return Err(DomainError::conflict("Join request is no longer pending"));
+        }
+        self.events.publish(mission_id, MissionEvent::MemberJoined { brawler_id: request.brawler_id });
+        Ok(())
+    }
+
+    pub async fn reject_join_request(
+        &self,
+        mission_id: i32,
+        request_id: i32,
+        reviewer_id: i32,
+    ) -> DomainResult<()> {
+        self.authorization
+            .require(mission_id, reviewer_id, Permission::ManageAdmissions)
+            .await?;
+        self.pending_join_request(mission_id, request_id).await?;
+
+        self.resolve_join_request(request_id, Some(reviewer_id), JoinRequestStatuses::Rejected)
+            .await
+    }
+
+    pub async fn cancel_join_request(
+        &self,
+        mission_id: i32,
+        request_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<()> {
+        let request = self.pending_join_request(mission_id, request_id).await?;
+        if request.brawler_id != brawler_id {
+            return Err(DomainError::forbidden("You can only cancel your own join request"));
+        }
+
+        self.resolve_join_request(request_id, None, JoinRequestStatuses::Cancelled)
+            .await
+    }
+
+    async fn resolve_join_request(
+        &self,
+        request_id: i32,
+        reviewer_id: Option<i32>,
+        status: JoinRequestStatuses,
+    ) -> DomainResult<()> {
+        if !self
+            .crew_operation_repository
+            .resolve_join_request(request_id, reviewer_id, status.to_string())
+            .await?
+        {
+            return Err(DomainError::conflict("Join request is no longer pending"));
+        }
+        Ok(())
+    }
+
+    pub async fn join_waitlist(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<WaitlistStatusModel> {
+        let mission = self
+            .mission_viewing_repository
+            .view_detail(mission_id, Some(brawler_id))
+            .await?;
+
+        if mission.is_joined || mission.chief_id == brawler_id {
+            return Err(DomainError::conflict("You are already in this mission"));
+        }
+
+        match mission.visibility.parse()? {
+            MissionVisibility::Public => {}
+            MissionVisibility::RequestToJoin => {
+                return Err(DomainError::forbidden(
+                    "This mission reviews new members, send a join request instead",
+                ));
+            }
+            MissionVisibility::InviteOnly => {
+                return Err(DomainError::not_found("Mission not found"));
+            }
+        }
+
+        let status: MissionStatuses = mission.status.parse()?;
+        if !status.is_joinable() {
+            return Err(DomainError::conflict("Mission is not joinable"));
+        }
+
+        let member_count = self
+            .mission_viewing_repository
+            .member_counting(mission_id)
+            .await?;
+        if (member_count as i32) < mission.max_members {
+            return Err(DomainError::conflict("Mission has free seats, join it directly"));
+        }
+
+        self.crew_operation_repository
+            .join_waitlist(AddMissionWaitlistEntity {
+                mission_id,
+                brawler_id,
+            })
+            .await
+            .map_err(DomainError::from)
+            .map_err(|e| match e {
+                DomainError::Conflict(_) => {
+                    DomainError::conflict("You are already on the waitlist")
+                }
+                other => other,
+            })?;
+
+        self.waitlist_status(mission_id, brawler_id).await
+    }
+
+    pub async fn leave_waitlist(&self, mission_id: i32, brawler_id: i32) -> DomainResult<()> {
+        if !self
+            .crew_operation_repository
+            .leave_waitlist(mission_id, brawler_id)
+            .await?
+        {
+            return Err(DomainError::not_found("You are not on the waitlist"));
+        }
+        Ok(())
+    }
+
+    pub async fn waitlist_status(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<WaitlistStatusModel> {
+        let mission = self
+            .mission_viewing_repository
+            .view_detail(mission_id, Some(brawler_id))
+            .await?;
+
+        // Invite-only missions don't exist for outsiders, their queue included. Nobody
+        // is left waiting on one: the waitlist is dropped when a mission stops being Public
+        if !mission.is_visible_to(Some(brawler_id)) {
+            return Err(DomainError::not_found("Mission not found"));
+        }
+
+        let position = self
+            .crew_operation_repository
+            .waitlist_position(mission_id, brawler_id)
+            .await?;
+        let waiting = self
+            .crew_operation_repository
+            .waitlist_size(mission_id)
+            .await?;
+
+        Ok(WaitlistStatusModel { position, waiting })
+    }
+
+    pub async fn waitlist(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<Vec<MissionWaitlistEntity>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ManageAdmissions)
+            .await?;
+
+        let entries = self.crew_operation_repository.waitlist(mission_id).await?;
+        Ok(entries)
+    }
 }
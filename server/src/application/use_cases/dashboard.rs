@@ -1,7 +1,7 @@
 use std::sync::Arc;
-use anyhow::Result;
 
 use crate::domain::{
+    errors::DomainResult,
     repositories::dashboard::DashboardRepository,
     value_objects::dashboard_model::DashboardSummary,
 };
@@ -21,7 +21,8 @@ where
         Self { dashboard_repository }
     }
 
-    pub async fn get_summary(&self, brawler_id: i32) -> Result<DashboardSummary> {
-        self.dashboard_repository.get_summary(brawler_id).await
+    pub async fn get_summary(&self, brawler_id: i32) -> DomainResult<DashboardSummary> {
+        let summary = self.dashboard_repository.get_summary(brawler_id).await?;
+        Ok(summary)
     }
 }
@@ -0,0 +1,244 @@
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDateTime, NaiveTime, Utc};
+
+use crate::{
+    application::services::notifier::Notifier,
+    config::config_model::EmailNotificationEnv,
+    domain::{
+        entities::{mail_outbox::{AddMailOutboxEntity, MailOutboxEntity}, missions::MissionEntity},
+        errors::DomainResult,
+        repositories::{
+            mail_outbox::MailOutboxRepository, mission_management::MissionManagementRepository,
+            mission_viewing::MissionViewingRepository, notifications::NotificationRepository,
+        },
+        services::mailer::{MailMessage, Mailer},
+        value_objects::{
+            email_model::{DigestRecipientModel, notification_mail_body},
+            notification_kinds::NotificationKinds,
+        },
+    },
+};
+
+const BATCH_SIZE: i64 = 50;
+// Time a claimed mail is hidden from other workers
+const CLAIM_LEASE_MINUTES: i64 = 5;
+// Failed mail backs off exponentially up to six hours
+const MAX_RETRY_MINUTES: i64 = 6 * 60;
+// Notifications listed in a single digest
+const DIGEST_LIMIT: i64 = 50;
+
+/// Works the mail channel: hands queued mail to the mailer, and produces
+/// daily digests and mission deadline reminders.
+pub struct EmailNotificationUseCase<T1, T2, T3, T4, T5>
+where
+    T1: MailOutboxRepository + Send + Sync,
+    T2: NotificationRepository + Send + Sync,
+    T3: MissionManagementRepository + Send + Sync,
+    T4: MissionViewingRepository + Send + Sync,
+    T5: Mailer + Send + Sync,
+{
+    mail_outbox_repository: Arc<T1>,
+    notification_repository: Arc<T2>,
+    mission_management_repository: Arc<T3>,
+    mission_viewing_repository: Arc<T4>,
+    mailer: Arc<T5>,
+    notifier: Notifier<T2>,
+    env: EmailNotificationEnv,
+}
+
+impl<T1, T2, T3, T4, T5> EmailNotificationUseCase<T1, T2, T3, T4, T5>
+where
+    T1: MailOutboxRepository + Send + Sync,
+    T2: NotificationRepository + Send + Sync,
+    T3: MissionManagementRepository + Send + Sync,
+    T4: MissionViewingRepository + Send + Sync,
+    T5: Mailer + Send + Sync,
+{
+    pub fn new(
+        mail_outbox_repository: Arc<T1>,
+        notification_repository: Arc<T2>,
+        mission_management_repository: Arc<T3>,
+        mission_viewing_repository: Arc<T4>,
+        mailer: Arc<T5>,
+        env: EmailNotificationEnv,
+    ) -> Self {
+        Self {
+            mail_outbox_repository,
+            notifier: Notifier::new(Arc::clone(&notification_repository)),
+            notification_repository,
+            mission_management_repository,
+            mission_viewing_repository,
+            mailer,
+            env,
+        }
+    }
+
+    /// Sends every queued mail that is due. Returns how many were accepted by the mailer.
+    pub async fn process_outbox(&self) -> DomainResult<usize> {
+        let mut sent = 0;
+        loop {
+            let due = self
+                .mail_outbox_repository
+                .claim_due(BATCH_SIZE, Duration::minutes(CLAIM_LEASE_MINUTES))
+                .await?;
+            let claimed = due.len() as i64;
+
+            for mail in due {
+                if self.deliver(mail).await? {
+                    sent += 1;
+                }
+            }
+
+            if claimed < BATCH_SIZE {
+                return Ok(sent);
+            }
+        }
+    }
+
+    async fn deliver(&self, mail: MailOutboxEntity) -> DomainResult<bool> {
+        let message = MailMessage {
+            to: mail.to_address.clone(),
+            subject: mail.subject.clone(),
+            body: mail.body.clone(),
+        };
+
+        let error = match self.mailer.send(message).await {
+            Ok(()) => {
+                self.mail_outbox_repository.mark_sent(mail.id).await?;
+                return Ok(true);
+            }
+            Err(e) => e.to_string(),
+        };
+
+        let attempts = mail.attempts + 1;
+        tracing::warn!(
+            "Mail {} to {} failed (attempt {}): {}",
+            mail.id,
+            mail.to_address,
+            attempts,
+            error
+        );
+        let next_attempt_at = (attempts < self.env.max_attempts).then(|| {
+            let delay = 2i64.pow(mail.attempts.clamp(0, 11) as u32).min(MAX_RETRY_MINUTES);
+            Utc::now().naive_utc() + Duration::minutes(delay)
+        });
+
+        self.mail_outbox_repository
+            .mark_failed(mail.id, error, next_attempt_at)
+            .await?;
+        Ok(false)
+    }
+
+    /// Queues a digest for every subscriber who has not had one since the
+    /// latest digest hour. Returns how many were queued.
+    pub async fn queue_digests(&self) -> DomainResult<usize> {
+        let cutoff = latest_digest_time(Utc::now().naive_utc(), self.env.digest_hour);
+        let recipients = self.mail_outbox_repository.claim_digests(cutoff).await?;
+        let mut mails = Vec::new();
+        for recipient in recipients {
+            if let Some(mail) = self.digest_for(recipient, cutoff).await? {
+                mails.push(mail);
+            }
+        }
+
+        let queued = mails.len();
+        self.mail_outbox_repository.enqueue(mails).await?;
+        Ok(queued)
+    }
+
+    /// Unread notifications since the previous digest, or nothing when there are none.
+    async fn digest_for(
+        &self,
+        recipient: DigestRecipientModel,
+        cutoff: NaiveDateTime,
+    ) -> DomainResult<Option<AddMailOutboxEntity>> {
+        let since = recipient.since.unwrap_or(cutoff - Duration::days(1));
+        let unread: Vec<_> = self
+            .notification_repository
+            .list(recipient.brawler_id, true, DIGEST_LIMIT, 0)
+            .await?
+            .into_iter()
+            .filter(|notification| notification.created_at > since)
+            .collect();
+        if unread.is_empty() {
+            return Ok(None);
+        }
+
+        let total = self.notification_repository.count(recipient.brawler_id, true).await?;
+        let mut summary = format!("You have {} unread notification(s):\r\n", total);
+        for notification in &unread {
+            summary.push_str(&format!(
+                "\r\n- {} ({} UTC)",
+                notification.message,
+                notification.created_at.format("%Y-%m-%d %H:%M")
+            ));
+        }
+
+        Ok(Some(AddMailOutboxEntity {
+            brawler_id: Some(recipient.brawler_id),
+            to_address: recipient.email,
+            subject: "Your daily summary".to_string(),
+            body: notification_mail_body(&recipient.display_name, &summary),
+        }))
+    }
+
+    /// Notifies the crews of missions that end within the reminder window.
+    /// Returns how many missions were reminded.
+    pub async fn remind_deadlines(&self) -> DomainResult<usize> {
+        let until = Utc::now().naive_utc() + Duration::hours(self.env.deadline_reminder_hours);
+        let missions = self
+            .mission_management_repository
+            .claim_deadline_reminders(until)
+            .await?;
+
+        for mission in &missions {
+            self.remind_crew(mission).await;
+        }
+
+        Ok(missions.len())
+    }
+
+    /// The reminder is already claimed, so failing to reach the crew is only logged.
+    async fn remind_crew(&self, mission: &MissionEntity) {
+        let Some(end_date) = mission.end_date else {
+            return;
+        };
+        let crew = match self.mission_viewing_repository.get_mission_crew(mission.id).await {
+            Ok(crew) => crew,
+            Err(e) => {
+                tracing::warn!("Failed to load the crew of mission {}: {:?}", mission.id, e);
+                return;
+            }
+        };
+
+        let mut recipients: Vec<i32> = crew.into_iter().map(|member| member.id).collect();
+        if !recipients.contains(&mission.chief_id) {
+            recipients.push(mission.chief_id);
+        }
+        self.notifier
+            .send(
+                recipients,
+                NotificationKinds::DeadlineApproaching,
+                Some(mission.id),
+                format!(
+                    "\"{}\" is due {} UTC",
+                    mission.name,
+                    end_date.format("%Y-%m-%d %H:%M")
+                ),
+            )
+            .await;
+    }
+}
+
+/// Today's digest time, or yesterday's when today's has not come yet.
+fn latest_digest_time(now: NaiveDateTime, digest_hour: u32) -> NaiveDateTime {
+    let today = now
+        .date()
+        .and_time(NaiveTime::from_hms_opt(digest_hour, 0, 0).unwrap_or_default());
+    if now >= today {
+        today
+    } else {
+        today - Duration::days(1)
+    }
+}
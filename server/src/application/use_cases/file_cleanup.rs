@@ -0,0 +1,171 @@
+use crate::domain::{
+    errors::DomainResult,
+    repositories::{
+        brawlers::BrawlerRepository, file_deletions::FileDeletionRepository,
+        mission_submissions::MissionSubmissionsRepository,
+    },
+    services::file_storage::FileStorage,
+    value_objects::file_stores::FileStores,
+};
+use chrono::{Duration, Utc};
+use std::{collections::HashSet, sync::Arc};
+
+// Deletions handled per round trip to the queue
+const BATCH_SIZE: i64 = 50;
+// Time a claimed deletion is hidden from other workers
+const CLAIM_LEASE_MINUTES: i64 = 5;
+// Failed deletions back off exponentially up to a day
+const MAX_RETRY_MINUTES: i64 = 24 * 60;
+const SUBMISSIONS_PREFIX: &str = "missions/";
+const AVATARS_PREFIX: &str = "avatar/";
+
+/// Removes files nothing points at any more: works off the deletion queue
+/// and sweeps storage for files the queue never heard about.
+pub struct FileCleanupUseCase<T1, T2, T3, T4, T5>
+where
+    T1: FileDeletionRepository + Send + Sync,
+    T2: MissionSubmissionsRepository + Send + Sync,
+    T3: BrawlerRepository + Send + Sync,
+    T4: FileStorage + Send + Sync,
+    T5: FileStorage + Send + Sync,
+{
+    file_deletion_repository: Arc<T1>,
+    mission_submissions_repository: Arc<T2>,
+    brawler_repository: Arc<T3>,
+    file_storage: Arc<T4>,
+    // Avatars live on Cloudinary, which may not be configured
+    avatar_storage: Option<Arc<T5>>,
+}
+
+impl<T1, T2, T3, T4, T5> FileCleanupUseCase<T1, T2, T3, T4, T5>
+where
+    T1: FileDeletionRepository + Send + Sync,
+    T2: MissionSubmissionsRepository + Send + Sync,
+    T3: BrawlerRepository + Send + Sync,
+    T4: FileStorage + Send + Sync,
+    T5: FileStorage + Send + Sync,
+{
+    pub fn new(
+        file_deletion_repository: Arc<T1>,
+        mission_submissions_repository: Arc<T2>,
+        brawler_repository: Arc<T3>,
+        file_storage: Arc<T4>,
+        avatar_storage: Option<Arc<T5>>,
+    ) -> Self {
+        Self {
+            file_deletion_repository,
+            mission_submissions_repository,
+            brawler_repository,
+            file_storage,
+            avatar_storage,
+        }
+    }
+
+    /// Deletes every queued file that is due. Returns how many were removed.
+    pub async fn process_deletions(&self) -> DomainResult<usize> {
+        let mut removed = 0;
+        loop {
+            let due = self
+                .file_deletion_repository
+                .claim_due(BATCH_SIZE, Duration::minutes(CLAIM_LEASE_MINUTES))
+                .await?;
+            let claimed = due.len() as i64;
+
+            for deletion in due {
+                match self.delete_stored(&deletion.store, &deletion.storage_key).await {
+                    Ok(()) => {
+                        self.file_deletion_repository.complete(deletion.id).await?;
+                        removed += 1;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to delete stored file {} (attempt {}): {}",
+                            deletion.storage_key,
+                            deletion.attempts + 1,
+                            e
+                        );
+                        let delay = 2i64.pow(deletion.attempts.clamp(0, 11) as u32).min(MAX_RETRY_MINUTES);
+                        self.file_deletion_repository
+                            .retry_at(
+                                deletion.id,
+                                e.to_string(),
+                                Utc::now().naive_utc() + Duration::minutes(delay),
+                            )
+                            .await?;
+                    }
+                }
+            }
+
+            if claimed < BATCH_SIZE {
+                return Ok(removed);
+            }
+        }
+    }
+
+    /// Queues stored files older than `grace` that no row refers to.
+    /// Returns how many were queued.
+    pub async fn reconcile(&self, grace: Duration) -> DomainResult<usize> {
+        let referenced = self.mission_submissions_repository.referenced_storage_keys().await?;
+        let mut queued = self
+            .sweep(FileStores::Files, self.file_storage.as_ref(), SUBMISSIONS_PREFIX, referenced, grace)
+            .await?;
+
+        if let Some(avatar_storage) = &self.avatar_storage {
+            // Avatar rows hold the bare public id, storage keys carry the resource type
+            let referenced = self
+                .brawler_repository
+                .avatar_public_ids()
+                .await?
+                .into_iter()
+                .map(|public_id| format!("image/{}", public_id))
+                .collect();
+            queued += self
+                .sweep(FileStores::Avatars, avatar_storage.as_ref(), AVATARS_PREFIX, referenced, grace)
+                .await?;
+        }
+
+        Ok(queued)
+    }
+
+    async fn sweep<S>(
+        &self,
+        store: FileStores,
+        storage: &S,
+        prefix: &str,
+        referenced: Vec<String>,
+        grace: Duration,
+    ) -> DomainResult<usize>
+    where
+        S: FileStorage + Send + Sync,
+    {
+        let mut known: HashSet<String> = referenced.into_iter().collect();
+        known.extend(self.file_deletion_repository.queued_keys(store).await?);
+
+        // Recent files may belong to an upload whose row is not written yet
+        let cutoff = Utc::now() - grace;
+        let orphans: Vec<String> = storage
+            .list(prefix)
+            .await?
+            .into_iter()
+            .filter(|object| object.last_modified < cutoff && !known.contains(&object.key))
+            .map(|object| object.key)
+            .collect();
+
+        let count = orphans.len();
+        if count > 0 {
+            tracing::info!("Queueing {} orphaned {} file(s) for deletion", count, store);
+            self.file_deletion_repository.enqueue(store, orphans).await?;
+        }
+        Ok(count)
+    }
+
+    async fn delete_stored(&self, store: &str, key: &str) -> anyhow::Result<()> {
+        match store.parse::<FileStores>()? {
+            FileStores::Files => self.file_storage.delete(key).await,
+            FileStores::Avatars => match &self.avatar_storage {
+                Some(avatar_storage) => avatar_storage.delete(key).await,
+                None => Err(anyhow::anyhow!("Cloudinary env is missing")),
+            },
+        }
+    }
+}
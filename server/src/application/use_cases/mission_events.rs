@@ -0,0 +1,77 @@
+use crate::{
+    application::services::{
+        mission_authorization::MissionAuthorization, mission_events::MissionEventHub,
+    },
+    domain::{
+        errors::DomainResult,
+        repositories::{auth_sessions::AuthSessionRepository, mission_viewing::MissionViewingRepository},
+        value_objects::{crew_roles::Permission, mission_events::MissionEventEnvelope},
+    },
+    infrastructure::jwt::jwt_model::StreamTicket,
+};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+pub struct MissionEventUseCase<T1, T2>
+where
+    T1: MissionViewingRepository + Send + Sync,
+    T2: AuthSessionRepository + Send + Sync,
+{
+    authorization: MissionAuthorization<T1>,
+    auth_session_repository: Arc<T2>,
+    events: Arc<MissionEventHub>,
+}
+
+impl<T1, T2> MissionEventUseCase<T1, T2>
+where
+    T1: MissionViewingRepository + Send + Sync,
+    T2: AuthSessionRepository + Send + Sync,
+{
+    pub fn new(
+        mission_viewing_repository: Arc<T1>,
+        auth_session_repository: Arc<T2>,
+        events: Arc<MissionEventHub>,
+    ) -> Self {
+        Self {
+            authorization: MissionAuthorization::new(mission_viewing_repository),
+            auth_session_repository,
+            events,
+        }
+    }
+
+    /// Only crew who may watch the workspace get a ticket; `session_exp` is the
+    /// expiry of the access token the ticket is traded for.
+    pub async fn issue_ticket(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+        session_id: i32,
+        session_exp: usize,
+    ) -> DomainResult<StreamTicket> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+
+        Ok(StreamTicket::new(brawler_id, session_id, mission_id, session_exp)?)
+    }
+
+    pub async fn subscribe(
+        &self,
+        mission_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<broadcast::Receiver<MissionEventEnvelope>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+
+        Ok(self.events.subscribe(mission_id))
+    }
+
+    /// Checked while a stream is open, so logging out or being signed out elsewhere ends it.
+    pub async fn session_active(&self, session_id: i32, brawler_id: i32) -> DomainResult<bool> {
+        Ok(self
+            .auth_session_repository
+            .is_active(session_id, brawler_id)
+            .await?)
+    }
+}
@@ -1,45 +1,76 @@
 use std::sync::Arc;
 
-use crate::domain::{
-    repositories::{
-        mission_management::MissionManagementRepository, mission_viewing::MissionViewingRepository,
+use crate::{
+    application::services::{
+        mission_authorization::MissionAuthorization, mission_events::MissionEventHub,
+    },
+    config::config_loader::get_upload_env,
+    domain::{
+        errors::{DomainError, DomainResult},
+        repositories::{
+            crew_operation::CrewOperationRepository,
+            mission_management::MissionManagementRepository,
+            mission_viewing::MissionViewingRepository,
+        },
+        value_objects::{
+            crew_roles::Permission,
+            file_types::FileType,
+            mission_events::MissionEvent,
+            mission_model::{
+                AddMissionModel, EditMissionModel, MAX_MISSION_TAGS, MAX_TAG_LENGTH, MissionModel,
+                normalize_tags,
+            },
+            mission_statuses::MissionStatuses,
+            mission_visibility::MissionVisibility,
+        },
     },
-    value_objects::mission_model::{AddMissionModel, EditMissionModel},
 };
 
-pub struct MissionManagementUseCase<T1, T2>
+pub struct MissionManagementUseCase<T1, T2, T3>
 where
     T1: MissionManagementRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
 {
     mission_management_repository: Arc<T1>,
     mission_viewing_repository: Arc<T2>,
+    crew_operation_repository: Arc<T3>,
+    authorization: MissionAuthorization<T2>,
+    events: Arc<MissionEventHub>,
 }
 
-use anyhow::Result;
-
-impl<T1, T2> MissionManagementUseCase<T1, T2>
+impl<T1, T2, T3> MissionManagementUseCase<T1, T2, T3>
 where
     T1: MissionManagementRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
 {
     pub fn new(
         mission_management_repository: Arc<T1>,
         mission_viewing_repository: Arc<T2>,
+        crew_operation_repository: Arc<T3>,
+        events: Arc<MissionEventHub>,
     ) -> Self {
         Self {
             mission_management_repository,
+            crew_operation_repository,
+            authorization: MissionAuthorization::new(Arc::clone(&mission_viewing_repository)),
             mission_viewing_repository,
+            events,
         }
     }
 
-    pub async fn add(&self, chief_id: i32, add_mission_model: AddMissionModel) -> Result<i32> {
+    pub async fn add(&self, chief_id: i32, add_mission_model: AddMissionModel) -> DomainResult<i32> {
         if add_mission_model.name.trim().is_empty() || add_mission_model.name.trim().len() < 3 {
-            return Err(anyhow::anyhow!(
-                "Mission name must be least 4 characters long"
+            return Err(DomainError::validation(
+                "Mission name must be least 4 characters long",
             ));
         }
 
+        ensure_upload_limit(add_mission_model.max_upload_mb)?;
+        ensure_file_types(add_mission_model.allowed_file_types.as_deref())?;
+        ensure_tags(add_mission_model.tags.as_deref())?;
+
         let insert_mission_entity = add_mission_model.to_entity(chief_id);
 
         let result = self
@@ -55,28 +86,37 @@ where
         mission_id: i32,
         chief_id: i32,
         mut edit_mission_model: EditMissionModel,
-    ) -> Result<i32> {
+    ) -> DomainResult<i32> {
+        let (mission, _) = self
+            .authorization
+            .require(mission_id, chief_id, Permission::EditSettings)
+            .await?;
+
         if let Some(name) = edit_mission_model.name {
             if name.trim().is_empty() {
                 edit_mission_model.name = None;
             } else if name.trim().len() < 3 {
-                return Err(anyhow::anyhow!(
-                    "Mission name must be least 4 characters long"
+                return Err(DomainError::validation(
+                    "Mission name must be least 4 characters long",
                 ));
             } else {
                 edit_mission_model.name = Some(name.trim().to_string())
             }
         }
 
+        ensure_upload_limit(edit_mission_model.max_upload_mb)?;
+        ensure_file_types(edit_mission_model.allowed_file_types.as_deref())?;
+        ensure_tags(edit_mission_model.tags.as_deref())?;
 
-
-        let edit_mission_entity = edit_mission_model.to_entity(chief_id);
+        let edit_mission_entity = edit_mission_model.to_entity();
 
         let result = self
             .mission_management_repository
             .edit(mission_id, edit_mission_entity)
             .await?;
 
+        self.settle_waitlist(&mission, chief_id).await?;
+
         Ok(result)
     }
 
@@ -85,24 +125,122 @@ where
         mission_id: i32,
         edit_mission_model: EditMissionModel,
         chief_id: i32,
-    ) -> Result<i32> {
-        let edit_mission_entity = edit_mission_model.to_entity(chief_id);
+    ) -> DomainResult<i32> {
+        let (mission, _) = self
+            .authorization
+            .require(mission_id, chief_id, Permission::EditSettings)
+            .await?;
+
+        ensure_upload_limit(edit_mission_model.max_upload_mb)?;
+        ensure_file_types(edit_mission_model.allowed_file_types.as_deref())?;
+        ensure_tags(edit_mission_model.tags.as_deref())?;
+
+        let edit_mission_entity = edit_mission_model.to_entity();
 
         let result = self
             .mission_management_repository
             .edit(mission_id, edit_mission_entity)
             .await?;
 
+        self.settle_waitlist(&mission, chief_id).await?;
+
         Ok(result)
     }
 
-    pub async fn remove(&self, mission_id: i32, chief_id: i32) -> Result<()> {
+    /// Brings the waitlist in line with an edit, judged on the mission as it is
+    /// afterwards. A mission that is no longer Public drops its queue, since those
+    /// brawlers would otherwise skip the invitation or approval; seats added by
+    /// raising `max_members` go to the queue.
+    async fn settle_waitlist(&self, before: &MissionModel, editor_id: i32) -> DomainResult<()> {
+        let mission = self
+            .mission_viewing_repository
+            .view_detail(before.id, Some(editor_id))
+            .await?;
+
+        if mission.visibility != MissionVisibility::Public.to_string() {
+            let dropped = self.crew_operation_repository.clear_waitlist(mission.id).await?;
+            if dropped > 0 {
+                tracing::info!(
+                    "Dropped {} waitlist entries of mission {}, it is no longer public",
+                    dropped,
+                    mission.id
+                );
+            }
+            return Ok(());
+        }
+
+        let raised = mission.max_members > before.max_members;
+        let status: MissionStatuses = mission.status.parse()?;
+        if !raised || !status.is_joinable() {
+            return Ok(());
+        }
+
+        let promoted = self
+            .crew_operation_repository
+            .promote_from_waitlist(mission.id)
+            .await?;
+        for brawler_id in promoted {
+            self.events.publish(mission.id, MissionEvent::MemberJoined { brawler_id });
+        }
+        Ok(())
+    }
+
+    pub async fn remove(&self, mission_id: i32, chief_id: i32) -> DomainResult<()> {
         tracing::info!("Attempting to remove mission {} by chief {}", mission_id, chief_id);
+        self.authorization
+            .require(mission_id, chief_id, Permission::DeleteMission)
+            .await?;
+
         if let Err(e) = self.mission_management_repository.remove(mission_id, chief_id).await {
             tracing::error!("Failed to remove mission {}: {:?}", mission_id, e);
-            return Err(e);
+            return Err(e.into());
         }
         tracing::info!("Successfully removed mission {}", mission_id);
+        self.events.publish(mission_id, MissionEvent::MissionDeleted);
         Ok(())
     }
 }
+
+/// A mission may only lower the server-wide upload cap, never raise it.
+fn ensure_upload_limit(max_upload_mb: Option<i32>) -> DomainResult<()> {
+    let Some(max_upload_mb) = max_upload_mb else {
+        return Ok(());
+    };
+    let ceiling = get_upload_env()?.max_mb;
+    if !(1..=ceiling).contains(&max_upload_mb) {
+        return Err(DomainError::validation(format!(
+            "Upload limit must be between 1 and {} MB",
+            ceiling
+        )));
+    }
+    Ok(())
+}
+
+fn ensure_file_types(allowed_file_types: Option<&[FileType]>) -> DomainResult<()> {
+    if allowed_file_types.is_some_and(|allowed| allowed.is_empty()) {
+        return Err(DomainError::validation(
+            "A mission has to accept at least one file type",
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_tags(tags: Option<&[String]>) -> DomainResult<()> {
+    let Some(tags) = tags else {
+        return Ok(());
+    };
+    let tags = normalize_tags(tags.to_vec());
+    if tags.len() > MAX_MISSION_TAGS {
+        return Err(DomainError::validation(format!(
+            "A mission can have at most {} tags",
+            MAX_MISSION_TAGS
+        )));
+    }
+    if tags.iter().any(|tag| tag.chars().count() > MAX_TAG_LENGTH) {
+        return Err(DomainError::validation(format!(
+            "Tags can be at most {} characters long",
+            MAX_TAG_LENGTH
+        )));
+    }
+    Ok(())
+}
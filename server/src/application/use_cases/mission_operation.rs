@@ -1,91 +1,185 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-
-use crate::domain::{
-    repositories::{
-        mission_operation::MissionOperationRepository, mission_viewing::MissionViewingRepository,
+use crate::{
+    application::services::{
+        mission_authorization::MissionAuthorization, mission_events::MissionEventHub,
+        notifier::Notifier,
+    },
+    domain::{
+        entities::mission_status_history::{
+            AddMissionStatusHistoryEntity, MissionStatusHistoryEntity,
+        },
+        errors::{DomainError, DomainResult},
+        repositories::{
+            mission_operation::MissionOperationRepository,
+            mission_viewing::MissionViewingRepository,
+            notifications::NotificationRepository,
+        },
+        value_objects::{
+            crew_roles::Permission, mission_events::MissionEvent, mission_model::MissionModel,
+            mission_statuses::MissionStatuses, notification_kinds::NotificationKinds,
+        },
     },
-    value_objects::mission_statuses::MissionStatuses,
 };
-pub struct MissionOperationUseCase<T1, T2>
+pub struct MissionOperationUseCase<T1, T2, T3>
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     mission_operation_repository: Arc<T1>,
     mission_viewing_repository: Arc<T2>,
+    authorization: MissionAuthorization<T2>,
+    events: Arc<MissionEventHub>,
+    notifier: Notifier<T3>,
 }
 
-impl<T1, T2> MissionOperationUseCase<T1, T2>
+impl<T1, T2, T3> MissionOperationUseCase<T1, T2, T3>
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
-    pub fn new(mission_operation_repository: Arc<T1>, mission_viewing_repository: Arc<T2>) -> Self {
+    pub fn new(
+        mission_operation_repository: Arc<T1>,
+        mission_viewing_repository: Arc<T2>,
+        notification_repository: Arc<T3>,
+        events: Arc<MissionEventHub>,
+    ) -> Self {
         Self {
             mission_operation_repository,
+            authorization: MissionAuthorization::new(Arc::clone(&mission_viewing_repository)),
             mission_viewing_repository,
+            events,
+            notifier: Notifier::new(notification_repository),
         }
     }
 
-    pub async fn in_progress(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let mission = self.mission_viewing_repository.view_detail(mission_id, Some(chief_id)).await?;
-
-        let member_count = self
-            .mission_viewing_repository
-            .member_counting(mission_id)
+    pub async fn transition(
+        &self,
+        mission_id: i32,
+        chief_id: i32,
+        next: MissionStatuses,
+        reason: Option<String>,
+    ) -> DomainResult<i32> {
+        let (mission, _) = self
+            .authorization
+            .require(mission_id, chief_id, Permission::ChangeStatus)
             .await?;
 
-        let is_status_open_or_fail = mission.status == MissionStatuses::Open.to_string()
-            || mission.status == MissionStatuses::Failed.to_string();
+        let current: MissionStatuses = mission.status.parse()?;
+        if !current.can_transition_to(&next) {
+            return Err(DomainError::conflict(format!(
+                "Mission can not move from {} to {}",
+                current, next
+            )));
+        }
+
+        if next == MissionStatuses::InProgress {
+            let member_count = self
+                .mission_viewing_repository
+                .member_counting(mission_id)
+                .await?;
 
-        let max_crew_per_mission = std::env::var("MAX_CREW_PER_MISSION")
-            .expect("missing value")
-            .parse()?;
+            let max_crew_per_mission = std::env::var("MAX_CREW_PER_MISSION")
+                .expect("missing value")
+                .parse::<u32>()
+                .map_err(|e| DomainError::Internal(e.into()))?;
 
-        let update_condition = is_status_open_or_fail
-            && member_count > 0
-            && member_count < max_crew_per_mission
-            && mission.chief_id == chief_id;
-        if !update_condition {
-            return Err(anyhow::anyhow!("Invalid condition to change stages!"));
+            if member_count == 0 || member_count >= max_crew_per_mission {
+                return Err(DomainError::conflict("Invalid condition to change stages!"));
+            }
         }
 
-        let result = self
+        let changed = self
             .mission_operation_repository
-            .to_progress(mission_id, chief_id)
+            .transition(AddMissionStatusHistoryEntity {
+                mission_id,
+                changed_by: Some(chief_id),
+                from_status: current.to_string(),
+                to_status: next.to_string(),
+                reason: reason
+                    .map(|reason| reason.trim().to_string())
+                    .filter(|reason| !reason.is_empty()),
+            })
             .await?;
-        Ok(result)
-    }
-    pub async fn to_completed(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let mission = self.mission_viewing_repository.view_detail(mission_id, Some(chief_id)).await?;
 
-        let update_condition = mission.status == MissionStatuses::InProgress.to_string()
-            && mission.chief_id == chief_id;
-        if !update_condition {
-            return Err(anyhow::anyhow!("Invalid condition to change stages!"));
+        if !changed {
+            return Err(DomainError::conflict(
+                "Mission status was changed by someone else, reload and try again",
+            ));
         }
-        let result = self
-            .mission_operation_repository
-            .to_completed(mission_id, chief_id)
-            .await?;
+        self.events.publish(mission_id, MissionEvent::MissionStatusChanged {
+            from_status: current.to_string(),
+            to_status: next.to_string(),
+        });
 
-        Ok(result)
+        if next == MissionStatuses::Completed {
+            self.notify_completed(&mission, chief_id).await;
+        }
+
+        Ok(mission_id)
     }
-    pub async fn to_failed(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let mission = self.mission_viewing_repository.view_detail(mission_id, Some(chief_id)).await?;
 
-        let update_condition = mission.status == MissionStatuses::InProgress.to_string()
-            && mission.chief_id == chief_id;
-        if !update_condition {
-            return Err(anyhow::anyhow!("Invalid condition to change stages!"));
+    /// The status change is already stored, so failing to reach the crew is only logged.
+    async fn notify_completed(&self, mission: &MissionModel, chief_id: i32) {
+        let crew = match self.mission_viewing_repository.get_mission_crew(mission.id).await {
+            Ok(crew) => crew,
+            Err(e) => {
+                tracing::warn!("Failed to load the crew of mission {}: {:?}", mission.id, e);
+                return;
+            }
+        };
+
+        let recipients = crew
+            .into_iter()
+            .map(|member| member.id)
+            .filter(|member_id| *member_id != chief_id)
+            .collect();
+        self.notifier
+            .send(
+                recipients,
+                NotificationKinds::MissionCompleted,
+                Some(mission.id),
+                format!("\"{}\" was marked as completed", mission.name),
+            )
+            .await;
+    }
+
+    pub async fn in_progress(&self, mission_id: i32, chief_id: i32) -> DomainResult<i32> {
+        self.transition(mission_id, chief_id, MissionStatuses::InProgress, None)
+            .await
+    }
+
+    pub async fn to_completed(&self, mission_id: i32, chief_id: i32) -> DomainResult<i32> {
+        self.transition(mission_id, chief_id, MissionStatuses::Completed, None)
+            .await
+    }
+
+    pub async fn to_failed(&self, mission_id: i32, chief_id: i32) -> DomainResult<i32> {
+        self.transition(mission_id, chief_id, MissionStatuses::Failed, None)
+            .await
+    }
+
+    pub async fn status_history(
+        &self,
+        mission_id: i32,
+        user_id: i32,
+    ) -> DomainResult<Vec<MissionStatusHistoryEntity>> {
+        // Same visibility as the mission itself
+        let mission = self
+            .mission_viewing_repository
+            .view_detail(mission_id, Some(user_id))
+            .await?;
+        if !mission.is_visible_to(Some(user_id)) {
+            return Err(DomainError::not_found("Mission not found"));
         }
-        let result = self
+
+        let history = self
             .mission_operation_repository
-            .to_failed(mission_id, chief_id)
+            .status_history(mission_id)
             .await?;
 
-        Ok(result)
+        Ok(history)
     }
 }
@@ -1,45 +1,85 @@
+use crate::application::services::{
+    mission_authorization::MissionAuthorization, mission_events::MissionEventHub,
+    notifier::Notifier, task_workflow::TaskWorkflow,
+};
 use crate::domain::{
-    entities::mission_submissions::{MissionSubmission, NewMissionSubmission},
+    entities::mission_submissions::{MissionSubmission, NewMissionSubmission, ReviewMissionSubmission},
+    errors::{DomainError, DomainResult},
     repositories::{
         mission_submissions::MissionSubmissionsRepository,
         mission_viewing::MissionViewingRepository,
+        notifications::NotificationRepository,
+    },
+    services::file_storage::{ByteSource, FileStorage, UploadTooLarge},
+    value_objects::{
+        crew_roles::{CrewRole, Permission},
+        file_types::sanitize_file_name,
+        mission_events::MissionEvent,
+        notification_kinds::NotificationKinds,
+        submission_review_model::{
+            ReviewSubmissionModel, SubmissionReviewDecisions, SubmissionReviewStatuses,
+        },
+        task_model::TaskModel,
+        task_statuses::TaskStatuses,
     },
-    value_objects::base64_img::Base64Img,
-};
-use crate::infrastructure::{
-    cloudinary::{upload_auto, UploadImageOptions},
 };
-use anyhow::Result;
+use crate::config::config_loader::get_upload_env;
+use crate::infrastructure::jwt::generate_opaque_token;
+use async_trait::async_trait;
 use std::sync::Arc;
 
-use crate::domain::value_objects::mission_submission_model::MissionSubmissionModel;
+use crate::domain::value_objects::mission_submission_model::{
+    MissionSubmissionModel, SubmissionDiffQuery, SubmissionVersionDiffModel,
+};
+
+// Download links handed out with submissions stay valid for an hour
+const DOWNLOAD_LINK_TTL_MINUTES: i64 = 60;
+// Enough for every signature `infer` knows about
+const SNIFF_LEN: usize = 8 * 1024;
 
-pub struct MissionSubmissionUseCase<T1, T2, T3>
+pub struct MissionSubmissionUseCase<T1, T2, T3, T4, T5>
 where
     T1: MissionViewingRepository + Send + Sync,
     T2: MissionSubmissionsRepository + Send + Sync,
     T3: crate::domain::repositories::tasks::TaskRepository + Send + Sync,
+    T4: FileStorage + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
 {
     mission_viewing_repository: Arc<T1>,
     mission_submissions_repository: Arc<T2>,
     task_repository: Arc<T3>,
+    file_storage: Arc<T4>,
+    authorization: MissionAuthorization<T1>,
+    workflow: TaskWorkflow<T3>,
+    notifier: Notifier<T5>,
+    events: Arc<MissionEventHub>,
 }
 
-impl<T1, T2, T3> MissionSubmissionUseCase<T1, T2, T3>
+impl<T1, T2, T3, T4, T5> MissionSubmissionUseCase<T1, T2, T3, T4, T5>
 where
     T1: MissionViewingRepository + Send + Sync,
     T2: MissionSubmissionsRepository + Send + Sync,
     T3: crate::domain::repositories::tasks::TaskRepository + Send + Sync,
+    T4: FileStorage + Send + Sync,
+    T5: NotificationRepository + Send + Sync,
 {
     pub fn new(
         mission_viewing_repository: Arc<T1>,
         mission_submissions_repository: Arc<T2>,
         task_repository: Arc<T3>,
+        file_storage: Arc<T4>,
+        notification_repository: Arc<T5>,
+        events: Arc<MissionEventHub>,
     ) -> Self {
         Self {
+            authorization: MissionAuthorization::new(Arc::clone(&mission_viewing_repository)),
             mission_viewing_repository,
             mission_submissions_repository,
+            workflow: TaskWorkflow::new(Arc::clone(&task_repository), Arc::clone(&events)),
             task_repository,
+            file_storage,
+            notifier: Notifier::new(notification_repository),
+            events,
         }
     }
 
@@ -48,118 +88,473 @@ where
         mission_id: i32,
         brawler_id: i32,
         task_id: Option<i32>,
-        base64_data: String,
+        upload: &mut dyn ByteSource,
         file_name: String,
-        file_type: String,
-    ) -> Result<MissionSubmission> {
-        // 1. Verify user is a member of the mission
-        let mission_details = self
-            .mission_viewing_repository
-            .view_detail(mission_id, Some(brawler_id))
+        declared_type: Option<String>,
+    ) -> DomainResult<MissionSubmission> {
+        // 1. Verify user's crew role allows submitting
+        let (mission, role) = self
+            .authorization
+            .require(mission_id, brawler_id, Permission::SubmitWork)
             .await?;
+        // Work handed in moves the task to Review, a new version under review leaves it there
+        let task_move = match task_id {
+            Some(tid) => {
+                let task = self.task_in_mission(mission_id, tid).await?;
+                self.workflow.submission_move(&task, role, brawler_id).await?
+            }
+            None => None,
+        };
 
-        if !mission_details.is_joined {
-            return Err(anyhow::anyhow!("You must be a member of this mission to submit work."));
+        // 2. Cap the upload and check its type against what the first bytes really are
+        let limit_mb = mission.max_upload_mb.min(get_upload_env()?.max_mb);
+        let mut upload = LimitedUpload::new(upload, limit_mb);
+        upload.read_head(SNIFF_LEN).await.map_err(|e| upload_error(e, limit_mb))?;
+        if upload.head.is_empty() {
+            return Err(DomainError::validation("The uploaded file is empty"));
         }
+        let file_name = sanitize_file_name(&file_name);
+        let file_type = mission
+            .submission_policy()
+            .check(&file_name, declared_type.as_deref(), &upload.head)
+            .map_err(DomainError::validation)?
+            .mime_type()
+            .to_string();
 
-        // 2. Upload file to Cloudinary
-        // Use Base64Img to ensure common prefix if missing
-        let b64_img = Base64Img::new(base64_data)?;
-        let upload_result = upload_auto(
-            b64_img.into_inner(),
-            UploadImageOptions {
-                folder: Some(format!("mission_{}_submissions", mission_id)),
-                public_id: None,
-                transformation: None,
-            },
-        )
-        .await?;
+        // 3. Stream the file to storage under a key nobody can guess
+        let key = format!(
+            "missions/{}/submissions/{}-{}",
+            mission_id,
+            generate_opaque_token(),
+            storage_file_name(&file_name)
+        );
+        let stored = self
+            .file_storage
+            .put(&key, &mut upload, &file_type)
+            .await
+            .map_err(|e| upload_error(e, limit_mb))?;
 
-        // 3. Save submission to database
+        // 4. Save submission to database
         let new_submission = NewMissionSubmission {
             mission_id,
             brawler_id,
-            file_url: &upload_result.url,
+            file_url: &stored.url,
             file_name: &file_name,
             file_type: &file_type,
             task_id,
             description: None,
+            storage_key: Some(&stored.key),
         };
 
-        let submission = self.mission_submissions_repository.create(new_submission).await?;
+        let mut submission = match self.mission_submissions_repository.create(new_submission).await {
+            Ok(submission) => submission,
+            Err(e) => {
+                if let Err(cleanup) = self.file_storage.delete(&stored.key).await {
+                    tracing::warn!("Failed to delete stored file {}: {}", stored.key, cleanup);
+                }
+                return Err(e.into());
+            }
+        };
+        if let Some(key) = &submission.storage_key {
+            submission.file_url = self.download_url(key).await?;
+        }
 
-        // 4. Update task has_submission flag
+        self.events.publish(mission_id, MissionEvent::SubmissionCreated {
+            submission_id: submission.id,
+            task_id,
+            brawler_id,
+        });
+
+        // 5. Update task has_submission flag
         if let Some(tid) = task_id {
-            let _ = self.task_repository.update(tid, crate::domain::entities::tasks::UpdateTaskEntity {
-                title: None,
-                description: None,
-                member_id: None,
-                status: Some("Review".to_string()),
-                priority: None,
-                updated_at: Some(chrono::Utc::now().naive_utc()),
-                has_submission: Some(true),
-            }).await;
+            self.workflow.record_submission(tid, true, task_move).await?;
         }
 
         Ok(submission)
     }
 
-    pub async fn get_submissions(&self, mission_id: i32, brawler_id: i32) -> Result<Vec<MissionSubmissionModel>> {
-        let mission_details = self.mission_viewing_repository.view_detail(mission_id, Some(brawler_id)).await?;
-
-        if !mission_details.is_joined && mission_details.chief_id != brawler_id {
-             return Err(anyhow::anyhow!("You do not have permission to view submissions for this mission."));
-        }
+    pub async fn get_submissions(&self, mission_id: i32, brawler_id: i32) -> DomainResult<Vec<MissionSubmissionModel>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
 
         let submissions = self.mission_submissions_repository.get_by_mission(mission_id).await?;
-        Ok(submissions)
+        self.with_download_urls(submissions).await
     }
 
-    pub async fn get_task_submission(&self, task_id: i32, _brawler_id: i32) -> Result<Option<MissionSubmissionModel>> {
-        let submission = self.mission_submissions_repository.get_by_task(task_id).await?;
-        Ok(submission)
+    pub async fn get_task_submission(&self, task_id: i32, brawler_id: i32) -> DomainResult<Option<MissionSubmissionModel>> {
+        let task = self.task_repository.get_by_id(task_id).await?;
+        self.authorization
+            .require(task.mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+
+        match self.mission_submissions_repository.get_by_task(task_id).await? {
+            Some(submission) => Ok(Some(self.with_download_url(submission).await?)),
+            None => Ok(None),
+        }
     }
 
-    pub async fn delete_submission(&self, id: i32, brawler_id: i32) -> Result<()> {
+    pub async fn delete_submission(&self, id: i32, brawler_id: i32) -> DomainResult<()> {
         let submission = self.mission_submissions_repository.get_by_id(id).await?
-            .ok_or_else(|| anyhow::anyhow!("Submission not found"))?;
+            .ok_or_else(|| DomainError::not_found("Submission not found"))?;
 
         let mission = self.mission_viewing_repository.view_detail(submission.mission_id, Some(brawler_id)).await?;
-        
-        if mission.chief_id != brawler_id && submission.brawler_id != brawler_id {
-            return Err(anyhow::anyhow!("Only the Chief or the submission owner can delete submissions"));
+
+        let role = self
+            .authorization
+            .role_in(&mission, brawler_id)
+            .await?
+            .ok_or_else(|| DomainError::forbidden("You are not a member of this mission"))?;
+        let is_reviewer = role.has(Permission::ReviewSubmissions);
+        if submission.brawler_id != brawler_id && !is_reviewer {
+            return Err(DomainError::forbidden("Only a reviewer or the submission owner can delete submissions"));
+        }
+        if submission.review_status == SubmissionReviewStatuses::Approved.to_string() && !is_reviewer {
+            return Err(DomainError::forbidden("Approved work can only be withdrawn by a reviewer"));
         }
 
-        self.mission_submissions_repository.delete(id).await?;
-        
+        // Taking back work under review or approved work reopens the task
+        let task_move = match submission.task_id {
+            Some(tid) => {
+                let task = self.task_repository.get_by_id(tid).await?;
+                let current: TaskStatuses = task.status.parse()?;
+                if matches!(current, TaskStatuses::Review | TaskStatuses::Done) {
+                    self.workflow
+                        .ensure_may_transition(&task, role, brawler_id, TaskStatuses::InProgress)
+                        .await?;
+                    Some(TaskStatuses::InProgress)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        // Task work keeps its history, only loose files are removed outright
+        if submission.task_id.is_some() {
+            if !self.mission_submissions_repository.withdraw(id).await? {
+                return Err(DomainError::conflict("Only the current version can be withdrawn"));
+            }
+        } else {
+            // The stored file is queued for removal along with the row
+            self.mission_submissions_repository.delete(id).await?;
+        }
+
+        self.events.publish(submission.mission_id, MissionEvent::SubmissionDeleted {
+            submission_id: id,
+            task_id: submission.task_id,
+        });
+
         // Update task if applicable
         if let Some(tid) = submission.task_id {
-            let _ = self.task_repository.update(tid, crate::domain::entities::tasks::UpdateTaskEntity {
-                title: None,
-                description: None,
-                member_id: None,
-                status: Some("In Progress".to_string()),
-                priority: None,
-                updated_at: Some(chrono::Utc::now().naive_utc()),
-                has_submission: Some(false),
-            }).await;
+            self.workflow.record_submission(tid, false, task_move).await?;
         }
 
         Ok(())
     }
 
-    pub async fn update_description(&self, id: i32, brawler_id: i32, description: String) -> Result<()> {
+    pub async fn task_versions(
+        &self,
+        mission_id: i32,
+        task_id: i32,
+        brawler_id: i32,
+    ) -> DomainResult<Vec<MissionSubmissionModel>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+        self.task_in_mission(mission_id, task_id).await?;
+
+        let versions = self.mission_submissions_repository.get_versions(task_id).await?;
+        self.with_download_urls(versions).await
+    }
+
+    pub async fn task_version(
+        &self,
+        mission_id: i32,
+        task_id: i32,
+        version: i32,
+        brawler_id: i32,
+    ) -> DomainResult<MissionSubmissionModel> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+        self.task_in_mission(mission_id, task_id).await?;
+
+        let submission = self.find_version(task_id, version).await?;
+        self.with_download_url(submission).await
+    }
+
+    pub async fn diff_versions(
+        &self,
+        mission_id: i32,
+        task_id: i32,
+        query: SubmissionDiffQuery,
+        brawler_id: i32,
+    ) -> DomainResult<SubmissionVersionDiffModel> {
+        if query.from == query.to {
+            return Err(DomainError::validation("Pick two different versions to compare"));
+        }
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ViewWorkspace)
+            .await?;
+        self.task_in_mission(mission_id, task_id).await?;
+
+        let older = self.find_version(task_id, query.from).await?;
+        let newer = self.find_version(task_id, query.to).await?;
+
+        Ok(SubmissionVersionDiffModel {
+            task_id,
+            from_version: older.version,
+            to_version: newer.version,
+            changes: older.diff(&newer),
+        })
+    }
+
+    async fn find_version(&self, task_id: i32, version: i32) -> DomainResult<MissionSubmissionModel> {
+        self.mission_submissions_repository
+            .get_version(task_id, version)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Version {} not found", version)))
+    }
+
+    async fn download_url(&self, key: &str) -> DomainResult<String> {
+        let url = self
+            .file_storage
+            .signed_url(key, chrono::Duration::minutes(DOWNLOAD_LINK_TTL_MINUTES))
+            .await?;
+        Ok(url)
+    }
+
+    /// Swaps the stored location for a link the caller can download from.
+    /// Submissions from before pluggable storage keep their Cloudinary URL.
+    async fn with_download_url(&self, mut submission: MissionSubmissionModel) -> DomainResult<MissionSubmissionModel> {
+        if let Some(key) = &submission.storage_key {
+            submission.file_url = self.download_url(key).await?;
+        }
+        Ok(submission)
+    }
+
+    async fn with_download_urls(&self, submissions: Vec<MissionSubmissionModel>) -> DomainResult<Vec<MissionSubmissionModel>> {
+        let mut signed = Vec::with_capacity(submissions.len());
+        for submission in submissions {
+            signed.push(self.with_download_url(submission).await?);
+        }
+        Ok(signed)
+    }
+
+    async fn task_in_mission(&self, mission_id: i32, task_id: i32) -> DomainResult<TaskModel> {
+        let task = self.task_repository.get_by_id(task_id).await?;
+        if task.mission_id != mission_id {
+            return Err(DomainError::not_found("Task not found"));
+        }
+        Ok(task)
+    }
+
+    pub async fn update_description(&self, id: i32, brawler_id: i32, description: String) -> DomainResult<()> {
         let submission = self.mission_submissions_repository.get_by_id(id).await?
-            .ok_or_else(|| anyhow::anyhow!("Submission not found"))?;
+            .ok_or_else(|| DomainError::not_found("Submission not found"))?;
 
         let mission = self.mission_viewing_repository.view_detail(submission.mission_id, Some(brawler_id)).await?;
-        
-        if mission.chief_id != brawler_id && submission.brawler_id != brawler_id {
-            return Err(anyhow::anyhow!("Only the Chief or the submission owner can update descriptions"));
+
+        if submission.brawler_id != brawler_id
+            && !self.authorization.allows(&mission, brawler_id, Permission::ReviewSubmissions).await?
+        {
+            return Err(DomainError::forbidden("Only a reviewer or the submission owner can update descriptions"));
         }
 
         self.mission_submissions_repository.update_description(id, description).await?;
 
         Ok(())
     }
+
+    pub async fn pending_review(&self, mission_id: i32, brawler_id: i32) -> DomainResult<Vec<MissionSubmissionModel>> {
+        self.authorization
+            .require(mission_id, brawler_id, Permission::ReviewSubmissions)
+            .await?;
+
+        let submissions = self.mission_submissions_repository.get_pending_review(mission_id).await?;
+        self.with_download_urls(submissions).await
+    }
+
+    pub async fn review(
+        &self,
+        mission_id: i32,
+        submission_id: i32,
+        reviewer_id: i32,
+        model: ReviewSubmissionModel,
+    ) -> DomainResult<()> {
+        let (mission, role) = self
+            .authorization
+            .require(mission_id, reviewer_id, Permission::ReviewSubmissions)
+            .await?;
+
+        let submission = self.mission_submissions_repository.get_by_id(submission_id).await?
+            .filter(|submission| submission.mission_id == mission_id)
+            .ok_or_else(|| DomainError::not_found("Submission not found"))?;
+
+        if submission.review_status != SubmissionReviewStatuses::Pending.to_string() {
+            return Err(DomainError::conflict("Submission has already been reviewed"));
+        }
+        // Decisions belong to the version the task is actually waiting on
+        if submission.task_id.is_some() && !submission.is_current {
+            return Err(DomainError::conflict("A newer version has been submitted"));
+        }
+        // The chief has nobody above them, everyone else needs a second pair of eyes
+        if submission.brawler_id == reviewer_id && role != CrewRole::Chief {
+            return Err(DomainError::forbidden("You can not review your own submission"));
+        }
+
+        let feedback = model
+            .feedback
+            .map(|feedback| feedback.trim().to_string())
+            .filter(|feedback| !feedback.is_empty());
+        if model.decision.needs_feedback() && feedback.is_none() {
+            return Err(DomainError::validation(
+                "Feedback is required when rejecting or requesting changes",
+            ));
+        }
+
+        // Check the task can follow before recording anything
+        let task_move = match submission.task_id {
+            Some(task_id) => {
+                let task = self.task_repository.get_by_id(task_id).await?;
+                let current: TaskStatuses = task.status.parse()?;
+                let next = model.decision.task_status();
+                if current.can_transition_to(next) {
+                    self.workflow.ensure_can_move(&task, next).await?;
+                    Some((task, next))
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        let review_status = model.decision.outcome().to_string();
+        let reviewed = self.mission_submissions_repository.review(
+            submission_id,
+            ReviewMissionSubmission {
+                review_status: review_status.clone(),
+                reviewed_by: reviewer_id,
+                review_feedback: feedback,
+                reviewed_at: chrono::Utc::now(),
+            },
+        ).await?;
+        if !reviewed {
+            return Err(DomainError::conflict("Submission has already been reviewed"));
+        }
+        self.events.publish(mission_id, MissionEvent::SubmissionReviewed {
+            submission_id,
+            task_id: submission.task_id,
+            review_status,
+        });
+
+        if submission.brawler_id != reviewer_id {
+            let verdict = match model.decision {
+                SubmissionReviewDecisions::Approve => "approved",
+                SubmissionReviewDecisions::Reject => "rejected",
+                SubmissionReviewDecisions::RequestChanges => "sent back for changes",
+            };
+            self.notifier
+                .send(
+                    vec![submission.brawler_id],
+                    NotificationKinds::SubmissionReviewed,
+                    Some(mission_id),
+                    format!(
+                        "Your submission \"{}\" in \"{}\" was {}",
+                        submission.file_name, mission.name, verdict
+                    ),
+                )
+                .await;
+        }
+
+        if let Some((task, next)) = task_move {
+            let task = self.workflow.set_status(task.id, next).await?;
+            self.workflow.roll_up(&task).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Keeps the original name recognisable in the key without letting it add path segments.
+fn storage_file_name(file_name: &str) -> String {
+    let name: String = file_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_' { c } else { '_' })
+        .take(100)
+        .collect();
+    match name.trim_matches('.') {
+        "" => "file".to_string(),
+        name => name.to_string(),
+    }
+}
+
+/// Replays the bytes read for type sniffing, then passes the rest of the upload
+/// through while refusing to go past the mission's size limit.
+struct LimitedUpload<'a> {
+    inner: &'a mut dyn ByteSource,
+    head: Vec<u8>,
+    head_sent: bool,
+    read: u64,
+    limit_bytes: u64,
+}
+
+impl<'a> LimitedUpload<'a> {
+    fn new(inner: &'a mut dyn ByteSource, limit_mb: i32) -> Self {
+        Self {
+            inner,
+            head: Vec::new(),
+            head_sent: false,
+            read: 0,
+            limit_bytes: u64::try_from(limit_mb).unwrap_or(0) * 1024 * 1024,
+        }
+    }
+
+    async fn pull(&mut self) -> anyhow::Result<Option<Vec<u8>>> {
+        let chunk = self.inner.next_chunk().await?;
+        if let Some(chunk) = &chunk {
+            self.read += chunk.len() as u64;
+            if self.read > self.limit_bytes {
+                return Err(UploadTooLarge {
+                    limit_bytes: self.limit_bytes,
+                }
+                .into());
+            }
+        }
+        Ok(chunk)
+    }
+
+    async fn read_head(&mut self, size: usize) -> anyhow::Result<()> {
+        while self.head.len() < size {
+            match self.pull().await? {
+                Some(chunk) => self.head.extend_from_slice(&chunk),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ByteSource for LimitedUpload<'_> {
+    async fn next_chunk(&mut self) -> anyhow::Result<Option<Vec<u8>>> {
+        if !self.head_sent {
+            self.head_sent = true;
+            if !self.head.is_empty() {
+                return Ok(Some(std::mem::take(&mut self.head)));
+            }
+        }
+        self.pull().await
+    }
+}
+
+fn upload_error(error: anyhow::Error, limit_mb: i32) -> DomainError {
+    if error.downcast_ref::<UploadTooLarge>().is_some() {
+        return DomainError::payload_too_large(format!(
+            "Files for this mission are limited to {} MB",
+            limit_mb
+        ));
+    }
+    error.into()
 }
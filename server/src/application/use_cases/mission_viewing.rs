@@ -1,11 +1,14 @@
 use std::sync::Arc;
 
-use anyhow::{Ok, Result};
-
 use crate::domain::{
+    errors::{DomainError, DomainResult},
     repositories::mission_viewing::MissionViewingRepository,
     value_objects::{
-        brawler_model::BrawlerModel, mission_filter::MissionFilter, mission_model::MissionModel,
+        brawler_model::BrawlerModel,
+        mission_filter::{
+            DEFAULT_MISSION_PAGE_SIZE, MAX_MISSION_PAGE_SIZE, MissionCursor, MissionFilter,
+        },
+        mission_model::{MissionModel, MissionPageModel},
     },
 };
 pub struct MissionViewingUseCase<T>
@@ -25,25 +28,105 @@ where
         }
     }
 
-    pub async fn get_one(&self, mission_id: i32, user_id: Option<i32>) -> Result<MissionModel> {
+    pub async fn get_one(&self, mission_id: i32, user_id: Option<i32>) -> DomainResult<MissionModel> {
         let model = self.mission_viewing_repository.view_detail(mission_id, user_id).await?;
 
+        if !model.is_visible_to(user_id) {
+            return Err(DomainError::not_found("Mission not found"));
+        }
+
         Ok(model)
     }
 
-    pub async fn get_crew(&self, mission_id: i32) -> Result<Vec<BrawlerModel>> {
+    pub async fn get_crew(&self, mission_id: i32, user_id: i32) -> DomainResult<Vec<BrawlerModel>> {
+        self.get_one(mission_id, Some(user_id)).await?;
+
         let result = self.mission_viewing_repository.get_mission_crew(mission_id).await?;
         Ok(result)
     }
 
-    pub async fn get_all(&self, filter: &MissionFilter, user_id: Option<i32>) -> Result<Vec<MissionModel>> {
-        let result = self.mission_viewing_repository.gets(filter, user_id).await?;
+    pub async fn get_all(
+        &self,
+        filter: &MissionFilter,
+        user_id: Option<i32>,
+    ) -> DomainResult<MissionPageModel> {
+        let limit = filter.limit.unwrap_or(DEFAULT_MISSION_PAGE_SIZE);
+        if !(1..=MAX_MISSION_PAGE_SIZE).contains(&limit) {
+            return Err(DomainError::validation(format!(
+                "Limit must be between 1 and {}",
+                MAX_MISSION_PAGE_SIZE
+            )));
+        }
+        ensure_ranges(filter)?;
 
-        Ok(result)
+        let cursor = match filter.cursor.as_deref() {
+            Some(raw) => {
+                let cursor = MissionCursor::decode(raw)
+                    .ok_or_else(|| DomainError::validation("Invalid cursor"))?;
+                // Keys of another sort order would skip or repeat missions
+                if cursor.sort != filter.sort {
+                    return Err(DomainError::validation(
+                        "Cursor belongs to a different sort order",
+                    ));
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+
+        // One extra row tells whether another page follows
+        let mut rows = self
+            .mission_viewing_repository
+            .gets(filter, cursor, limit + 1, user_id)
+            .await?;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+
+        let next_cursor = rows.last().filter(|_| has_more).map(|last| {
+            MissionCursor {
+                sort: filter.sort,
+                sort_key: last.sort_key,
+                id: last.mission.id,
+            }
+            .encode()
+        });
+        let total = self.mission_viewing_repository.count(filter, user_id).await?;
+
+        Ok(MissionPageModel {
+            items: rows.into_iter().map(|row| row.mission).collect(),
+            next_cursor,
+            limit,
+            total,
+        })
     }
 
-    pub async fn get_joined(&self, user_id: i32) -> Result<Vec<MissionModel>> {
+    pub async fn get_joined(&self, user_id: i32) -> DomainResult<Vec<MissionModel>> {
         let result = self.mission_viewing_repository.get_joined(user_id).await?;
         Ok(result)
     }
 }
+
+fn ensure_ranges(filter: &MissionFilter) -> DomainResult<()> {
+    if let (Some(min), Some(max)) = (filter.min_member_count, filter.max_member_count)
+        && min > max
+    {
+        return Err(DomainError::validation(
+            "min_member_count cannot be greater than max_member_count",
+        ));
+    }
+    if let (Some(after), Some(before)) = (filter.created_after, filter.created_before)
+        && after > before
+    {
+        return Err(DomainError::validation(
+            "created_after cannot be later than created_before",
+        ));
+    }
+    if let (Some(after), Some(before)) = (filter.ends_after, filter.ends_before)
+        && after > before
+    {
+        return Err(DomainError::validation(
+            "ends_after cannot be later than ends_before",
+        ));
+    }
+    Ok(())
+}
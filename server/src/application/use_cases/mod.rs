@@ -1,9 +1,16 @@
 pub mod authentication;
 pub mod brawlers;
+pub mod comments;
 pub mod crew_operation;
 pub mod dashboard;
+pub mod email_notifications;
+pub mod file_cleanup;
+pub mod mission_events;
 pub mod mission_management;
 pub mod mission_operation;
 pub mod mission_viewing;
+pub mod notifications;
+pub mod search;
 pub mod tasks;
+pub mod webhooks;
 pub mod mission_submissions;
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::domain::{
+    entities::notifications::{NotificationEntity, NotificationPreferenceEntity},
+    errors::{DomainError, DomainResult},
+    repositories::notifications::NotificationRepository,
+    value_objects::{
+        notification_kinds::NotificationKinds,
+        notification_model::{
+            DEFAULT_NOTIFICATION_PAGE_SIZE, MAX_NOTIFICATION_PAGE_SIZE, NotificationFilter,
+            NotificationPageModel, NotificationPreferenceModel, UnreadCountModel,
+            UpdateNotificationPreferenceModel,
+        },
+    },
+};
+
+pub struct NotificationUseCase<T>
+where
+    T: NotificationRepository + Send + Sync,
+{
+    notification_repository: Arc<T>,
+}
+
+impl<T> NotificationUseCase<T>
+where
+    T: NotificationRepository + Send + Sync,
+{
+    pub fn new(notification_repository: Arc<T>) -> Self {
+        Self { notification_repository }
+    }
+
+    pub async fn list(
+        &self,
+        brawler_id: i32,
+        filter: NotificationFilter,
+    ) -> DomainResult<NotificationPageModel> {
+        let page = filter.page.unwrap_or(1);
+        if page < 1 {
+            return Err(DomainError::validation("Page must be at least 1"));
+        }
+        let per_page = filter.per_page.unwrap_or(DEFAULT_NOTIFICATION_PAGE_SIZE);
+        if !(1..=MAX_NOTIFICATION_PAGE_SIZE).contains(&per_page) {
+            return Err(DomainError::validation(format!(
+                "Page size must be between 1 and {}",
+                MAX_NOTIFICATION_PAGE_SIZE
+            )));
+        }
+        let offset = (page - 1)
+            .checked_mul(per_page)
+            .ok_or_else(|| DomainError::validation("Page is out of range"))?;
+
+        let items = self
+            .notification_repository
+            .list(brawler_id, filter.unread_only, per_page, offset)
+            .await?;
+        let total = self
+            .notification_repository
+            .count(brawler_id, filter.unread_only)
+            .await?;
+
+        Ok(NotificationPageModel {
+            items,
+            page,
+            per_page,
+            total,
+        })
+    }
+
+    pub async fn unread_count(&self, brawler_id: i32) -> DomainResult<UnreadCountModel> {
+        let unread = self.notification_repository.count(brawler_id, true).await?;
+        Ok(UnreadCountModel { unread })
+    }
+
+    pub async fn set_read(
+        &self,
+        notification_id: i32,
+        brawler_id: i32,
+        read: bool,
+    ) -> DomainResult<NotificationEntity> {
+        self.notification_repository
+            .set_read(notification_id, brawler_id, read)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Notification not found"))
+    }
+
+    pub async fn mark_all_read(&self, brawler_id: i32) -> DomainResult<usize> {
+        let marked = self.notification_repository.mark_all_read(brawler_id).await?;
+        Ok(marked)
+    }
+
+    /// Every kind is listed; kinds never touched are enabled.
+    pub async fn preferences(&self, brawler_id: i32) -> DomainResult<Vec<NotificationPreferenceModel>> {
+        let stored = self.notification_repository.preferences(brawler_id).await?;
+
+        let preferences = NotificationKinds::ALL
+            .into_iter()
+            .map(|kind| {
+                let preference = stored
+                    .iter()
+                    .find(|preference| preference.kind == kind.to_string());
+                let enabled = preference.is_none_or(|preference| preference.enabled);
+                NotificationPreferenceModel {
+                    kind,
+                    enabled,
+                    email: kind.emails(enabled, preference.and_then(|preference| preference.email_enabled)),
+                }
+            })
+            .collect();
+
+        Ok(preferences)
+    }
+
+    pub async fn update_preferences(
+        &self,
+        brawler_id: i32,
+        preferences: Vec<UpdateNotificationPreferenceModel>,
+    ) -> DomainResult<Vec<NotificationPreferenceModel>> {
+        let stored = self.notification_repository.preferences(brawler_id).await?;
+        let updated_at = Utc::now().naive_utc();
+        let entities = preferences
+            .into_iter()
+            .map(|preference| {
+                let kind = preference.kind.to_string();
+                let existing = stored.iter().find(|existing| existing.kind == kind);
+                // Mail is only stored once it was chosen, so turning a kind off also stops its mail
+                NotificationPreferenceEntity {
+                    brawler_id,
+                    enabled: preference
+                        .enabled
+                        .unwrap_or_else(|| existing.is_none_or(|existing| existing.enabled)),
+                    email_enabled: preference
+                        .email
+                        .or_else(|| existing.and_then(|existing| existing.email_enabled)),
+                    kind,
+                    updated_at,
+                }
+            })
+            .collect();
+
+        self.notification_repository.set_preferences(entities).await?;
+
+        self.preferences(brawler_id).await
+    }
+}
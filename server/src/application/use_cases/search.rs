@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    repositories::search::SearchRepository,
+    value_objects::search_model::{
+        DEFAULT_SEARCH_PAGE_SIZE, MAX_SEARCH_PAGE_SIZE, MAX_SEARCH_QUERY_LENGTH, SearchKinds,
+        SearchQuery, SearchResultsModel, escape_snippet,
+    },
+};
+
+pub struct SearchUseCase<T>
+where
+    T: SearchRepository + Send + Sync,
+{
+    search_repository: Arc<T>,
+}
+
+impl<T> SearchUseCase<T>
+where
+    T: SearchRepository + Send + Sync,
+{
+    pub fn new(search_repository: Arc<T>) -> Self {
+        Self { search_repository }
+    }
+
+    pub async fn search(
+        &self,
+        brawler_id: i32,
+        search_query: SearchQuery,
+    ) -> DomainResult<SearchResultsModel> {
+        let query = search_query.q.trim();
+        if query.is_empty() {
+            return Err(DomainError::validation("Search query is required"));
+        }
+        if query.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Search query can be at most {} characters long",
+                MAX_SEARCH_QUERY_LENGTH
+            )));
+        }
+
+        let page = search_query.page.unwrap_or(1);
+        if page < 1 {
+            return Err(DomainError::validation("Page must be at least 1"));
+        }
+        let per_page = search_query.per_page.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE);
+        if !(1..=MAX_SEARCH_PAGE_SIZE).contains(&per_page) {
+            return Err(DomainError::validation(format!(
+                "Page size must be between 1 and {}",
+                MAX_SEARCH_PAGE_SIZE
+            )));
+        }
+        let offset = (page - 1)
+            .checked_mul(per_page)
+            .ok_or_else(|| DomainError::validation("Page is out of range"))?;
+
+        let kinds = parse_kinds(search_query.kinds.as_deref())?;
+
+        let mut items = self
+            .search_repository
+            .search(brawler_id, query, &kinds, per_page, offset)
+            .await?;
+        for item in &mut items {
+            item.snippet = escape_snippet(&item.snippet);
+        }
+        let total = self.search_repository.count(brawler_id, query, &kinds).await?;
+
+        Ok(SearchResultsModel {
+            items,
+            page,
+            per_page,
+            total,
+        })
+    }
+}
+
+fn parse_kinds(kinds: Option<&str>) -> DomainResult<Vec<SearchKinds>> {
+    let Some(kinds) = kinds.filter(|kinds| !kinds.trim().is_empty()) else {
+        return Ok(vec![SearchKinds::Mission, SearchKinds::Task, SearchKinds::Submission]);
+    };
+
+    kinds
+        .split(',')
+        .map(|kind| {
+            kind.trim()
+                .parse::<SearchKinds>()
+                .map_err(|e| DomainError::validation(e.to_string()))
+        })
+        .collect()
+}
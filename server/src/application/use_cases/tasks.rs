@@ -1,50 +1,83 @@
-use crate::domain::{
-    entities::tasks::{CreateTaskEntity, UpdateTaskEntity},
-    repositories::{
-        mission_viewing::MissionViewingRepository, tasks::TaskRepository,
-        mission_submissions::MissionSubmissionsRepository,
+use crate::{
+    application::services::{
+        mission_authorization::MissionAuthorization, mission_events::MissionEventHub,
+        notifier::Notifier, task_workflow::TaskWorkflow,
+    },
+    domain::{
+        entities::{
+            task_dependencies::AddTaskDependencyEntity,
+            tasks::{CreateTaskEntity, UpdateTaskEntity},
+        },
+        errors::{DomainError, DomainResult},
+        repositories::{
+            mission_viewing::MissionViewingRepository, tasks::TaskRepository,
+            mission_submissions::MissionSubmissionsRepository,
+            notifications::NotificationRepository,
+        },
+        value_objects::{
+            crew_roles::Permission,
+            mission_events::MissionEvent,
+            mission_model::MissionModel,
+            notification_kinds::NotificationKinds,
+            task_model::{
+                AddTaskDependencyModel, CreateTaskModel, TaskModel, TaskNodeModel, UpdateTaskModel,
+            },
+            task_statuses::TaskStatuses,
+        },
     },
-    value_objects::task_model::{CreateTaskModel, TaskModel, UpdateTaskModel},
 };
-use anyhow::Result;
 use chrono::Local;
 use std::sync::Arc;
 
-pub struct TaskUseCase<T1, T2, T3>
+pub struct TaskUseCase<T1, T2, T3, T4>
 where
     T1: TaskRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
     T3: MissionSubmissionsRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     task_repository: Arc<T1>,
-    mission_viewing_repository: Arc<T2>,
+    authorization: MissionAuthorization<T2>,
+    workflow: TaskWorkflow<T1>,
     mission_submissions_repository: Arc<T3>,
+    events: Arc<MissionEventHub>,
+    notifier: Notifier<T4>,
 }
 
-impl<T1, T2, T3> TaskUseCase<T1, T2, T3>
+impl<T1, T2, T3, T4> TaskUseCase<T1, T2, T3, T4>
 where
     T1: TaskRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
     T3: MissionSubmissionsRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     pub fn new(
         task_repository: Arc<T1>,
         mission_viewing_repository: Arc<T2>,
         mission_submissions_repository: Arc<T3>,
+        notification_repository: Arc<T4>,
+        events: Arc<MissionEventHub>,
     ) -> Self {
         Self {
+            workflow: TaskWorkflow::new(Arc::clone(&task_repository), Arc::clone(&events)),
             task_repository,
-            mission_viewing_repository,
+            authorization: MissionAuthorization::new(mission_viewing_repository),
             mission_submissions_repository,
+            events,
+            notifier: Notifier::new(notification_repository),
         }
     }
 
-    pub async fn create(&self, mission_id: i32, user_id: i32, model: CreateTaskModel) -> Result<TaskModel> {
-        let mission = self.mission_viewing_repository.view_detail(mission_id, None).await?;
-        
-        if mission.chief_id != user_id {
-            return Err(anyhow::anyhow!("Only the Chief can create tasks"));
-        }
+    pub async fn create(&self, mission_id: i32, user_id: i32, model: CreateTaskModel) -> DomainResult<TaskModel> {
+        let (mission, _) = self
+            .authorization
+            .require(mission_id, user_id, Permission::ManageTasks)
+            .await?;
+
+        let parent = match model.parent_task_id {
+            Some(parent_task_id) => Some(self.task_in_mission(mission_id, parent_task_id).await?),
+            None => None,
+        };
 
         let entity = CreateTaskEntity {
             mission_id,
@@ -52,63 +85,227 @@ where
             description: model.description,
             member_id: model.member_id,
             created_by: user_id,
-            status: "Pending".to_string(),
+            status: TaskStatuses::Pending.to_string(),
             priority: model.priority.unwrap_or_else(|| "Medium".to_string()),
             start_date: model.start_date,
             end_date: model.end_date,
+            parent_task_id: model.parent_task_id,
         };
 
-        self.task_repository.create(entity).await
+        let task = self.task_repository.create(entity).await?;
+        self.events.publish(mission_id, MissionEvent::TaskCreated { task: task.clone() });
+        self.notify_assignee(&mission, &task, user_id).await;
+
+        // A parent under review or done is no longer finished once it gains open work
+        if parent.is_some() {
+            self.workflow.roll_up(&task).await?;
+        }
+
+        Ok(task)
     }
 
-    pub async fn update(&self, task_id: i32, user_id: i32, model: UpdateTaskModel) -> Result<TaskModel> {
+    pub async fn update(&self, task_id: i32, user_id: i32, model: UpdateTaskModel) -> DomainResult<TaskModel> {
         let task = self.task_repository.get_by_id(task_id).await?;
-        let mission = self.mission_viewing_repository.view_detail(task.mission_id, None).await?;
+        let (mission, role) = self
+            .authorization
+            .require(task.mission_id, user_id, Permission::ViewWorkspace)
+            .await?;
 
-        // Allow update if user is chief OR if user is assigned and updating status only?
-        // For now, let's restrict major updates to Chief, but maybe status updates to assignee.
-        // The user request said "Only mission owner can: ... Create and assign tasks". 
-        // It didn't explicitly forbid members from updating status.
-        // But for strict "Owner-only control", let's assume Members are View-Only for now as per "Members can: View tasks".
+        let edits_details = model.title.is_some()
+            || model.description.is_some()
+            || model.member_id.is_some()
+            || model.priority.is_some();
+        if edits_details && !role.has(Permission::ManageTasks) {
+            return Err(DomainError::forbidden(format!(
+                "{} role is not allowed to edit task details",
+                role
+            )));
+        }
 
-        if mission.chief_id != user_id {
-            return Err(anyhow::anyhow!("Only the Chief can update tasks"));
+        let status = model
+            .status
+            .as_deref()
+            .map(str::parse::<TaskStatuses>)
+            .transpose()
+            .map_err(|e| DomainError::validation(e.to_string()))?
+            .filter(|status| status.to_string() != task.status);
+        if let Some(status) = status {
+            self.workflow.ensure_may_transition(&task, role, user_id, status).await?;
         }
 
         let entity = UpdateTaskEntity {
             title: model.title,
             description: model.description,
             member_id: model.member_id,
-            status: model.status,
+            status: status.map(|status| status.to_string()),
             priority: model.priority,
             updated_at: Some(Local::now().naive_local()),
             has_submission: None,
         };
 
-        self.task_repository.update(task_id, entity).await
+        let updated = self.task_repository.update(task_id, entity).await?;
+        self.events.publish(updated.mission_id, MissionEvent::TaskUpdated { task: updated.clone() });
+        if updated.member_id != task.member_id {
+            self.notify_assignee(&mission, &updated, user_id).await;
+        }
+
+        if updated.status != task.status {
+            self.workflow.roll_up(&updated).await?;
+        }
+
+        Ok(updated)
     }
 
-    pub async fn delete(&self, task_id: i32, user_id: i32) -> Result<()> {
-        let task = self.task_repository.get_by_id(task_id).await?;
-        let mission = self.mission_viewing_repository.view_detail(task.mission_id, None).await?;
+    pub async fn change_status(
+        &self,
+        mission_id: i32,
+        task_id: i32,
+        user_id: i32,
+        status: TaskStatuses,
+    ) -> DomainResult<TaskModel> {
+        let task = self.task_in_mission(mission_id, task_id).await?;
+
+        self.update(
+            task.id,
+            user_id,
+            UpdateTaskModel {
+                title: None,
+                description: None,
+                member_id: None,
+                status: Some(status.to_string()),
+                priority: None,
+            },
+        )
+        .await
+    }
+
+    pub async fn claim(&self, mission_id: i32, task_id: i32, user_id: i32) -> DomainResult<TaskModel> {
+        self.authorization
+            .require(mission_id, user_id, Permission::SubmitWork)
+            .await?;
+        let task = self.task_in_mission(mission_id, task_id).await?;
 
-        if mission.chief_id != user_id {
-            return Err(anyhow::anyhow!("Only the Chief can delete tasks"));
+        match task.member_id {
+            Some(member_id) if member_id == user_id => return Ok(task),
+            Some(_) => return Err(DomainError::conflict("Task is already assigned")),
+            None => {}
+        }
+        if task.status == TaskStatuses::Done.to_string() {
+            return Err(DomainError::conflict("Task is already done"));
         }
 
+        let task = self
+            .task_repository
+            .claim(task_id, user_id)
+            .await?
+            .ok_or_else(|| DomainError::conflict("Task is already assigned"))?;
+        self.events.publish(mission_id, MissionEvent::TaskUpdated { task: task.clone() });
+        Ok(task)
+    }
+
+    pub async fn delete(&self, task_id: i32, user_id: i32) -> DomainResult<()> {
+        let task = self.task_repository.get_by_id(task_id).await?;
+        self.authorization
+            .require(task.mission_id, user_id, Permission::ManageTasks)
+            .await?;
+
         // Manual cleanup for associated submissions as fallback to CASCADE
         self.mission_submissions_repository.delete_all_by_task(task_id).await?;
         
-        self.task_repository.delete(task_id).await
+        self.task_repository.delete(task_id).await?;
+        self.events.publish(task.mission_id, MissionEvent::TaskDeleted { task_id });
+        Ok(())
+    }
+
+    pub async fn get_by_mission(&self, mission_id: i32, user_id: i32) -> DomainResult<Vec<TaskNodeModel>> {
+        self.authorization
+            .require(mission_id, user_id, Permission::ViewWorkspace)
+            .await?;
+
+        let tasks = self.task_repository.get_by_mission_id(mission_id).await?;
+        Ok(tasks)
     }
 
-    pub async fn get_by_mission(&self, mission_id: i32, user_id: i32) -> Result<Vec<TaskModel>> {
-        // Verify user is member or chief
-        let mission = self.mission_viewing_repository.view_detail(mission_id, Some(user_id)).await?;
-        if !mission.is_joined && mission.chief_id != user_id {
-             return Err(anyhow::anyhow!("Access denied"));
+    pub async fn add_dependency(
+        &self,
+        mission_id: i32,
+        task_id: i32,
+        user_id: i32,
+        model: AddTaskDependencyModel,
+    ) -> DomainResult<()> {
+        self.authorization
+            .require(mission_id, user_id, Permission::ManageTasks)
+            .await?;
+
+        if task_id == model.blocked_by_task_id {
+            return Err(DomainError::validation("A task can not block itself"));
+        }
+        let task = self.task_in_mission(mission_id, task_id).await?;
+        self.task_in_mission(mission_id, model.blocked_by_task_id).await?;
+
+        let added = self
+            .task_repository
+            .add_dependency(
+                mission_id,
+                AddTaskDependencyEntity {
+                    task_id,
+                    blocked_by_task_id: model.blocked_by_task_id,
+                },
+            )
+            .await?;
+        if !added {
+            return Err(DomainError::conflict(
+                "That dependency would create a cycle between tasks",
+            ));
         }
+        self.events.publish(mission_id, MissionEvent::TaskUpdated { task });
+        Ok(())
+    }
+
+    pub async fn remove_dependency(
+        &self,
+        mission_id: i32,
+        task_id: i32,
+        blocked_by_task_id: i32,
+        user_id: i32,
+    ) -> DomainResult<()> {
+        self.authorization
+            .require(mission_id, user_id, Permission::ManageTasks)
+            .await?;
+        let task = self.task_in_mission(mission_id, task_id).await?;
 
-        self.task_repository.get_by_mission_id(mission_id).await
+        if !self
+            .task_repository
+            .remove_dependency(task_id, blocked_by_task_id)
+            .await?
+        {
+            return Err(DomainError::not_found("Dependency not found"));
+        }
+        self.events.publish(mission_id, MissionEvent::TaskUpdated { task });
+        Ok(())
+    }
+
+    /// Tells the assignee about the task, unless they assigned it to themselves.
+    async fn notify_assignee(&self, mission: &MissionModel, task: &TaskModel, user_id: i32) {
+        let Some(member_id) = task.member_id.filter(|member_id| *member_id != user_id) else {
+            return;
+        };
+
+        self.notifier
+            .send(
+                vec![member_id],
+                NotificationKinds::TaskAssigned,
+                Some(mission.id),
+                format!("You were assigned \"{}\" in \"{}\"", task.title, mission.name),
+            )
+            .await;
+    }
+
+    async fn task_in_mission(&self, mission_id: i32, task_id: i32) -> DomainResult<TaskModel> {
+        let task = self.task_repository.get_by_id(task_id).await?;
+        if task.mission_id != mission_id {
+            return Err(DomainError::not_found("Task not found"));
+        }
+        Ok(task)
     }
 }
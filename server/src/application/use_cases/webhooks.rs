@@ -0,0 +1,410 @@
+use crate::{
+    application::services::mission_authorization::MissionAuthorization,
+    domain::{
+        entities::webhooks::{
+            AddMissionWebhookEntity, AddWebhookDeliveryEntity, EditMissionWebhookEntity,
+            MissionWebhookEntity, WebhookDeliveryEntity,
+        },
+        errors::{DomainError, DomainResult},
+        repositories::{mission_viewing::MissionViewingRepository, webhooks::WebhookRepository},
+        services::webhook_sender::{WebhookRequest, WebhookSender},
+        value_objects::{
+            crew_roles::Permission,
+            mission_events::{MissionEvent, MissionEventEnvelope},
+            webhook_model::{
+                CreateWebhookModel, EditWebhookModel, MIN_WEBHOOK_SECRET_LENGTH, WebhookModel,
+            },
+        },
+    },
+    infrastructure::jwt::generate_opaque_token,
+};
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{collections::HashMap, sync::Arc};
+
+// Deliveries sent per round trip to the queue
+const BATCH_SIZE: i64 = 50;
+// Time a claimed delivery is hidden from other workers
+const CLAIM_LEASE_MINUTES: i64 = 5;
+// Failed deliveries back off exponentially up to six hours
+const MAX_RETRY_MINUTES: i64 = 6 * 60;
+// Entries returned by the delivery log
+const DELIVERY_LOG_LIMIT: i64 = 100;
+
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+/// `sha256=` followed by the hex HMAC-SHA256 of `{timestamp}.{body}`, keyed with the webhook secret.
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// Lets chiefs point mission events at their own endpoints, and delivers
+/// those events with retries.
+pub struct WebhookUseCase<T1, T2, T3>
+where
+    T1: WebhookRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: WebhookSender + Send + Sync,
+{
+    webhook_repository: Arc<T1>,
+    authorization: MissionAuthorization<T2>,
+    webhook_sender: Arc<T3>,
+    max_attempts: i32,
+}
+
+impl<T1, T2, T3> WebhookUseCase<T1, T2, T3>
+where
+    T1: WebhookRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: WebhookSender + Send + Sync,
+{
+    pub fn new(
+        webhook_repository: Arc<T1>,
+        mission_viewing_repository: Arc<T2>,
+        webhook_sender: Arc<T3>,
+        max_attempts: i32,
+    ) -> Self {
+        Self {
+            webhook_repository,
+            authorization: MissionAuthorization::new(mission_viewing_repository),
+            webhook_sender,
+            max_attempts: max_attempts.max(1),
+        }
+    }
+
+    pub async fn create(
+        &self,
+        mission_id: i32,
+        chief_id: i32,
+        model: CreateWebhookModel,
+    ) -> DomainResult<WebhookModel> {
+        self.authorization
+            .require(mission_id, chief_id, Permission::ManageWebhooks)
+            .await?;
+
+        let url = self.ensure_target(&model.url).await?;
+        let event_types = ensure_event_types(model.event_types)?;
+        let secret = match model.secret {
+            Some(secret) => ensure_secret(secret)?,
+            None => generate_opaque_token(),
+        };
+
+        let webhook = self
+            .webhook_repository
+            .create(AddMissionWebhookEntity {
+                mission_id,
+                url,
+                secret,
+                event_types,
+                created_by: Some(chief_id),
+            })
+            .await?;
+
+        Ok(WebhookModel::from_entity(webhook, true))
+    }
+
+    pub async fn list(&self, mission_id: i32, chief_id: i32) -> DomainResult<Vec<WebhookModel>> {
+        self.authorization
+            .require(mission_id, chief_id, Permission::ManageWebhooks)
+            .await?;
+
+        let webhooks = self.webhook_repository.list_by_mission(mission_id).await?;
+
+        Ok(webhooks
+            .into_iter()
+            .map(|webhook| WebhookModel::from_entity(webhook, false))
+            .collect())
+    }
+
+    pub async fn edit(
+        &self,
+        mission_id: i32,
+        webhook_id: i32,
+        chief_id: i32,
+        model: EditWebhookModel,
+    ) -> DomainResult<WebhookModel> {
+        self.authorization
+            .require(mission_id, chief_id, Permission::ManageWebhooks)
+            .await?;
+        self.webhook_in_mission(mission_id, webhook_id).await?;
+
+        let secret = model.secret.map(ensure_secret).transpose()?;
+        let reveal_secret = secret.is_some();
+        let url = match model.url.as_deref() {
+            Some(url) => Some(self.ensure_target(url).await?),
+            None => None,
+        };
+        let entity = EditMissionWebhookEntity {
+            url,
+            secret,
+            event_types: model.event_types.map(ensure_event_types).transpose()?,
+            is_active: model.is_active,
+            updated_at: Utc::now().naive_utc(),
+        };
+
+        let webhook = self.webhook_repository.edit(webhook_id, entity).await?;
+
+        Ok(WebhookModel::from_entity(webhook, reveal_secret))
+    }
+
+    pub async fn delete(&self, mission_id: i32, webhook_id: i32, chief_id: i32) -> DomainResult<()> {
+        self.authorization
+            .require(mission_id, chief_id, Permission::ManageWebhooks)
+            .await?;
+        self.webhook_in_mission(mission_id, webhook_id).await?;
+
+        self.webhook_repository.delete(webhook_id).await?;
+        Ok(())
+    }
+
+    /// Most recent deliveries first.
+    pub async fn deliveries(
+        &self,
+        mission_id: i32,
+        webhook_id: i32,
+        chief_id: i32,
+    ) -> DomainResult<Vec<WebhookDeliveryEntity>> {
+        self.authorization
+            .require(mission_id, chief_id, Permission::ManageWebhooks)
+            .await?;
+        self.webhook_in_mission(mission_id, webhook_id).await?;
+
+        let deliveries = self
+            .webhook_repository
+            .deliveries(webhook_id, DELIVERY_LOG_LIMIT)
+            .await?;
+        Ok(deliveries)
+    }
+
+    /// Sends a `Ping` event right away, even to a disabled webhook, and
+    /// returns the delivery as it stands after the first attempt.
+    pub async fn send_test(
+        &self,
+        mission_id: i32,
+        webhook_id: i32,
+        chief_id: i32,
+    ) -> DomainResult<WebhookDeliveryEntity> {
+        self.authorization
+            .require(mission_id, chief_id, Permission::ManageWebhooks)
+            .await?;
+        let webhook = self.webhook_in_mission(mission_id, webhook_id).await?;
+
+        let envelope = MissionEventEnvelope::new(mission_id, MissionEvent::Ping { webhook_id });
+        let delivery = self
+            .webhook_repository
+            .enqueue(vec![AddWebhookDeliveryEntity {
+                webhook_id,
+                event_type: envelope.event.kind().to_string(),
+                payload: to_payload(&envelope)?,
+            }])
+            .await?
+            .pop()
+            .ok_or_else(|| DomainError::Internal(anyhow::anyhow!("Test delivery was not stored")))?;
+
+        self.deliver(delivery, &webhook).await
+    }
+
+    /// Queues a delivery for every active webhook of the mission that asked for
+    /// this kind of event. Returns how many were queued.
+    pub async fn enqueue_event(&self, envelope: &MissionEventEnvelope) -> DomainResult<usize> {
+        let kind = envelope.event.kind();
+        if !MissionEvent::WEBHOOK_KINDS.contains(&kind) {
+            return Ok(0);
+        }
+
+        let webhooks = self
+            .webhook_repository
+            .subscribed(envelope.mission_id, kind)
+            .await?;
+        if webhooks.is_empty() {
+            return Ok(0);
+        }
+
+        let payload = to_payload(envelope)?;
+        let deliveries = webhooks
+            .into_iter()
+            .map(|webhook| AddWebhookDeliveryEntity {
+                webhook_id: webhook.id,
+                event_type: kind.to_string(),
+                payload: payload.clone(),
+            })
+            .collect();
+
+        let queued = self.webhook_repository.enqueue(deliveries).await?;
+        Ok(queued.len())
+    }
+
+    /// Sends every delivery that is due. Returns how many were accepted by their receiver.
+    pub async fn process_deliveries(&self) -> DomainResult<usize> {
+        let mut delivered = 0;
+        loop {
+            let due = self
+                .webhook_repository
+                .claim_due(BATCH_SIZE, Duration::minutes(CLAIM_LEASE_MINUTES))
+                .await?;
+            let claimed = due.len() as i64;
+
+            let mut webhooks: HashMap<i32, Option<MissionWebhookEntity>> = HashMap::new();
+            for delivery in due {
+                let webhook = match webhooks.get(&delivery.webhook_id) {
+                    Some(webhook) => webhook.clone(),
+                    None => {
+                        let webhook = self.webhook_repository.get(delivery.webhook_id).await?;
+                        webhooks.insert(delivery.webhook_id, webhook.clone());
+                        webhook
+                    }
+                };
+
+                match webhook {
+                    Some(webhook) if webhook.is_active => {
+                        let delivery = self.deliver(delivery, &webhook).await?;
+                        if delivery.delivered_at.is_some() {
+                            delivered += 1;
+                        }
+                    }
+                    _ => {
+                        self.webhook_repository
+                            .mark_failed(delivery.id, None, "Webhook is disabled".to_string(), None)
+                            .await?;
+                    }
+                }
+            }
+
+            if claimed < BATCH_SIZE {
+                return Ok(delivered);
+            }
+        }
+    }
+
+    async fn deliver(
+        &self,
+        delivery: WebhookDeliveryEntity,
+        webhook: &MissionWebhookEntity,
+    ) -> DomainResult<WebhookDeliveryEntity> {
+        let timestamp = Utc::now().timestamp();
+        let signature = sign(&webhook.secret, timestamp, &delivery.payload)?;
+        let request = WebhookRequest {
+            url: webhook.url.clone(),
+            headers: vec![
+                (EVENT_HEADER.to_string(), delivery.event_type.clone()),
+                (DELIVERY_HEADER.to_string(), delivery.id.to_string()),
+                (TIMESTAMP_HEADER.to_string(), timestamp.to_string()),
+                (SIGNATURE_HEADER.to_string(), format!("sha256={}", signature)),
+            ],
+            body: delivery.payload.clone(),
+        };
+
+        let (response_status, error) = match self.webhook_sender.send(request).await {
+            Ok(response) if response.is_success() => {
+                let delivery = self
+                    .webhook_repository
+                    .mark_delivered(delivery.id, i32::from(response.status))
+                    .await?;
+                return Ok(delivery);
+            }
+            Ok(response) => (
+                Some(i32::from(response.status)),
+                format!("Receiver answered with status {}", response.status),
+            ),
+            Err(e) => (None, e.to_string()),
+        };
+
+        let attempts = delivery.attempts + 1;
+        tracing::warn!(
+            "Webhook delivery {} to {} failed (attempt {}): {}",
+            delivery.id,
+            webhook.url,
+            attempts,
+            error
+        );
+        let next_attempt_at = (attempts < self.max_attempts).then(|| {
+            let delay = 2i64.pow(delivery.attempts.clamp(0, 11) as u32).min(MAX_RETRY_MINUTES);
+            Utc::now().naive_utc() + Duration::minutes(delay)
+        });
+
+        let delivery = self
+            .webhook_repository
+            .mark_failed(delivery.id, response_status, error, next_attempt_at)
+            .await?;
+        Ok(delivery)
+    }
+
+    /// A well-formed URL the sender agrees to deliver to.
+    async fn ensure_target(&self, url: &str) -> DomainResult<String> {
+        let url = ensure_url(url)?;
+        self.webhook_sender
+            .check_target(&url)
+            .await
+            .map_err(|e| DomainError::validation(format!("Webhook URL is not allowed: {}", e)))?;
+        Ok(url)
+    }
+
+    async fn webhook_in_mission(
+        &self,
+        mission_id: i32,
+        webhook_id: i32,
+    ) -> DomainResult<MissionWebhookEntity> {
+        self.webhook_repository
+            .get(webhook_id)
+            .await?
+            .filter(|webhook| webhook.mission_id == mission_id)
+            .ok_or_else(|| DomainError::not_found("Webhook not found in this mission"))
+    }
+}
+
+fn to_payload(envelope: &MissionEventEnvelope) -> DomainResult<String> {
+    serde_json::to_string(envelope).map_err(|e| DomainError::Internal(e.into()))
+}
+
+fn sign(secret: &str, timestamp: i64, payload: &str) -> DomainResult<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| DomainError::Internal(anyhow::anyhow!(e.to_string())))?;
+    mac.update(format!("{}.{}", timestamp, payload).as_bytes());
+    Ok(format!("{:x}", mac.finalize().into_bytes()))
+}
+
+fn ensure_url(url: &str) -> DomainResult<String> {
+    let url = url.trim();
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|_| DomainError::validation("Webhook URL is not a valid URL"))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(DomainError::validation(
+            "Webhook URL must be an http or https address",
+        ));
+    }
+    Ok(url.to_string())
+}
+
+fn ensure_event_types(event_types: Vec<String>) -> DomainResult<Vec<String>> {
+    let mut accepted: Vec<String> = Vec::new();
+    for event_type in event_types {
+        let event_type = event_type.trim().to_string();
+        if !MissionEvent::WEBHOOK_KINDS.contains(&event_type.as_str()) {
+            return Err(DomainError::validation(format!(
+                "Unknown event type {}; expected one of {}",
+                event_type,
+                MissionEvent::WEBHOOK_KINDS.join(", ")
+            )));
+        }
+        if !accepted.contains(&event_type) {
+            accepted.push(event_type);
+        }
+    }
+
+    if accepted.is_empty() {
+        return Err(DomainError::validation(
+            "A webhook has to subscribe to at least one event type",
+        ));
+    }
+    Ok(accepted)
+}
+
+fn ensure_secret(secret: String) -> DomainResult<String> {
+    if secret.len() < MIN_WEBHOOK_SECRET_LENGTH || secret.len() > 255 {
+        return Err(DomainError::validation(format!(
+            "Webhook secret must be between {} and 255 characters long",
+            MIN_WEBHOOK_SECRET_LENGTH
+        )));
+    }
+    Ok(secret)
+}
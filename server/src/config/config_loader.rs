@@ -3,7 +3,12 @@ use std::env;
 use anyhow::Result;
 
 use crate::config::{
-    config_model::{CloudinaryEnv, Database, DotEnvyConfig, JwtEnv, Server},
+    config_model::{
+        CloudinaryEnv, Database, DotEnvyConfig, EmailNotificationEnv, FileCleanupEnv,
+        FileStorageBackend, FileStorageEnv, JwtEnv, LoginSecurityEnv, LoginThrottleBackend,
+        MailEnv, MailTransport, PasswordEnv, S3Env, Server, SmtpEnv, SmtpSecurity, TotpEnv,
+        UploadEnv, WebhookEnv,
+    },
     stage::Stage,
 };
 
@@ -53,6 +58,12 @@ pub fn get_jwt_env() -> Result<JwtEnv> {
     Ok(JwtEnv {
         secret: env::var("JWT_USER_SECRET")?,
         ttl: env::var("JWT_TTL")?.parse::<i64>()?,
+        access_ttl: env::var("JWT_ACCESS_TTL")
+            .unwrap_or("15".to_string())
+            .parse::<i64>()?,
+        stream_ticket_ttl: env::var("JWT_STREAM_TICKET_TTL")
+            .unwrap_or("30".to_string())
+            .parse::<i64>()?,
     })
 }
 
@@ -64,3 +75,281 @@ pub fn get_cloudinary_env() -> Result<CloudinaryEnv> {
         api_secret: env::var("CLOUDINARY_API_SECRET")?,
     })
 }
+
+pub fn get_s3_env() -> Result<S3Env> {
+    dotenvy::dotenv().ok();
+    Ok(S3Env {
+        endpoint: env::var("S3_ENDPOINT")?.trim_end_matches('/').to_string(),
+        region: env::var("S3_REGION").unwrap_or("us-east-1".to_string()),
+        bucket: env::var("S3_BUCKET")?,
+        access_key: env::var("S3_ACCESS_KEY")?,
+        secret_key: env::var("S3_SECRET_KEY")?,
+        path_style: env::var("S3_PATH_STYLE")
+            .map(|v| v == "true")
+            .unwrap_or(true),
+    })
+}
+
+pub fn get_file_storage_env() -> Result<FileStorageEnv> {
+    dotenvy::dotenv().ok();
+
+    let backend = match env::var("FILE_STORAGE")
+        .unwrap_or("cloudinary".to_string())
+        .to_lowercase()
+        .as_str()
+    {
+        "cloudinary" => FileStorageBackend::Cloudinary,
+        "local" => FileStorageBackend::Local,
+        "s3" => FileStorageBackend::S3,
+        other => anyhow::bail!("Unknown FILE_STORAGE: {}", other),
+    };
+
+    let signing_secret = env::var("FILE_SIGNING_SECRET")?;
+    // A leaked file link must not help forge access tokens
+    if env::var("JWT_USER_SECRET").is_ok_and(|jwt_secret| jwt_secret == signing_secret) {
+        anyhow::bail!("FILE_SIGNING_SECRET must differ from JWT_USER_SECRET");
+    }
+
+    Ok(FileStorageEnv {
+        backend,
+        local_dir: env::var("FILE_STORAGE_DIR").unwrap_or("uploads".to_string()),
+        public_base_url: env::var("FILE_PUBLIC_BASE_URL")
+            .unwrap_or("/api/files".to_string())
+            .trim_end_matches('/')
+            .to_string(),
+        signing_secret,
+        cloudinary: match backend {
+            FileStorageBackend::Cloudinary => Some(get_cloudinary_env()?),
+            _ => None,
+        },
+        s3: match backend {
+            FileStorageBackend::S3 => Some(get_s3_env()?),
+            _ => None,
+        },
+    })
+}
+
+pub fn get_upload_env() -> Result<UploadEnv> {
+    dotenvy::dotenv().ok();
+    Ok(UploadEnv {
+        max_mb: env::var("UPLOAD_MAX_MB")
+            .unwrap_or("100".to_string())
+            .parse::<i32>()?,
+    })
+}
+
+pub fn get_file_cleanup_env() -> Result<FileCleanupEnv> {
+    dotenvy::dotenv().ok();
+    Ok(FileCleanupEnv {
+        poll_seconds: env::var("FILE_CLEANUP_POLL_SECONDS")
+            .unwrap_or("60".to_string())
+            .parse::<u64>()?,
+        reconcile_hours: env::var("FILE_RECONCILE_HOURS")
+            .unwrap_or("24".to_string())
+            .parse::<u64>()?,
+        grace_minutes: env::var("FILE_RECONCILE_GRACE_MINUTES")
+            .unwrap_or("60".to_string())
+            .parse::<i64>()?,
+    })
+}
+
+pub fn get_webhook_env() -> Result<WebhookEnv> {
+    dotenvy::dotenv().ok();
+    Ok(WebhookEnv {
+        poll_seconds: env::var("WEBHOOK_POLL_SECONDS")
+            .unwrap_or("10".to_string())
+            .parse::<u64>()?,
+        max_attempts: env::var("WEBHOOK_MAX_ATTEMPTS")
+            .unwrap_or("8".to_string())
+            .parse::<i32>()?,
+        timeout_seconds: env::var("WEBHOOK_TIMEOUT_SECONDS")
+            .unwrap_or("10".to_string())
+            .parse::<u64>()?,
+        allow_private_targets: env::var("WEBHOOK_ALLOW_PRIVATE_TARGETS")
+            .unwrap_or("false".to_string())
+            .parse::<bool>()?,
+    })
+}
+
+const COMMON_PASSWORDS: [&str; 20] = [
+    "123456", "12345678", "123456789", "1234567890", "password", "password1", "password123",
+    "qwerty", "qwerty123", "abc123", "111111", "000000", "iloveyou", "admin", "admin123",
+    "welcome", "letmein", "monkey", "dragon", "football",
+];
+
+pub fn get_password_env() -> Result<PasswordEnv> {
+    dotenvy::dotenv().ok();
+
+    let mut banned_passwords: Vec<String> =
+        COMMON_PASSWORDS.iter().map(|p| p.to_string()).collect();
+    if let Ok(extra) = env::var("PASSWORD_BANNED_LIST") {
+        banned_passwords.extend(
+            extra
+                .split(',')
+                .map(|p| p.trim().to_lowercase())
+                .filter(|p| !p.is_empty()),
+        );
+    }
+
+    Ok(PasswordEnv {
+        min_length: env::var("PASSWORD_MIN_LENGTH")
+            .unwrap_or("8".to_string())
+            .parse::<usize>()?,
+        banned_passwords,
+        reset_ttl: env::var("PASSWORD_RESET_TTL")
+            .unwrap_or("30".to_string())
+            .parse::<i64>()?,
+    })
+}
+
+pub fn get_smtp_env() -> Result<SmtpEnv> {
+    dotenvy::dotenv().ok();
+
+    let security = match env::var("SMTP_SECURITY")
+        .unwrap_or("starttls".to_string())
+        .to_lowercase()
+        .as_str()
+    {
+        "none" => SmtpSecurity::None,
+        "starttls" => SmtpSecurity::StartTls,
+        "tls" => SmtpSecurity::Tls,
+        other => anyhow::bail!("Unknown SMTP_SECURITY: {}", other),
+    };
+    let default_port = match security {
+        SmtpSecurity::None => "25",
+        SmtpSecurity::StartTls => "587",
+        SmtpSecurity::Tls => "465",
+    };
+
+    let username = env::var("SMTP_USERNAME").ok().filter(|username| !username.is_empty());
+    if username.is_some() && security == SmtpSecurity::None {
+        // Credentials would cross the wire in the clear
+        anyhow::bail!("SMTP_USERNAME requires SMTP_SECURITY to be starttls or tls");
+    }
+
+    Ok(SmtpEnv {
+        host: env::var("SMTP_HOST")?,
+        port: env::var("SMTP_PORT")
+            .unwrap_or(default_port.to_string())
+            .parse::<u16>()?,
+        security,
+        username,
+        password: env::var("SMTP_PASSWORD").ok(),
+        hello_name: env::var("SMTP_HELLO_NAME").unwrap_or("localhost".to_string()),
+        timeout_seconds: env::var("SMTP_TIMEOUT_SECONDS")
+            .unwrap_or("30".to_string())
+            .parse::<u64>()?,
+    })
+}
+
+pub fn get_mail_env() -> Result<MailEnv> {
+    dotenvy::dotenv().ok();
+
+    let transport = match env::var("MAIL_TRANSPORT")
+        .unwrap_or("file".to_string())
+        .to_lowercase()
+        .as_str()
+    {
+        "file" => MailTransport::File,
+        "smtp" => MailTransport::Smtp,
+        other => anyhow::bail!("Unknown MAIL_TRANSPORT: {}", other),
+    };
+
+    Ok(MailEnv {
+        from: env::var("MAIL_FROM").unwrap_or("no-reply@localhost".to_string()),
+        transport,
+        outbox_dir: env::var("MAIL_OUTBOX_DIR").unwrap_or("outbox".to_string()),
+        smtp: match transport {
+            MailTransport::Smtp => Some(get_smtp_env()?),
+            MailTransport::File => None,
+        },
+        verification_ttl: env::var("EMAIL_VERIFICATION_TTL")
+            .unwrap_or("1440".to_string())
+            .parse::<i64>()?,
+        verification_limit: env::var("EMAIL_VERIFICATION_LIMIT")
+            .unwrap_or("5".to_string())
+            .parse::<i64>()?,
+        verification_window: env::var("EMAIL_VERIFICATION_WINDOW")
+            .unwrap_or("60".to_string())
+            .parse::<i64>()?,
+    })
+}
+
+pub fn get_email_notification_env() -> Result<EmailNotificationEnv> {
+    dotenvy::dotenv().ok();
+
+    let digest_hour = env::var("MAIL_DIGEST_HOUR")
+        .unwrap_or("7".to_string())
+        .parse::<u32>()?;
+    if digest_hour > 23 {
+        anyhow::bail!("MAIL_DIGEST_HOUR must be between 0 and 23");
+    }
+
+    Ok(EmailNotificationEnv {
+        poll_seconds: env::var("MAIL_POLL_SECONDS")
+            .unwrap_or("30".to_string())
+            .parse::<u64>()?,
+        max_attempts: env::var("MAIL_MAX_ATTEMPTS")
+            .unwrap_or("6".to_string())
+            .parse::<i32>()?,
+        digest_hour,
+        deadline_reminder_hours: env::var("MAIL_DEADLINE_REMINDER_HOURS")
+            .unwrap_or("24".to_string())
+            .parse::<i64>()?,
+    })
+}
+
+pub fn get_login_security_env() -> Result<LoginSecurityEnv> {
+    dotenvy::dotenv().ok();
+
+    let throttle_backend = match env::var("LOGIN_THROTTLE_STORE")
+        .unwrap_or("memory".to_string())
+        .to_lowercase()
+        .as_str()
+    {
+        "memory" => LoginThrottleBackend::Memory,
+        "postgres" => LoginThrottleBackend::Postgres,
+        other => anyhow::bail!("Unknown LOGIN_THROTTLE_STORE: {}", other),
+    };
+
+    let admin_ids = env::var("ADMIN_BRAWLER_IDS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse::<i32>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(LoginSecurityEnv {
+        max_failures: env::var("LOGIN_MAX_FAILURES")
+            .unwrap_or("5".to_string())
+            .parse::<u32>()?,
+        lockout_base: env::var("LOGIN_LOCKOUT_BASE")
+            .unwrap_or("30".to_string())
+            .parse::<i64>()?,
+        lockout_max: env::var("LOGIN_LOCKOUT_MAX")
+            .unwrap_or("900".to_string())
+            .parse::<i64>()?,
+        failure_window: env::var("LOGIN_FAILURE_WINDOW")
+            .unwrap_or("3600".to_string())
+            .parse::<i64>()?,
+        throttle_backend,
+        trust_proxy_headers: env::var("TRUST_PROXY_HEADERS")
+            .map(|v| v == "true")
+            .unwrap_or(false),
+        admin_ids,
+    })
+}
+
+pub fn get_totp_env() -> Result<TotpEnv> {
+    dotenvy::dotenv().ok();
+    Ok(TotpEnv {
+        issuer: env::var("TOTP_ISSUER").unwrap_or("Brawlers".to_string()),
+        challenge_ttl: env::var("TOTP_CHALLENGE_TTL")
+            .unwrap_or("300".to_string())
+            .parse::<i64>()?,
+        recovery_codes: env::var("TOTP_RECOVERY_CODES")
+            .unwrap_or("10".to_string())
+            .parse::<usize>()?,
+    })
+}
@@ -13,7 +13,12 @@ pub struct Database {
 #[derive(Debug, Clone)]
 pub struct JwtEnv {
     pub secret: String,
+    // Refresh session lifetime in days
     pub ttl: i64,
+    // Access token lifetime in minutes
+    pub access_ttl: i64,
+    // Event stream ticket lifetime in seconds
+    pub stream_ticket_ttl: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +28,161 @@ pub struct CloudinaryEnv {
     pub api_secret: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct PasswordEnv {
+    pub min_length: usize,
+    pub banned_passwords: Vec<String>,
+    // Reset token lifetime in minutes
+    pub reset_ttl: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MailTransport {
+    File,
+    Smtp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmtpSecurity {
+    // Plain connection, only for relays on a trusted network
+    None,
+    // Upgraded with STARTTLS after the greeting, usually port 587
+    StartTls,
+    // TLS from the first byte, usually port 465
+    Tls,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpEnv {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    // Login is skipped when no username is set, and refused without TLS
+    pub username: Option<String>,
+    pub password: Option<String>,
+    // Name announced in EHLO
+    pub hello_name: String,
+    pub timeout_seconds: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MailEnv {
+    pub from: String,
+    pub transport: MailTransport,
+    // Where the file transport drops its messages
+    pub outbox_dir: String,
+    pub smtp: Option<SmtpEnv>,
+    // Email verification token lifetime in minutes
+    pub verification_ttl: i64,
+    // Verification mails a brawler may request per window
+    pub verification_limit: i64,
+    // Length of that window in minutes
+    pub verification_window: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailNotificationEnv {
+    // How often queued mail, digests and deadline reminders are processed, in seconds
+    pub poll_seconds: u64,
+    // Attempts before a mail is given up as Failed
+    pub max_attempts: i32,
+    // Hour of the day (UTC) from which the daily digest goes out
+    pub digest_hour: u32,
+    // How long before a mission's end date its crew is reminded, in hours
+    pub deadline_reminder_hours: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TotpEnv {
+    // Shown next to the account in authenticator apps
+    pub issuer: String,
+    // Lifetime of the login challenge token in seconds
+    pub challenge_ttl: i64,
+    pub recovery_codes: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoginThrottleBackend {
+    Memory,
+    Postgres,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginSecurityEnv {
+    // Consecutive failures allowed before a key gets locked out
+    pub max_failures: u32,
+    // First lockout in seconds, doubled on every further failure
+    pub lockout_base: i64,
+    // Upper bound for a single lockout in seconds
+    pub lockout_max: i64,
+    // Failures older than this many seconds no longer count
+    pub failure_window: i64,
+    pub throttle_backend: LoginThrottleBackend,
+    // Take the client IP from X-Forwarded-For when running behind a proxy
+    pub trust_proxy_headers: bool,
+    pub admin_ids: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileStorageBackend {
+    Cloudinary,
+    Local,
+    S3,
+}
+
+#[derive(Debug, Clone)]
+pub struct S3Env {
+    // Base URL of the service, e.g. http://localhost:9000 for MinIO
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+    // Address objects as endpoint/bucket/key instead of bucket.endpoint/key
+    pub path_style: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileStorageEnv {
+    pub backend: FileStorageBackend,
+    // Root directory of the local backend
+    pub local_dir: String,
+    // Where the local backend's download route is mounted
+    pub public_base_url: String,
+    // Signs download links handed out by the local backend
+    pub signing_secret: String,
+    pub cloudinary: Option<CloudinaryEnv>,
+    pub s3: Option<S3Env>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UploadEnv {
+    // Server-wide cap in megabytes; a mission's own limit can only be lower
+    pub max_mb: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileCleanupEnv {
+    // How often queued file deletions are retried, in seconds
+    pub poll_seconds: u64,
+    // How often stored files are checked for orphans, in hours; 0 turns it off
+    pub reconcile_hours: u64,
+    // Files younger than this many minutes are never treated as orphans
+    pub grace_minutes: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookEnv {
+    // How often due webhook deliveries are sent, in seconds
+    pub poll_seconds: u64,
+    // Attempts before a delivery is given up as Failed
+    pub max_attempts: i32,
+    // How long a receiver gets to answer, in seconds
+    pub timeout_seconds: u64,
+    // Lets webhooks reach loopback, private and link-local addresses; for local testing only
+    pub allow_private_targets: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct DotEnvyConfig {
     pub server: Server,
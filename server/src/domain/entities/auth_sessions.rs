@@ -0,0 +1,40 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::infrastructure::database::schema::{auth_sessions, refresh_tokens};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = auth_sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuthSessionEntity {
+    pub id: i32,
+    pub brawler_id: i32,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = auth_sessions)]
+pub struct AddAuthSessionEntity {
+    pub brawler_id: i32,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = refresh_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RefreshTokenEntity {
+    pub id: i32,
+    pub session_id: i32,
+    pub token_hash: String,
+    pub created_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = refresh_tokens)]
+pub struct AddRefreshTokenEntity {
+    pub session_id: i32,
+    pub token_hash: String,
+}
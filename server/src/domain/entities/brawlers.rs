@@ -14,6 +14,10 @@ pub struct BrawlerEntity {
     pub avatar_url: Option<String>,
     pub avatar_public_id: Option<String>,
     pub bio: Option<String>,
+    pub email: Option<String>,
+    pub email_verified_at: Option<NaiveDateTime>,
+    pub email_digest: bool,
+    pub last_digest_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, Insertable)]
@@ -22,4 +26,5 @@ pub struct RegisterBrawlerEntity {
     pub username: String,
     pub password: String,
     pub display_name: String,
+    pub email: Option<String>,
 }
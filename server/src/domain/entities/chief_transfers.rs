@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::chief_transfers;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = chief_transfers)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ChiefTransferEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub from_brawler_id: i32,
+    pub to_brawler_id: i32,
+    pub old_chief_role: Option<String>,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub resolved_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = chief_transfers)]
+pub struct AddChiefTransferEntity {
+    pub mission_id: i32,
+    pub from_brawler_id: i32,
+    pub to_brawler_id: i32,
+    pub old_chief_role: Option<String>,
+}
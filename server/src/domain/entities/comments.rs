@@ -0,0 +1,42 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::{comment_edits, comments};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = comments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CommentEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub task_id: Option<i32>,
+    pub submission_id: Option<i32>,
+    pub parent_id: Option<i32>,
+    pub author_id: Option<i32>,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+    pub edited_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = comments)]
+pub struct AddCommentEntity {
+    pub mission_id: i32,
+    pub task_id: Option<i32>,
+    pub submission_id: Option<i32>,
+    pub parent_id: Option<i32>,
+    pub author_id: Option<i32>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = comment_edits)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CommentEditEntity {
+    pub id: i32,
+    pub comment_id: i32,
+    pub body: String,
+    pub edited_at: NaiveDateTime,
+}
@@ -0,0 +1,24 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::infrastructure::database::schema::file_deletions;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, QueryableByName)]
+#[diesel(table_name = file_deletions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FileDeletionEntity {
+    pub id: i32,
+    pub store: String,
+    pub storage_key: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = file_deletions)]
+pub struct AddFileDeletionEntity {
+    pub store: String,
+    pub storage_key: String,
+}
@@ -0,0 +1,37 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::{login_attempts, login_throttles};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = login_attempts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LoginAttemptEntity {
+    pub id: i32,
+    pub username: String,
+    pub brawler_id: Option<i32>,
+    pub ip_address: String,
+    pub success: bool,
+    pub reason: Option<String>,
+    pub attempted_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = login_attempts)]
+pub struct AddLoginAttemptEntity {
+    pub username: String,
+    pub brawler_id: Option<i32>,
+    pub ip_address: String,
+    pub success: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable, Insertable)]
+#[diesel(table_name = login_throttles)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LoginThrottleEntity {
+    pub throttle_key: String,
+    pub failures: i32,
+    pub last_failure_at: NaiveDateTime,
+}
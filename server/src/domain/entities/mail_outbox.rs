@@ -0,0 +1,30 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::infrastructure::database::schema::mail_outbox;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, QueryableByName)]
+#[diesel(table_name = mail_outbox)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MailOutboxEntity {
+    pub id: i32,
+    pub brawler_id: Option<i32>,
+    pub to_address: String,
+    pub subject: String,
+    pub body: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub sent_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mail_outbox)]
+pub struct AddMailOutboxEntity {
+    pub brawler_id: Option<i32>,
+    pub to_address: String,
+    pub subject: String,
+    pub body: String,
+}
@@ -0,0 +1,40 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::mission_invitations;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = mission_invitations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionInvitationEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub code: String,
+    pub created_by: i32,
+    pub invitee_id: Option<i32>,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl MissionInvitationEntity {
+    pub fn is_usable(&self, now: NaiveDateTime) -> bool {
+        self.revoked_at.is_none()
+            && self.expires_at > now
+            && self.max_uses.is_none_or(|max_uses| self.use_count < max_uses)
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_invitations)]
+pub struct AddMissionInvitationEntity {
+    pub mission_id: i32,
+    pub code: String,
+    pub created_by: i32,
+    pub invitee_id: Option<i32>,
+    pub max_uses: Option<i32>,
+    pub expires_at: NaiveDateTime,
+}
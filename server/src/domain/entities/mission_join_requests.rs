@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::mission_join_requests;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = mission_join_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionJoinRequestEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub brawler_id: i32,
+    pub message: Option<String>,
+    pub status: String,
+    pub reviewed_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub reviewed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_join_requests)]
+pub struct AddMissionJoinRequestEntity {
+    pub mission_id: i32,
+    pub brawler_id: i32,
+    pub message: Option<String>,
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::mission_status_history;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = mission_status_history)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionStatusHistoryEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub changed_by: Option<i32>,
+    pub from_status: String,
+    pub to_status: String,
+    pub reason: Option<String>,
+    pub changed_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_status_history)]
+pub struct AddMissionStatusHistoryEntity {
+    pub mission_id: i32,
+    pub changed_by: Option<i32>,
+    pub from_status: String,
+    pub to_status: String,
+    pub reason: Option<String>,
+}
@@ -16,6 +16,15 @@ pub struct MissionSubmission {
     pub submitted_at: DateTime<Utc>,
     pub task_id: Option<i32>,
     pub description: Option<String>,
+    pub review_status: String,
+    pub reviewed_by: Option<i32>,
+    pub review_feedback: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub version: i32,
+    pub is_current: bool,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing)]
+    pub storage_key: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -28,4 +37,14 @@ pub struct NewMissionSubmission<'a> {
     pub file_type: &'a str,
     pub task_id: Option<i32>,
     pub description: Option<&'a str>,
+    pub storage_key: Option<&'a str>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = mission_submissions)]
+pub struct ReviewMissionSubmission {
+    pub review_status: String,
+    pub reviewed_by: i32,
+    pub review_feedback: Option<String>,
+    pub reviewed_at: DateTime<Utc>,
 }
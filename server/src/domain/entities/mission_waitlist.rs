@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::mission_waitlist;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = mission_waitlist)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionWaitlistEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub brawler_id: i32,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_waitlist)]
+pub struct AddMissionWaitlistEntity {
+    pub mission_id: i32,
+    pub brawler_id: i32,
+}
@@ -18,6 +18,11 @@ pub struct MissionEntity {
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
     pub max_members: i32,
+    pub visibility: String,
+    pub max_upload_mb: i32,
+    pub allowed_file_types: Vec<String>,
+    pub end_date: Option<NaiveDateTime>,
+    pub tags: Vec<String>,
 }
 
 impl MissionEntity {
@@ -31,6 +36,11 @@ impl MissionEntity {
             chief_display_name,
             member_count,
             max_members: self.max_members,
+            visibility: self.visibility.clone(),
+            max_upload_mb: self.max_upload_mb,
+            allowed_file_types: self.allowed_file_types.clone(),
+            end_date: self.end_date,
+            tags: self.tags.clone(),
             created_at: self.created_at,
             updated_at: self.updated_at,
             is_joined: false,
@@ -46,14 +56,24 @@ pub struct AddMissionEntity {
     pub status: String,
     pub description: Option<String>,
     pub max_members: i32,
+    pub visibility: String,
+    pub max_upload_mb: i32,
+    pub allowed_file_types: Vec<String>,
+    pub end_date: Option<NaiveDateTime>,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, AsChangeset)]
 #[diesel(table_name = missions)]
 pub struct EditMissionEntity {
-    pub chief_id: i32,
     pub name: Option<String>,
     pub description: Option<String>,
     pub max_members: Option<i32>,
-    pub status: Option<String>,
+    pub visibility: Option<String>,
+    pub max_upload_mb: Option<i32>,
+    pub allowed_file_types: Option<Vec<String>>,
+    pub end_date: Option<NaiveDateTime>,
+    pub deadline_reminded_at: Option<Option<NaiveDateTime>>,
+    pub tags: Option<Vec<String>>,
+    pub updated_at: NaiveDateTime,
 }
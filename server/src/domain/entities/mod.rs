@@ -1,5 +1,21 @@
+pub mod auth_sessions;
 pub mod brawlers;
+pub mod chief_transfers;
+pub mod comments;
 pub mod crew_memberships;
+pub mod email_verifications;
+pub mod file_deletions;
+pub mod login_attempts;
+pub mod mail_outbox;
+pub mod mission_invitations;
+pub mod mission_join_requests;
+pub mod mission_status_history;
+pub mod mission_waitlist;
 pub mod missions;
+pub mod notifications;
+pub mod password_resets;
+pub mod task_dependencies;
 pub mod tasks;
+pub mod two_factor;
+pub mod webhooks;
 pub mod mission_submissions;
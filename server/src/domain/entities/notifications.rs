@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::{notification_preferences, notifications};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
+#[diesel(table_name = notifications)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationEntity {
+    pub id: i32,
+    pub brawler_id: i32,
+    pub kind: String,
+    pub mission_id: Option<i32>,
+    pub message: String,
+    pub read_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = notifications)]
+pub struct AddNotificationEntity {
+    pub brawler_id: i32,
+    pub kind: String,
+    pub mission_id: Option<i32>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable, Insertable)]
+#[diesel(table_name = notification_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationPreferenceEntity {
+    pub brawler_id: i32,
+    pub kind: String,
+    pub enabled: bool,
+    pub updated_at: NaiveDateTime,
+    pub email_enabled: Option<bool>,
+}
@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::task_dependencies;
+
+#[derive(Debug, Clone, Selectable, Queryable, Serialize)]
+#[diesel(table_name = task_dependencies)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TaskDependencyEntity {
+    pub task_id: i32,
+    pub blocked_by_task_id: i32,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = task_dependencies)]
+pub struct AddTaskDependencyEntity {
+    pub task_id: i32,
+    pub blocked_by_task_id: i32,
+}
@@ -14,6 +14,7 @@ pub struct CreateTaskEntity {
     pub priority: String,
     pub start_date: Option<NaiveDateTime>,
     pub end_date: Option<NaiveDateTime>,
+    pub parent_task_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, AsChangeset)]
@@ -44,4 +45,5 @@ pub struct TaskEntityInternal {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub has_submission: bool,
+    pub parent_task_id: Option<i32>,
 }
@@ -0,0 +1,29 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::infrastructure::database::schema::{brawler_totp, totp_recovery_codes};
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = brawler_totp)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BrawlerTotpEntity {
+    pub brawler_id: i32,
+    pub secret: String,
+    pub enabled_at: Option<NaiveDateTime>,
+    pub last_used_step: Option<i64>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = brawler_totp)]
+pub struct AddBrawlerTotpEntity {
+    pub brawler_id: i32,
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = totp_recovery_codes)]
+pub struct AddRecoveryCodeEntity {
+    pub brawler_id: i32,
+    pub code_hash: String,
+}
@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::infrastructure::database::schema::{mission_webhooks, webhook_deliveries};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = mission_webhooks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MissionWebhookEntity {
+    pub id: i32,
+    pub mission_id: i32,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mission_webhooks)]
+pub struct AddMissionWebhookEntity {
+    pub mission_id: i32,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub created_by: Option<i32>,
+}
+
+#[derive(Debug, Clone, AsChangeset)]
+#[diesel(table_name = mission_webhooks)]
+pub struct EditMissionWebhookEntity {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable, QueryableByName, Serialize)]
+#[diesel(table_name = webhook_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookDeliveryEntity {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct AddWebhookDeliveryEntity {
+    pub webhook_id: i32,
+    pub event_type: String,
+    pub payload: String,
+}
@@ -0,0 +1,105 @@
+use std::fmt::Display;
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+pub type DomainResult<T> = std::result::Result<T, DomainError>;
+
+#[derive(Debug)]
+pub enum DomainError {
+    NotFound(String),
+    Forbidden(String),
+    Conflict(String),
+    Validation(String),
+    Unauthorized(String),
+    TooManyRequests(String),
+    PayloadTooLarge(String),
+    Internal(anyhow::Error),
+}
+
+impl DomainError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::Forbidden(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict(message.into())
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::Validation(message.into())
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::Unauthorized(message.into())
+    }
+
+    pub fn too_many_requests(message: impl Into<String>) -> Self {
+        Self::TooManyRequests(message.into())
+    }
+
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::PayloadTooLarge(message.into())
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::NotFound(_) => "NOT_FOUND",
+            DomainError::Forbidden(_) => "FORBIDDEN",
+            DomainError::Conflict(_) => "CONFLICT",
+            DomainError::Validation(_) => "VALIDATION_ERROR",
+            DomainError::Unauthorized(_) => "UNAUTHORIZED",
+            DomainError::TooManyRequests(_) => "TOO_MANY_REQUESTS",
+            DomainError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            DomainError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+impl Display for DomainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DomainError::NotFound(message)
+            | DomainError::Forbidden(message)
+            | DomainError::Conflict(message)
+            | DomainError::Validation(message)
+            | DomainError::Unauthorized(message)
+            | DomainError::TooManyRequests(message)
+            | DomainError::PayloadTooLarge(message) => write!(f, "{}", message),
+            DomainError::Internal(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for DomainError {}
+
+// Repositories still speak `anyhow`, so the diesel errors that carry meaning
+// for the caller are picked out here and everything else stays internal.
+impl From<anyhow::Error> for DomainError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast_ref::<DieselError>() {
+            Some(DieselError::NotFound) => DomainError::not_found("Resource not found"),
+            // Constraint and column names stay in the log, callers only learn what went wrong
+            Some(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info)) => {
+                tracing::warn!(
+                    "Unique violation on {}: {}",
+                    info.constraint_name().unwrap_or("unknown constraint"),
+                    info.message()
+                );
+                DomainError::conflict("Resource already exists")
+            }
+            Some(DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info)) => {
+                tracing::warn!(
+                    "Foreign key violation on {}: {}",
+                    info.constraint_name().unwrap_or("unknown constraint"),
+                    info.message()
+                );
+                DomainError::validation("Referenced resource does not exist")
+            }
+            _ => DomainError::Internal(error),
+        }
+    }
+}
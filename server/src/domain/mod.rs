@@ -1,3 +1,5 @@
 pub mod entities;
+pub mod errors;
 pub mod repositories;
+pub mod services;
 pub mod value_objects;
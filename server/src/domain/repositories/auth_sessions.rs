@@ -0,0 +1,32 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::domain::entities::auth_sessions::{
+    AddAuthSessionEntity, AuthSessionEntity, RefreshTokenEntity,
+};
+
+#[async_trait]
+pub trait AuthSessionRepository {
+    async fn start(
+        &self,
+        add_session_entity: AddAuthSessionEntity,
+        token_hash: String,
+    ) -> Result<AuthSessionEntity>;
+    async fn find_by_token_hash(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<(RefreshTokenEntity, AuthSessionEntity)>>;
+    /// Marks `used_token_id` as used and stores its successor. Returns `false`
+    /// when the token had already been used, i.e. another request won the race.
+    async fn rotate(
+        &self,
+        used_token_id: i32,
+        session_id: i32,
+        token_hash: String,
+        expires_at: NaiveDateTime,
+    ) -> Result<bool>;
+    async fn revoke(&self, session_id: i32) -> Result<()>;
+    async fn revoke_all(&self, brawler_id: i32) -> Result<()>;
+    async fn is_active(&self, session_id: i32, brawler_id: i32) -> Result<bool>;
+}
@@ -6,14 +6,14 @@ use crate::{
             base64_img::Base64Img, uploaded_img::UploadedImg, brawler_model::UpdateBrawlerModel,
         },
     },
-    infrastructure::{cloudinary::UploadImageOptions, jwt::jwt_model::Passport},
+    infrastructure::cloudinary::UploadImageOptions,
 };
 use anyhow::Result;
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait BrawlerRepository {
-    async fn register(&self, register_brawler_entity: RegisterBrawlerEntity) -> Result<Passport>;
+    async fn register(&self, register_brawler_entity: RegisterBrawlerEntity) -> Result<BrawlerEntity>;
     async fn find_by_username(&self, username: String) -> Result<BrawlerEntity>;
     async fn find_by_id(&self, brawler_id: i32) -> Result<BrawlerEntity>;
     async fn upload_base64img(
@@ -23,6 +23,12 @@ pub trait BrawlerRepository {
         opt: UploadImageOptions,
     ) -> Result<UploadedImg>;
     async fn member_counting(&self, mission_id: i32) -> Result<u32>;
+    /// Whether `brawler_id` is in the crew of any live mission led by `chief_id`.
+    async fn is_led_by(&self, brawler_id: i32, chief_id: i32) -> Result<bool>;
     async fn get_missions(&self, brawler_id: i32) -> Result<Vec<MissionEntity>>;
     async fn update_profile(&self, brawler_id: i32, model: UpdateBrawlerModel) -> Result<()>;
+    async fn update_password(&self, brawler_id: i32, hashed_password: String) -> Result<()>;
+    async fn set_email_digest(&self, brawler_id: i32, enabled: bool) -> Result<()>;
+    /// Public ids of every avatar still in use, used to spot orphans.
+    async fn avatar_public_ids(&self) -> Result<Vec<String>>;
 }
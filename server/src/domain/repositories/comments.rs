@@ -0,0 +1,26 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::comments::{AddCommentEntity, CommentEditEntity, CommentEntity},
+    value_objects::comment_model::{CommentModel, CommentTarget},
+};
+
+#[async_trait]
+pub trait CommentRepository {
+    /// Stores the comment and notifies every mentioned brawler but the author.
+    async fn create(&self, entity: AddCommentEntity, mentions: Vec<i32>) -> Result<CommentEntity>;
+    /// Keeps the previous body in the edit history and notifies only brawlers
+    /// who were not mentioned before.
+    async fn edit(&self, id: i32, body: String, mentions: Vec<i32>) -> Result<CommentEntity>;
+    /// Blanks the comment and drops its history, leaving a placeholder for its replies.
+    async fn delete(&self, id: i32) -> Result<()>;
+    async fn get_by_id(&self, id: i32) -> Result<Option<CommentEntity>>;
+    /// Every comment on the target, oldest first.
+    async fn get_by_target(&self, target: CommentTarget) -> Result<Vec<CommentModel>>;
+    /// Earlier bodies of the comment, oldest first.
+    async fn get_edits(&self, id: i32) -> Result<Vec<CommentEditEntity>>;
+    /// Ids of the mission's chief and crew whose username is in `usernames`,
+    /// compared case-insensitively.
+    async fn find_members_by_username(&self, mission_id: i32, usernames: Vec<String>) -> Result<Vec<i32>>;
+}
@@ -1,11 +1,84 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::domain::entities::crew_memberships::CrewMemberShips;
+use crate::domain::entities::{
+    chief_transfers::{AddChiefTransferEntity, ChiefTransferEntity},
+    crew_memberships::CrewMemberShips,
+    mission_invitations::{AddMissionInvitationEntity, MissionInvitationEntity},
+    mission_join_requests::{AddMissionJoinRequestEntity, MissionJoinRequestEntity},
+    mission_waitlist::{AddMissionWaitlistEntity, MissionWaitlistEntity},
+};
 
 #[async_trait]
 pub trait CrewOperationRepository {
     async fn join(&self, crew_member_ships: CrewMemberShips) -> Result<()>;
     async fn leave(&self, crew_member_ships: CrewMemberShips) -> Result<()>;
     async fn update_role(&self, mission_id: i32, brawler_id: i32, role: String) -> Result<()>;
+    /// Opens a pending transfer, cancelling any other one still pending for the mission.
+    async fn create_chief_transfer(
+        &self,
+        add_chief_transfer_entity: AddChiefTransferEntity,
+    ) -> Result<ChiefTransferEntity>;
+    async fn find_chief_transfer(&self, transfer_id: i32) -> Result<ChiefTransferEntity>;
+    async fn chief_transfers(&self, mission_id: i32) -> Result<Vec<ChiefTransferEntity>>;
+    /// Closes a pending transfer without swapping. Returns `false` if it was no longer pending.
+    async fn resolve_chief_transfer(&self, transfer_id: i32, status: String) -> Result<bool>;
+    /// Swaps the chief in one transaction. Returns `false` if the transfer is no longer
+    /// pending, the mission changed hands meanwhile or the target left the crew.
+    async fn complete_chief_transfer(&self, transfer_id: i32) -> Result<bool>;
+    async fn create_invitation(
+        &self,
+        add_mission_invitation_entity: AddMissionInvitationEntity,
+    ) -> Result<MissionInvitationEntity>;
+    async fn find_invitation_by_code(&self, code: &str) -> Result<Option<MissionInvitationEntity>>;
+    async fn invitations(&self, mission_id: i32) -> Result<Vec<MissionInvitationEntity>>;
+    /// Returns `false` if the invitation does not belong to the mission or was already revoked.
+    async fn revoke_invitation(&self, mission_id: i32, invitation_id: i32) -> Result<bool>;
+    /// Counts a use and adds the membership in one transaction. Returns `false` if the
+    /// invitation was revoked, expired or used up meanwhile.
+    async fn redeem_invitation(
+        &self,
+        invitation_id: i32,
+        crew_member_ships: CrewMemberShips,
+    ) -> Result<bool>;
+    async fn create_join_request(
+        &self,
+        add_mission_join_request_entity: AddMissionJoinRequestEntity,
+    ) -> Result<MissionJoinRequestEntity>;
+    async fn find_join_request(&self, request_id: i32) -> Result<MissionJoinRequestEntity>;
+    async fn join_requests(
+        &self,
+        mission_id: i32,
+        status: Option<String>,
+    ) -> Result<Vec<MissionJoinRequestEntity>>;
+    /// Marks a pending request approved and adds the membership in one transaction.
+    /// Returns `false` if the request was no longer pending.
+    async fn approve_join_request(
+        &self,
+        request_id: i32,
+        reviewer_id: i32,
+        crew_member_ships: CrewMemberShips,
+    ) -> Result<bool>;
+    /// Closes a pending request without admitting anyone. Returns `false` if it was no longer pending.
+    async fn resolve_join_request(
+        &self,
+        request_id: i32,
+        reviewer_id: Option<i32>,
+        status: String,
+    ) -> Result<bool>;
+    async fn join_waitlist(
+        &self,
+        add_mission_waitlist_entity: AddMissionWaitlistEntity,
+    ) -> Result<MissionWaitlistEntity>;
+    async fn leave_waitlist(&self, mission_id: i32, brawler_id: i32) -> Result<bool>;
+    /// Entries in FIFO order.
+    async fn waitlist(&self, mission_id: i32) -> Result<Vec<MissionWaitlistEntity>>;
+    /// 1-based place in the queue, or `None` when the brawler isn't waiting.
+    async fn waitlist_position(&self, mission_id: i32, brawler_id: i32) -> Result<Option<i64>>;
+    async fn waitlist_size(&self, mission_id: i32) -> Result<i64>;
+    /// Empties the mission's waitlist. Returns how many brawlers were waiting.
+    async fn clear_waitlist(&self, mission_id: i32) -> Result<usize>;
+    /// Moves waiting brawlers into every free seat, oldest first, leaving each a
+    /// notification. Only Public missions seat anyone. Returns the promoted brawler ids.
+    async fn promote_from_waitlist(&self, mission_id: i32) -> Result<Vec<i32>>;
 }
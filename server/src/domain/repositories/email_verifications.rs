@@ -0,0 +1,20 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::domain::entities::email_verifications::{
+    AddEmailVerificationEntity, EmailVerificationEntity,
+};
+
+#[async_trait]
+pub trait EmailVerificationRepository {
+    /// Stores a new token and marks any unused ones the brawler still has as used.
+    async fn create(&self, add_email_verification_entity: AddEmailVerificationEntity) -> Result<()>;
+    /// Tokens issued to the brawler since `since`, used or not.
+    async fn count_since(&self, brawler_id: i32, since: NaiveDateTime) -> Result<i64>;
+    async fn find_by_token_hash(&self, token_hash: String) -> Result<Option<EmailVerificationEntity>>;
+    /// Consumes the token and makes its address the brawler's verified email,
+    /// taking it off any account that only holds it unverified.
+    /// Returns `false` if the token had already been used.
+    async fn confirm(&self, id: i32) -> Result<bool>;
+}
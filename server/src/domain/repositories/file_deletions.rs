@@ -0,0 +1,17 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+
+use crate::domain::{entities::file_deletions::FileDeletionEntity, value_objects::file_stores::FileStores};
+
+#[async_trait]
+pub trait FileDeletionRepository {
+    /// Keys already waiting are left alone.
+    async fn enqueue(&self, store: FileStores, storage_keys: Vec<String>) -> Result<()>;
+    /// Takes up to `limit` due deletions and pushes them back by `lease`, so
+    /// another worker won't pick them up while they are being processed.
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<FileDeletionEntity>>;
+    async fn complete(&self, id: i32) -> Result<()>;
+    async fn retry_at(&self, id: i32, error: String, next_attempt_at: NaiveDateTime) -> Result<()>;
+    async fn queued_keys(&self, store: FileStores) -> Result<Vec<String>>;
+}
@@ -0,0 +1,14 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::login_attempts::{AddLoginAttemptEntity, LoginAttemptEntity},
+    value_objects::login_attempt_filter::LoginAttemptFilter,
+};
+
+#[async_trait]
+pub trait LoginAttemptRepository {
+    async fn record(&self, add_login_attempt_entity: AddLoginAttemptEntity) -> Result<()>;
+    /// Newest attempts first, capped at `filter.limit`.
+    async fn search(&self, filter: &LoginAttemptFilter) -> Result<Vec<LoginAttemptEntity>>;
+}
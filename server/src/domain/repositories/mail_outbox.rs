@@ -0,0 +1,26 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+
+use crate::domain::{
+    entities::mail_outbox::{AddMailOutboxEntity, MailOutboxEntity},
+    value_objects::email_model::DigestRecipientModel,
+};
+
+#[async_trait]
+pub trait MailOutboxRepository {
+    async fn enqueue(&self, mails: Vec<AddMailOutboxEntity>) -> Result<()>;
+    /// Leases up to `limit` due mails so that concurrent workers skip them for `lease`.
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<MailOutboxEntity>>;
+    async fn mark_sent(&self, id: i32) -> Result<()>;
+    /// Schedules another attempt at `next_attempt_at`, or gives up when it is `None`.
+    async fn mark_failed(
+        &self,
+        id: i32,
+        error: String,
+        next_attempt_at: Option<NaiveDateTime>,
+    ) -> Result<()>;
+    /// Stamps and returns the verified brawlers who opted into the digest
+    /// and have not had one since `cutoff`.
+    async fn claim_digests(&self, cutoff: NaiveDateTime) -> Result<Vec<DigestRecipientModel>>;
+}
@@ -1,11 +1,16 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::domain::entities::missions::{AddMissionEntity, EditMissionEntity};
+use chrono::NaiveDateTime;
+
+use crate::domain::entities::missions::{AddMissionEntity, EditMissionEntity, MissionEntity};
 
 #[async_trait]
 pub trait MissionManagementRepository {
     async fn add(&self, add_mission_entity: AddMissionEntity) -> Result<i32>;
     async fn edit(&self, mission_id: i32, edit_mission_entity: EditMissionEntity) -> Result<i32>;
     async fn remove(&self, mission_id: i32, chief_id: i32) -> Result<()>;
+    /// Stamps and returns the live missions whose deadline falls before `until`
+    /// and whose crew has not been reminded yet.
+    async fn claim_deadline_reminders(&self, until: NaiveDateTime) -> Result<Vec<MissionEntity>>;
 }
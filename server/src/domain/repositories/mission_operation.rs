@@ -1,9 +1,14 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
+use crate::domain::entities::mission_status_history::{
+    AddMissionStatusHistoryEntity, MissionStatusHistoryEntity,
+};
+
 #[async_trait]
 pub trait MissionOperationRepository {
-    async fn to_progress(&self, mission_id: i32, chief_id: i32) -> Result<i32>;
-    async fn to_completed(&self, mission_id: i32, chief_id: i32) -> Result<i32>;
-    async fn to_failed(&self, mission_id: i32, chief_id: i32) -> Result<i32>;
+    /// Moves the mission from `from_status` to `to_status` and records it in the
+    /// history. Returns `false` if the mission was no longer in `from_status`.
+    async fn transition(&self, change: AddMissionStatusHistoryEntity) -> Result<bool>;
+    async fn status_history(&self, mission_id: i32) -> Result<Vec<MissionStatusHistoryEntity>>;
 }
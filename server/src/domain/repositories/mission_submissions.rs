@@ -1,16 +1,34 @@
-use crate::domain::entities::mission_submissions::{MissionSubmission, NewMissionSubmission};
+use crate::domain::entities::mission_submissions::{
+    MissionSubmission, NewMissionSubmission, ReviewMissionSubmission,
+};
 use crate::domain::value_objects::mission_submission_model::MissionSubmissionModel;
 use anyhow::Result;
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait MissionSubmissionsRepository: Send + Sync {
+    /// Task submissions become the task's next version and its current one.
     async fn create(&self, new_submission: NewMissionSubmission<'_>) -> Result<MissionSubmission>;
+    /// Current work only: loose submissions and the current version of each task.
     async fn get_by_mission(&self, mission_id: i32) -> Result<Vec<MissionSubmissionModel>>;
+    /// The current version, if the task has one.
     async fn get_by_task(&self, task_id: i32) -> Result<Option<MissionSubmissionModel>>;
+    /// Every version of the task's submission, v1 first, withdrawn ones included.
+    async fn get_versions(&self, task_id: i32) -> Result<Vec<MissionSubmissionModel>>;
+    async fn get_version(&self, task_id: i32, version: i32) -> Result<Option<MissionSubmissionModel>>;
+    /// Retires the current version while keeping it in the history.
+    /// Returns `false` if it was no longer current.
+    async fn withdraw(&self, id: i32) -> Result<bool>;
     async fn get_by_id(&self, id: i32) -> Result<Option<MissionSubmissionModel>>;
     async fn delete_all_by_member(&self, mission_id: i32, brawler_id: i32) -> Result<()>;
     async fn delete_all_by_task(&self, task_id: i32) -> Result<()>;
     async fn update_description(&self, id: i32, description: String) -> Result<()>;
+    /// Deleting rows also queues their stored files for removal.
     async fn delete(&self, id: i32) -> Result<()>;
+    /// Every storage key still pointed at by a submission, used to spot orphans.
+    async fn referenced_storage_keys(&self) -> Result<Vec<String>>;
+    /// Oldest first, so reviewers work through the queue in order.
+    async fn get_pending_review(&self, mission_id: i32) -> Result<Vec<MissionSubmissionModel>>;
+    /// Records the review. Returns `false` if the submission was already reviewed.
+    async fn review(&self, id: i32, review: ReviewMissionSubmission) -> Result<bool>;
 }
@@ -3,15 +3,27 @@ use async_trait::async_trait;
 
 use crate::domain::{
     value_objects::{
-        brawler_model::BrawlerModel, mission_filter::MissionFilter, mission_model::MissionModel,
+        brawler_model::BrawlerModel,
+        mission_filter::{MissionCursor, MissionFilter},
+        mission_model::{KeyedMissionModel, MissionModel},
     },
 };
 
 #[async_trait]
 pub trait MissionViewingRepository {
     async fn view_detail(&self, mission_id: i32, user_id: Option<i32>) -> Result<MissionModel>;
-    async fn gets(&self, filter: &MissionFilter, user_id: Option<i32>) -> Result<Vec<MissionModel>>;
+    /// Up to `limit` missions matching the filter, in `filter.sort` order after `cursor`.
+    async fn gets(
+        &self,
+        filter: &MissionFilter,
+        cursor: Option<MissionCursor>,
+        limit: i64,
+        user_id: Option<i32>,
+    ) -> Result<Vec<KeyedMissionModel>>;
+    async fn count(&self, filter: &MissionFilter, user_id: Option<i32>) -> Result<i64>;
     async fn get_joined(&self, user_id: i32) -> Result<Vec<MissionModel>>;
     async fn member_counting(&self, mission_id: i32) -> Result<u32>;
     async fn get_mission_crew(&self, mission_id: i32) -> Result<Vec<BrawlerModel>>;
+    /// The stored crew role, or `None` when the brawler is not in the crew.
+    async fn member_role(&self, mission_id: i32, brawler_id: i32) -> Result<Option<String>>;
 }
@@ -1,9 +1,20 @@
+pub mod auth_sessions;
 pub mod brawlers;
+pub mod comments;
 pub mod crew_operation;
 pub mod dashboard;
+pub mod email_verifications;
+pub mod file_deletions;
+pub mod login_attempts;
+pub mod mail_outbox;
 pub mod mission_management;
 pub mod mission_operation;
 pub mod mission_viewing;
+pub mod notifications;
+pub mod password_resets;
+pub mod search;
 pub mod tasks;
+pub mod two_factor;
+pub mod webhooks;
 pub mod mission_submissions;
 // pub mod transaction_provider;
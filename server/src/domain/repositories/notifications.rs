@@ -0,0 +1,30 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::entities::notifications::{
+    AddNotificationEntity, NotificationEntity, NotificationPreferenceEntity,
+};
+
+#[async_trait]
+pub trait NotificationRepository {
+    /// Notices of a kind the recipient switched off are dropped.
+    async fn notify(&self, notices: Vec<AddNotificationEntity>) -> Result<()>;
+    async fn list(
+        &self,
+        brawler_id: i32,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<NotificationEntity>>;
+    async fn count(&self, brawler_id: i32, unread_only: bool) -> Result<i64>;
+    /// Returns `None` when the notification does not belong to `brawler_id`.
+    async fn set_read(
+        &self,
+        id: i32,
+        brawler_id: i32,
+        read: bool,
+    ) -> Result<Option<NotificationEntity>>;
+    async fn mark_all_read(&self, brawler_id: i32) -> Result<usize>;
+    async fn preferences(&self, brawler_id: i32) -> Result<Vec<NotificationPreferenceEntity>>;
+    async fn set_preferences(&self, preferences: Vec<NotificationPreferenceEntity>) -> Result<()>;
+}
@@ -0,0 +1,13 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::entities::password_resets::{AddPasswordResetEntity, PasswordResetEntity};
+
+#[async_trait]
+pub trait PasswordResetRepository {
+    /// Stores a new reset token, discarding any unused ones the brawler still has.
+    async fn create(&self, add_password_reset_entity: AddPasswordResetEntity) -> Result<()>;
+    async fn find_by_token_hash(&self, token_hash: String) -> Result<Option<PasswordResetEntity>>;
+    /// Marks the token as used. Returns `false` if it had already been consumed.
+    async fn consume(&self, id: i32) -> Result<bool>;
+}
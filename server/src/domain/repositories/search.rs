@@ -0,0 +1,18 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::value_objects::search_model::{SearchHitModel, SearchKinds};
+
+#[async_trait]
+pub trait SearchRepository {
+    /// Best matches first, limited to what `brawler_id` may see.
+    async fn search(
+        &self,
+        brawler_id: i32,
+        query: &str,
+        kinds: &[SearchKinds],
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SearchHitModel>>;
+    async fn count(&self, brawler_id: i32, query: &str, kinds: &[SearchKinds]) -> Result<i64>;
+}
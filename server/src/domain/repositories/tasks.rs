@@ -1,6 +1,9 @@
 use crate::domain::{
-    entities::tasks::{CreateTaskEntity, UpdateTaskEntity},
-    value_objects::task_model::TaskModel,
+    entities::{
+        task_dependencies::AddTaskDependencyEntity,
+        tasks::{CreateTaskEntity, UpdateTaskEntity},
+    },
+    value_objects::task_model::{TaskModel, TaskNodeModel},
 };
 use anyhow::Result;
 use async_trait::async_trait;
@@ -11,6 +14,15 @@ pub trait TaskRepository {
     async fn update(&self, task_id: i32, entity: UpdateTaskEntity) -> Result<TaskModel>;
     async fn delete(&self, task_id: i32) -> Result<()>;
     async fn get_by_id(&self, task_id: i32) -> Result<TaskModel>;
-    async fn get_by_mission_id(&self, mission_id: i32) -> Result<Vec<TaskModel>>;
+    /// Every task of the mission with its blockers and subtask progress.
+    async fn get_by_mission_id(&self, mission_id: i32) -> Result<Vec<TaskNodeModel>>;
+    /// Assigns the task only if nobody holds it yet; `None` when someone got there first.
+    async fn claim(&self, task_id: i32, member_id: i32) -> Result<Option<TaskModel>>;
     async fn get_by_assignee(&self, member_id: i32) -> Result<Vec<TaskModel>>;
+    async fn get_subtasks(&self, task_id: i32) -> Result<Vec<TaskModel>>;
+    /// Blockers of the task that are not done yet.
+    async fn get_open_blockers(&self, task_id: i32) -> Result<Vec<TaskModel>>;
+    /// Returns `false`, writing nothing, when the edge would close a cycle.
+    async fn add_dependency(&self, mission_id: i32, entity: AddTaskDependencyEntity) -> Result<bool>;
+    async fn remove_dependency(&self, task_id: i32, blocked_by_task_id: i32) -> Result<bool>;
 }
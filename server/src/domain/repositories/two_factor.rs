@@ -0,0 +1,21 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::entities::two_factor::{AddBrawlerTotpEntity, BrawlerTotpEntity};
+
+#[async_trait]
+pub trait TwoFactorRepository {
+    async fn find(&self, brawler_id: i32) -> Result<Option<BrawlerTotpEntity>>;
+    /// Replaces any pending secret and recovery codes. Stays disabled until `enable`.
+    async fn start_enrollment(
+        &self,
+        add_totp_entity: AddBrawlerTotpEntity,
+        recovery_code_hashes: Vec<String>,
+    ) -> Result<()>;
+    async fn enable(&self, brawler_id: i32, step: i64) -> Result<()>;
+    /// Remembers the accepted step. Returns `false` if it (or a later one) was already used.
+    async fn use_step(&self, brawler_id: i32, step: i64) -> Result<bool>;
+    /// Returns `false` if no unused code with that hash exists.
+    async fn consume_recovery_code(&self, brawler_id: i32, code_hash: String) -> Result<bool>;
+    async fn disable(&self, brawler_id: i32) -> Result<()>;
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+
+use crate::domain::entities::webhooks::{
+    AddMissionWebhookEntity, AddWebhookDeliveryEntity, EditMissionWebhookEntity,
+    MissionWebhookEntity, WebhookDeliveryEntity,
+};
+
+#[async_trait]
+pub trait WebhookRepository {
+    async fn create(&self, entity: AddMissionWebhookEntity) -> Result<MissionWebhookEntity>;
+    async fn get(&self, id: i32) -> Result<Option<MissionWebhookEntity>>;
+    async fn list_by_mission(&self, mission_id: i32) -> Result<Vec<MissionWebhookEntity>>;
+    async fn edit(&self, id: i32, entity: EditMissionWebhookEntity) -> Result<MissionWebhookEntity>;
+    async fn delete(&self, id: i32) -> Result<()>;
+    /// Active webhooks of the mission that asked for `event_type`.
+    async fn subscribed(&self, mission_id: i32, event_type: &str) -> Result<Vec<MissionWebhookEntity>>;
+    async fn enqueue(&self, deliveries: Vec<AddWebhookDeliveryEntity>) -> Result<Vec<WebhookDeliveryEntity>>;
+    /// Takes up to `limit` due deliveries and pushes them back by `lease`, so
+    /// another worker won't pick them up while they are being sent.
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<WebhookDeliveryEntity>>;
+    async fn mark_delivered(&self, id: i32, response_status: i32) -> Result<WebhookDeliveryEntity>;
+    /// Without `next_attempt_at` the delivery gives up and is marked Failed.
+    async fn mark_failed(
+        &self,
+        id: i32,
+        response_status: Option<i32>,
+        error: String,
+        next_attempt_at: Option<NaiveDateTime>,
+    ) -> Result<WebhookDeliveryEntity>;
+    async fn deliveries(&self, webhook_id: i32, limit: i64) -> Result<Vec<WebhookDeliveryEntity>>;
+}
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+#[derive(Debug, Clone)]
+pub struct StoredFile {
+    /// What the backend knows the file by; it may differ from the requested key.
+    pub key: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredObject {
+    pub key: String,
+    pub last_modified: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileObject {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+}
+
+/// Hands an upload over chunk by chunk so it never has to sit in memory whole.
+#[async_trait]
+pub trait ByteSource: Send {
+    /// `None` once the upload is complete.
+    async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>>;
+}
+
+/// Raised by a `ByteSource` that gave up because the upload outgrew its limit.
+#[derive(Debug)]
+pub struct UploadTooLarge {
+    pub limit_bytes: u64,
+}
+
+impl std::fmt::Display for UploadTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Upload exceeds the limit of {} bytes", self.limit_bytes)
+    }
+}
+
+impl std::error::Error for UploadTooLarge {}
+
+/// Where uploaded files live. Keys are `/` separated paths such as
+/// `missions/4/submissions/report.pdf`.
+#[async_trait]
+pub trait FileStorage {
+    /// Streams `source` into the backend; a failed upload leaves nothing behind.
+    async fn put(&self, key: &str, source: &mut dyn ByteSource, content_type: &str) -> Result<StoredFile>;
+    async fn get(&self, key: &str) -> Result<Option<FileObject>>;
+    /// Deleting a key that does not exist is not an error.
+    async fn delete(&self, key: &str) -> Result<()>;
+    /// A link that lets whoever holds it download the file until it expires.
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String>;
+    /// Every stored object whose key starts with `prefix`.
+    async fn list(&self, prefix: &str) -> Result<Vec<StoredObject>>;
+}
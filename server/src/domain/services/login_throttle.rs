@@ -0,0 +1,18 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+
+#[derive(Debug, Clone)]
+pub struct FailureRecord {
+    pub failures: u32,
+    pub last_failure_at: NaiveDateTime,
+}
+
+/// Counts consecutive failed logins per key (a username or a client IP).
+#[async_trait]
+pub trait LoginThrottle {
+    async fn get(&self, key: &str) -> Result<Option<FailureRecord>>;
+    /// Bumps the counter for `key`, starting over when the previous failure is older than `window`.
+    async fn register_failure(&self, key: &str, window: Duration) -> Result<FailureRecord>;
+    async fn reset(&self, key: &str) -> Result<()>;
+}
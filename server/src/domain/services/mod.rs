@@ -0,0 +1,4 @@
+pub mod file_storage;
+pub mod login_throttle;
+pub mod mailer;
+pub mod webhook_sender;
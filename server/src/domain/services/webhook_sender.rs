@@ -0,0 +1,28 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookResponse {
+    pub status: u16,
+}
+
+impl WebhookResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+#[async_trait]
+pub trait WebhookSender {
+    /// Errors only when no response came back at all.
+    async fn send(&self, request: WebhookRequest) -> Result<WebhookResponse>;
+    /// Fails when the URL leads somewhere deliveries must not go, such as the server's own network.
+    async fn check_target(&self, url: &str) -> Result<()>;
+}
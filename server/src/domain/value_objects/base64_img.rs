@@ -1,5 +1,8 @@
 use anyhow::Result;
 use base64::{Engine, engine::general_purpose};
+
+use crate::domain::value_objects::file_types::FileTypePolicy;
+
 #[derive(Debug, Clone)]
 pub struct Base64Img(String);
 
@@ -8,6 +11,7 @@ impl Base64Img {
         self.0
     }
 
+    /// Accepts an avatar image and turns it into a data URI.
     pub fn new(data: String) -> Result<Self> {
         if data.is_empty() {
             return Err(anyhow::anyhow!("data can not be empty !!"));
@@ -16,18 +20,11 @@ impl Base64Img {
             Ok(bs) => bs,
             Err(_) => return Err(anyhow::anyhow!("invalid img data !!")),
         };
-        let file_type = match infer::get(&bytes) {
-            Some(t) if t.mime_type() == "image/png" 
-                    || t.mime_type() == "image/jpeg" 
-                    || t.mime_type() == "application/pdf"
-                    || t.mime_type() == "application/msword"
-                    || t.mime_type() == "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
-                t.mime_type()
-            }
-            _ => return Err(anyhow::anyhow!("Unsupported file type. Please use PNG, JPEG, PDF, or DOC/DOCX.")),
-        };
+        let file_type = FileTypePolicy::avatars()
+            .check("", None, &bytes)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
 
-        let base64text = format!("data:{};base64,{}", file_type, data);
+        let base64text = format!("data:{};base64,{}", file_type.mime_type(), data);
         Ok(Self(base64text))
     }
 }
@@ -12,6 +12,7 @@ pub struct RegisterBrawlerModel {
     pub username: String,
     pub password: String,
     pub display_name: String,
+    pub email: Option<String>,
 }
 
 impl RegisterBrawlerModel {
@@ -20,6 +21,7 @@ impl RegisterBrawlerModel {
             username: self.username.clone(),
             password: self.password.clone(),
             display_name: self.display_name.clone(),
+            email: self.email.clone(),
         }
     }
 }
@@ -47,4 +49,24 @@ pub struct BrawlerModel {
 pub struct UpdateBrawlerModel {
     pub display_name: Option<String>,
     pub bio: Option<String>,
+    /// Goes through verification instead of being stored right away
+    #[diesel(skip_update)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangePasswordModel {
+    pub old_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetRequestModel {
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetConfirmModel {
+    pub token: String,
+    pub new_password: String,
 }
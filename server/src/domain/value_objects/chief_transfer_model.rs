@@ -0,0 +1,33 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::crew_roles::CrewRole;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ChiefTransferStatuses {
+    Pending,
+    Completed,
+    Declined,
+    Cancelled,
+}
+
+impl Display for ChiefTransferStatuses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChiefTransferStatuses::Pending => write!(f, "Pending"),
+            ChiefTransferStatuses::Completed => write!(f, "Completed"),
+            ChiefTransferStatuses::Declined => write!(f, "Declined"),
+            ChiefTransferStatuses::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferChiefModel {
+    pub to_brawler_id: i32,
+    // Role the current chief keeps in the crew; leave empty to step out of the mission
+    pub old_chief_role: Option<CrewRole>,
+    #[serde(default)]
+    pub require_acceptance: bool,
+}
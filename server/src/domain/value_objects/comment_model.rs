@@ -0,0 +1,140 @@
+use chrono::NaiveDateTime;
+use diesel::{prelude::*, sql_types::{Array, Int4, Nullable, Text, Timestamp}};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_COMMENT_LENGTH: usize = 5000;
+
+/// What a comment thread hangs off.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommentTarget {
+    Task(i32),
+    Submission(i32),
+}
+
+impl CommentTarget {
+    pub fn task_id(&self) -> Option<i32> {
+        match self {
+            CommentTarget::Task(task_id) => Some(*task_id),
+            CommentTarget::Submission(_) => None,
+        }
+    }
+
+    pub fn submission_id(&self) -> Option<i32> {
+        match self {
+            CommentTarget::Task(_) => None,
+            CommentTarget::Submission(submission_id) => Some(*submission_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct CommentModel {
+    #[diesel(sql_type = Int4)]
+    pub id: i32,
+    #[diesel(sql_type = Int4)]
+    pub mission_id: i32,
+    #[diesel(sql_type = Nullable<Int4>)]
+    pub task_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    pub submission_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    pub parent_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    pub author_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub author_name: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub author_avatar_url: Option<String>,
+    // Empty once the comment is deleted
+    #[diesel(sql_type = Text)]
+    pub body: String,
+    #[diesel(sql_type = Array<Int4>)]
+    pub mentions: Vec<i32>,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub edited_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// A comment with its replies nested under it, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentThreadModel {
+    #[serde(flatten)]
+    pub comment: CommentModel,
+    pub replies: Vec<CommentThreadModel>,
+}
+
+impl CommentThreadModel {
+    /// Nests a flat, oldest-first list of comments into threads.
+    pub fn build(comments: Vec<CommentModel>) -> Vec<CommentThreadModel> {
+        let mut children: std::collections::HashMap<Option<i32>, Vec<CommentModel>> =
+            std::collections::HashMap::new();
+        for comment in comments {
+            children.entry(comment.parent_id).or_default().push(comment);
+        }
+        Self::attach(None, &mut children)
+    }
+
+    fn attach(
+        parent_id: Option<i32>,
+        children: &mut std::collections::HashMap<Option<i32>, Vec<CommentModel>>,
+    ) -> Vec<CommentThreadModel> {
+        children
+            .remove(&parent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|comment| {
+                let replies = Self::attach(Some(comment.id), children);
+                CommentThreadModel { comment, replies }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCommentModel {
+    pub body: String,
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditCommentModel {
+    pub body: String,
+}
+
+/// Lowercased usernames written as `@username`, each once, in order of appearance.
+/// An `@` glued to a preceding word, as in an email address, is not a mention.
+pub fn parse_mentions(body: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let starts_mention = c == '@' && !previous.is_some_and(|p| p.is_alphanumeric() || p == '_');
+        previous = Some(c);
+        if !starts_mention {
+            continue;
+        }
+
+        let start = index + 1;
+        let mut end = start;
+        while let Some(&(next_index, next)) = chars.peek() {
+            if !(next.is_alphanumeric() || matches!(next, '_' | '.' | '-')) {
+                break;
+            }
+            end = next_index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+
+        // A sentence ending right after the name is not part of it
+        let username = body[start..end].trim_end_matches(['.', '-']).to_lowercase();
+        if !username.is_empty() && !mentions.contains(&username) {
+            mentions.push(username);
+        }
+    }
+
+    mentions
+}
@@ -0,0 +1,102 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Permission {
+    ViewWorkspace,
+    SubmitWork,
+    ManageTasks,
+    ReviewSubmissions,
+    KickMembers,
+    ManageAdmissions,
+    ManageRoles,
+    EditSettings,
+    ChangeStatus,
+    DeleteMission,
+    TransferChieftainship,
+    ManageWebhooks,
+}
+
+/// Chief is never stored in `crew_memberships`; it comes from `missions.chief_id`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum CrewRole {
+    Chief,
+    Officer,
+    Member,
+    Observer,
+}
+
+impl Display for CrewRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrewRole::Chief => write!(f, "Chief"),
+            CrewRole::Officer => write!(f, "Officer"),
+            CrewRole::Member => write!(f, "Member"),
+            CrewRole::Observer => write!(f, "Observer"),
+        }
+    }
+}
+
+impl FromStr for CrewRole {
+    type Err = anyhow::Error;
+
+    fn from_str(role: &str) -> Result<Self, Self::Err> {
+        match role.trim().to_lowercase().as_str() {
+            "chief" => Ok(Self::Chief),
+            "officer" => Ok(Self::Officer),
+            "member" => Ok(Self::Member),
+            "observer" => Ok(Self::Observer),
+            _ => Err(anyhow::anyhow!("Invalid crew role: {}", role)),
+        }
+    }
+}
+
+impl CrewRole {
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            CrewRole::Chief => &[
+                Permission::ViewWorkspace,
+                Permission::SubmitWork,
+                Permission::ManageTasks,
+                Permission::ReviewSubmissions,
+                Permission::KickMembers,
+                Permission::ManageAdmissions,
+                Permission::ManageRoles,
+                Permission::EditSettings,
+                Permission::ChangeStatus,
+                Permission::DeleteMission,
+                Permission::TransferChieftainship,
+                Permission::ManageWebhooks,
+            ],
+            CrewRole::Officer => &[
+                Permission::ViewWorkspace,
+                Permission::SubmitWork,
+                Permission::ManageTasks,
+                Permission::ReviewSubmissions,
+                Permission::KickMembers,
+                Permission::ManageAdmissions,
+            ],
+            CrewRole::Member => &[Permission::ViewWorkspace, Permission::SubmitWork],
+            CrewRole::Observer => &[Permission::ViewWorkspace],
+        }
+    }
+
+    pub fn has(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            CrewRole::Chief => 3,
+            CrewRole::Officer => 2,
+            CrewRole::Member => 1,
+            CrewRole::Observer => 0,
+        }
+    }
+
+    /// Officers can act on members and observers, but not on each other.
+    pub fn outranks(&self, other: &CrewRole) -> bool {
+        self.rank() > other.rank()
+    }
+}
@@ -0,0 +1,110 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDateTime;
+use diesel::{
+    QueryableByName,
+    sql_types::{Int4, Nullable, Timestamp, Varchar},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::{DomainError, DomainResult};
+
+// Matches the width of brawlers.email
+pub const MAX_EMAIL_LENGTH: usize = 255;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum MailOutboxStatuses {
+    Pending,
+    Sent,
+    Failed,
+}
+
+impl Display for MailOutboxStatuses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MailOutboxStatuses::Pending => write!(f, "Pending"),
+            MailOutboxStatuses::Sent => write!(f, "Sent"),
+            MailOutboxStatuses::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+impl FromStr for MailOutboxStatuses {
+    type Err = anyhow::Error;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "Pending" => Ok(Self::Pending),
+            "Sent" => Ok(Self::Sent),
+            "Failed" => Ok(Self::Failed),
+            _ => Err(anyhow::anyhow!("Invalid mail status: {}", status)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEmailModel {
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyEmailModel {
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailDigestModel {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailSettingsModel {
+    pub email: Option<String>,
+    pub verified: bool,
+    /// Daily summary of unread notifications
+    pub digest: bool,
+}
+
+/// A brawler whose daily digest is due, with the end of the previous one.
+#[derive(Debug, Clone, QueryableByName)]
+pub struct DigestRecipientModel {
+    #[diesel(sql_type = Int4)]
+    pub brawler_id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub email: String,
+    #[diesel(sql_type = Varchar)]
+    pub display_name: String,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub since: Option<NaiveDateTime>,
+}
+
+/// Trims and lowercases an address after a shape check; whether it exists
+/// is only proven by the verification mail.
+pub fn normalize_email(email: &str) -> DomainResult<String> {
+    let email = email.trim().to_lowercase();
+    let invalid = || DomainError::validation("Email address is not valid");
+
+    if email.len() > MAX_EMAIL_LENGTH || email.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(invalid());
+    }
+    let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+    if local.is_empty()
+        || domain.contains('@')
+        || !domain.contains('.')
+        || domain.starts_with('.')
+        || domain.ends_with('.')
+    {
+        return Err(invalid());
+    }
+
+    Ok(email)
+}
+
+/// Body shared by every notification mail.
+pub fn notification_mail_body(display_name: &str, message: &str) -> String {
+    format!(
+        "Hi {},\r\n\r\n{}\r\n\r\n\
+         You can choose which notifications reach your inbox in your notification preferences.",
+        display_name, message
+    )
+}
@@ -0,0 +1,33 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Which backend a queued file deletion is meant for.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum FileStores {
+    /// The configured `FILE_STORAGE` backend holding submissions.
+    Files,
+    /// Cloudinary, where avatars always live.
+    Avatars,
+}
+
+impl Display for FileStores {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileStores::Files => write!(f, "Files"),
+            FileStores::Avatars => write!(f, "Avatars"),
+        }
+    }
+}
+
+impl FromStr for FileStores {
+    type Err = anyhow::Error;
+
+    fn from_str(store: &str) -> Result<Self, Self::Err> {
+        match store {
+            "Files" => Ok(Self::Files),
+            "Avatars" => Ok(Self::Avatars),
+            _ => Err(anyhow::anyhow!("Invalid file store: {}", store)),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// The kinds of file the server knows how to recognise. Binary formats are
+/// detected from their content, text formats from being valid UTF-8.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FileType {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Pdf,
+    Doc,
+    Docx,
+    Xls,
+    Xlsx,
+    Ppt,
+    Pptx,
+    Zip,
+    Tar,
+    Gzip,
+    SevenZip,
+    Mp4,
+    Webm,
+    Mp3,
+    Text,
+    Markdown,
+    Csv,
+}
+
+impl FileType {
+    pub const ALL: [FileType; 21] = [
+        FileType::Png,
+        FileType::Jpeg,
+        FileType::Gif,
+        FileType::Webp,
+        FileType::Pdf,
+        FileType::Doc,
+        FileType::Docx,
+        FileType::Xls,
+        FileType::Xlsx,
+        FileType::Ppt,
+        FileType::Pptx,
+        FileType::Zip,
+        FileType::Tar,
+        FileType::Gzip,
+        FileType::SevenZip,
+        FileType::Mp4,
+        FileType::Webm,
+        FileType::Mp3,
+        FileType::Text,
+        FileType::Markdown,
+        FileType::Csv,
+    ];
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            FileType::Png => "image/png",
+            FileType::Jpeg => "image/jpeg",
+            FileType::Gif => "image/gif",
+            FileType::Webp => "image/webp",
+            FileType::Pdf => "application/pdf",
+            FileType::Doc => "application/msword",
+            FileType::Docx => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            FileType::Xls => "application/vnd.ms-excel",
+            FileType::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            FileType::Ppt => "application/vnd.ms-powerpoint",
+            FileType::Pptx => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            FileType::Zip => "application/zip",
+            FileType::Tar => "application/x-tar",
+            FileType::Gzip => "application/gzip",
+            FileType::SevenZip => "application/x-7z-compressed",
+            FileType::Mp4 => "video/mp4",
+            FileType::Webm => "video/webm",
+            FileType::Mp3 => "audio/mpeg",
+            FileType::Text => "text/plain",
+            FileType::Markdown => "text/markdown",
+            FileType::Csv => "text/csv",
+        }
+    }
+
+    /// Extensions a file of this type may carry. Plain text accepts any, which
+    /// is what lets source files through.
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            FileType::Png => &["png"],
+            FileType::Jpeg => &["jpg", "jpeg"],
+            FileType::Gif => &["gif"],
+            FileType::Webp => &["webp"],
+            FileType::Pdf => &["pdf"],
+            FileType::Doc => &["doc"],
+            FileType::Docx => &["docx"],
+            FileType::Xls => &["xls"],
+            FileType::Xlsx => &["xlsx"],
+            FileType::Ppt => &["ppt"],
+            FileType::Pptx => &["pptx"],
+            FileType::Zip => &["zip"],
+            FileType::Tar => &["tar"],
+            FileType::Gzip => &["gz", "tgz"],
+            FileType::SevenZip => &["7z"],
+            FileType::Mp4 => &["mp4", "m4v"],
+            FileType::Webm => &["webm"],
+            FileType::Mp3 => &["mp3"],
+            FileType::Text => &[],
+            FileType::Markdown => &["md", "markdown"],
+            FileType::Csv => &["csv"],
+        }
+    }
+
+    /// Other content types browsers commonly declare for this type.
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            FileType::Jpeg => &["image/jpg", "image/pjpeg"],
+            FileType::Zip => &["application/x-zip-compressed", "application/x-zip"],
+            FileType::Gzip => &["application/x-gzip", "application/x-compressed-tar"],
+            FileType::Mp3 => &["audio/mp3"],
+            FileType::Markdown => &["text/x-markdown", "text/plain"],
+            FileType::Csv => &["application/vnd.ms-excel", "text/plain"],
+            _ => &[],
+        }
+    }
+
+    fn is_text(&self) -> bool {
+        matches!(self, FileType::Text | FileType::Markdown | FileType::Csv)
+    }
+
+    /// Office Open XML documents are zip archives underneath.
+    fn is_zip_container(&self) -> bool {
+        matches!(self, FileType::Docx | FileType::Xlsx | FileType::Pptx)
+    }
+
+    fn accepts_declared(&self, declared: &str) -> bool {
+        declared == self.mime_type() || self.aliases().contains(&declared)
+    }
+
+    /// Works out what `head`, the first bytes of a file named `extension`, really is.
+    fn detect(head: &[u8], extension: &str) -> Result<Self, String> {
+        if let Some(kind) = infer::get(head) {
+            let detected = kind.mime_type();
+            if detected == FileType::Zip.mime_type()
+                && let Some(container) = FileType::ALL
+                    .iter()
+                    .find(|file_type| file_type.is_zip_container() && file_type.extensions().contains(&extension))
+            {
+                return Ok(*container);
+            }
+            return FileType::ALL
+                .iter()
+                .find(|file_type| file_type.mime_type() == detected)
+                .copied()
+                .ok_or_else(|| format!("Files of type {} are not accepted", detected));
+        }
+
+        if !looks_like_text(head) {
+            return Err("The file type could not be recognised".to_string());
+        }
+        Ok(FileType::ALL
+            .iter()
+            .find(|file_type| file_type.is_text() && file_type.extensions().contains(&extension))
+            .copied()
+            .unwrap_or(FileType::Text))
+    }
+}
+
+/// Valid UTF-8 without NUL bytes; a character cut off at the end of `head` is fine.
+fn looks_like_text(head: &[u8]) -> bool {
+    if head.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+impl Display for FileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FileType::Png => "Png",
+            FileType::Jpeg => "Jpeg",
+            FileType::Gif => "Gif",
+            FileType::Webp => "Webp",
+            FileType::Pdf => "Pdf",
+            FileType::Doc => "Doc",
+            FileType::Docx => "Docx",
+            FileType::Xls => "Xls",
+            FileType::Xlsx => "Xlsx",
+            FileType::Ppt => "Ppt",
+            FileType::Pptx => "Pptx",
+            FileType::Zip => "Zip",
+            FileType::Tar => "Tar",
+            FileType::Gzip => "Gzip",
+            FileType::SevenZip => "SevenZip",
+            FileType::Mp4 => "Mp4",
+            FileType::Webm => "Webm",
+            FileType::Mp3 => "Mp3",
+            FileType::Text => "Text",
+            FileType::Markdown => "Markdown",
+            FileType::Csv => "Csv",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for FileType {
+    type Err = anyhow::Error;
+
+    fn from_str(file_type: &str) -> Result<Self, Self::Err> {
+        FileType::ALL
+            .iter()
+            .find(|candidate| candidate.to_string() == file_type)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Invalid file type: {}", file_type))
+    }
+}
+
+/// Which file types an upload may be, checked against what the content really is.
+#[derive(Debug, Clone)]
+pub struct FileTypePolicy {
+    allowed: Vec<FileType>,
+}
+
+impl FileTypePolicy {
+    pub fn new(allowed: Vec<FileType>) -> Self {
+        Self { allowed }
+    }
+
+    /// Avatars are shown inline everywhere, so only images qualify.
+    pub fn avatars() -> Self {
+        Self::new(vec![FileType::Png, FileType::Jpeg, FileType::Gif, FileType::Webp])
+    }
+
+    /// What a mission accepts until its chief says otherwise.
+    pub fn default_submission_types() -> Vec<FileType> {
+        vec![
+            FileType::Png,
+            FileType::Jpeg,
+            FileType::Pdf,
+            FileType::Doc,
+            FileType::Docx,
+        ]
+    }
+
+    /// Accepts the file only if its content is an allowed type and both its
+    /// name and the client-declared content type agree with that content.
+    pub fn check(&self, file_name: &str, declared_type: Option<&str>, head: &[u8]) -> Result<FileType, String> {
+        let extension = file_name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_lowercase())
+            .unwrap_or_default();
+
+        let file_type = FileType::detect(head, &extension)?;
+        if !self.allowed.contains(&file_type) {
+            return Err(format!(
+                "{} files are not accepted, allowed types are {}",
+                file_type,
+                self.allowed
+                    .iter()
+                    .map(|allowed| allowed.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if !extension.is_empty()
+            && !file_type.extensions().is_empty()
+            && !file_type.extensions().contains(&extension.as_str())
+        {
+            return Err(format!(
+                "The file is {} but its name ends in .{}",
+                file_type, extension
+            ));
+        }
+
+        let declared = declared_type
+            .map(|declared| declared.split(';').next().unwrap_or_default().trim().to_lowercase())
+            .filter(|declared| !declared.is_empty() && declared != "application/octet-stream");
+        if let Some(declared) = declared
+            && !file_type.accepts_declared(&declared)
+        {
+            return Err(format!(
+                "The file was declared as {} but its content is {}",
+                declared,
+                file_type.mime_type()
+            ));
+        }
+
+        Ok(file_type)
+    }
+}
+
+/// A file name that is safe to store and show back: no directories, no control
+/// characters and a sane length.
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let base = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(200)
+        .collect();
+    let cleaned = cleaned.trim().trim_start_matches('.').trim();
+    if cleaned.is_empty() {
+        return "file".to_string();
+    }
+    cleaned.to_string()
+}
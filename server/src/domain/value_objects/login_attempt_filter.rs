@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct LoginAttemptFilter {
+    pub username: Option<String>,
+    pub ip_address: Option<String>,
+    pub limit: Option<i64>,
+}
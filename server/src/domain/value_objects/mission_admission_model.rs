@@ -0,0 +1,40 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum JoinRequestStatuses {
+    Pending,
+    Approved,
+    Rejected,
+    Cancelled,
+}
+
+impl Display for JoinRequestStatuses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinRequestStatuses::Pending => write!(f, "Pending"),
+            JoinRequestStatuses::Approved => write!(f, "Approved"),
+            JoinRequestStatuses::Rejected => write!(f, "Rejected"),
+            JoinRequestStatuses::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CreateInvitationModel {
+    // Restricts the code to one brawler; leave empty for a shareable link
+    pub invitee_id: Option<i32>,
+    pub max_uses: Option<i32>,
+    pub expires_in_hours: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct JoinRequestModel {
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct JoinRequestFilter {
+    pub status: Option<JoinRequestStatuses>,
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::domain::value_objects::task_model::TaskModel;
+
+/// Something that changed inside a mission workspace. Events carry just enough
+/// for a client to patch its view or know what to fetch again.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum MissionEvent {
+    TaskCreated { task: TaskModel },
+    TaskUpdated { task: TaskModel },
+    TaskDeleted { task_id: i32 },
+    SubmissionCreated { submission_id: i32, task_id: Option<i32>, brawler_id: i32 },
+    SubmissionReviewed { submission_id: i32, task_id: Option<i32>, review_status: String },
+    SubmissionDeleted { submission_id: i32, task_id: Option<i32> },
+    MemberJoined { brawler_id: i32 },
+    MemberLeft { brawler_id: i32 },
+    MemberKicked { brawler_id: i32 },
+    MissionStatusChanged { from_status: String, to_status: String },
+    MissionDeleted,
+    /// The subscriber fell behind and missed events; everything should be fetched again.
+    Resync,
+    /// Sent to a single webhook on request, to check that the endpoint is reachable.
+    Ping { webhook_id: i32 },
+}
+
+impl MissionEvent {
+    /// Kinds a webhook can subscribe to; `Resync` and `Ping` are never broadcast to webhooks.
+    pub const WEBHOOK_KINDS: [&'static str; 11] = [
+        "TaskCreated",
+        "TaskUpdated",
+        "TaskDeleted",
+        "SubmissionCreated",
+        "SubmissionReviewed",
+        "SubmissionDeleted",
+        "MemberJoined",
+        "MemberLeft",
+        "MemberKicked",
+        "MissionStatusChanged",
+        "MissionDeleted",
+    ];
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MissionEvent::TaskCreated { .. } => "TaskCreated",
+            MissionEvent::TaskUpdated { .. } => "TaskUpdated",
+            MissionEvent::TaskDeleted { .. } => "TaskDeleted",
+            MissionEvent::SubmissionCreated { .. } => "SubmissionCreated",
+            MissionEvent::SubmissionReviewed { .. } => "SubmissionReviewed",
+            MissionEvent::SubmissionDeleted { .. } => "SubmissionDeleted",
+            MissionEvent::MemberJoined { .. } => "MemberJoined",
+            MissionEvent::MemberLeft { .. } => "MemberLeft",
+            MissionEvent::MemberKicked { .. } => "MemberKicked",
+            MissionEvent::MissionStatusChanged { .. } => "MissionStatusChanged",
+            MissionEvent::MissionDeleted => "MissionDeleted",
+            MissionEvent::Resync => "Resync",
+            MissionEvent::Ping { .. } => "Ping",
+        }
+    }
+
+    /// Whether `brawler_id` loses access to the mission once this event happens.
+    pub fn ends_access_for(&self, brawler_id: i32) -> bool {
+        match self {
+            MissionEvent::MemberLeft { brawler_id: member }
+            | MissionEvent::MemberKicked { brawler_id: member } => *member == brawler_id,
+            MissionEvent::MissionDeleted => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MissionEventEnvelope {
+    pub mission_id: i32,
+    #[serde(flatten)]
+    pub event: MissionEvent,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl MissionEventEnvelope {
+    pub fn new(mission_id: i32, event: MissionEvent) -> Self {
+        Self {
+            mission_id,
+            event,
+            occurred_at: Utc::now(),
+        }
+    }
+}
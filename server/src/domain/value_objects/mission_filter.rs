@@ -1,9 +1,115 @@
+use std::{fmt::Display, str::FromStr};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Datelike, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 
 use crate::domain::value_objects::mission_statuses::MissionStatuses;
 
+pub const DEFAULT_MISSION_PAGE_SIZE: i64 = 20;
+pub const MAX_MISSION_PAGE_SIZE: i64 = 100;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct MissionFilter {
     pub name: Option<String>,
     pub status: Option<MissionStatuses>,
+    pub chief_id: Option<i32>,
+    pub min_member_count: Option<i64>,
+    pub max_member_count: Option<i64>,
+    /// `true` keeps missions that can still take members, `false` only full ones
+    pub has_free_slots: Option<bool>,
+    pub created_after: Option<NaiveDateTime>,
+    pub created_before: Option<NaiveDateTime>,
+    pub ends_after: Option<NaiveDateTime>,
+    pub ends_before: Option<NaiveDateTime>,
+    /// Comma separated, a mission has to carry every one of them
+    pub tags: Option<String>,
+    #[serde(default)]
+    pub sort: MissionSort,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl MissionFilter {
+    pub fn tag_list(&self) -> Vec<String> {
+        self.tags
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|tag| tag.trim().to_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum MissionSort {
+    #[default]
+    Newest,
+    MostMembers,
+    FreeSlots,
+    EndingSoon,
+}
+
+impl Display for MissionSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissionSort::Newest => write!(f, "Newest"),
+            MissionSort::MostMembers => write!(f, "MostMembers"),
+            MissionSort::FreeSlots => write!(f, "FreeSlots"),
+            MissionSort::EndingSoon => write!(f, "EndingSoon"),
+        }
+    }
+}
+
+impl FromStr for MissionSort {
+    type Err = anyhow::Error;
+
+    fn from_str(sort: &str) -> Result<Self, Self::Err> {
+        match sort {
+            "Newest" => Ok(Self::Newest),
+            "MostMembers" => Ok(Self::MostMembers),
+            "FreeSlots" => Ok(Self::FreeSlots),
+            "EndingSoon" => Ok(Self::EndingSoon),
+            _ => Err(anyhow::anyhow!("Invalid mission sort: {}", sort)),
+        }
+    }
+}
+
+/// Position after the last mission of a page: its sort key with the id as tie breaker.
+/// Handed to clients as an opaque string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MissionCursor {
+    pub sort: MissionSort,
+    pub sort_key: i64,
+    pub id: i32,
+}
+
+impl MissionCursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}:{}:{}", self.sort, self.sort_key, self.id))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        let raw = String::from_utf8(bytes).ok()?;
+        let mut parts = raw.splitn(3, ':');
+        let cursor = Self {
+            sort: parts.next()?.parse().ok()?,
+            sort_key: parts.next()?.parse().ok()?,
+            id: parts.next()?.parse().ok()?,
+        };
+        cursor.sort_key_in_range().then_some(cursor)
+    }
+
+    /// Newest keys are microseconds since the epoch and get turned back into a
+    /// timestamp by the database, so they have to stay within years 1 to 9999.
+    fn sort_key_in_range(&self) -> bool {
+        match self.sort {
+            MissionSort::Newest => DateTime::from_timestamp_micros(self.sort_key)
+                .is_some_and(|created_at| (1..=9999).contains(&created_at.year())),
+            MissionSort::MostMembers | MissionSort::FreeSlots | MissionSort::EndingSoon => true,
+        }
+    }
 }
@@ -1,15 +1,24 @@
 use chrono::NaiveDateTime;
 use diesel::{
     QueryableByName,
-    sql_types::{BigInt, Int4, Nullable, Text, Timestamp, Varchar},
+    sql_types::{Array, BigInt, Int4, Nullable, Text, Timestamp, Varchar},
 };
 use serde::{Deserialize, Serialize};
 
 use crate::domain::{
     entities::missions::{AddMissionEntity, EditMissionEntity},
-    value_objects::mission_statuses::MissionStatuses,
+    value_objects::{
+        file_types::{FileType, FileTypePolicy},
+        mission_statuses::MissionStatuses,
+        mission_visibility::MissionVisibility,
+    },
 };
 
+// Matches the column default on missions.max_upload_mb
+pub const DEFAULT_MAX_UPLOAD_MB: i32 = 25;
+pub const MAX_MISSION_TAGS: usize = 10;
+pub const MAX_TAG_LENGTH: usize = 30;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, QueryableByName)]
 pub struct MissionModel {
     #[diesel(sql_type = Int4)]
@@ -28,6 +37,16 @@ pub struct MissionModel {
     pub member_count: i64,
     #[diesel(sql_type = Int4)]
     pub max_members: i32,
+    #[diesel(sql_type = Varchar)]
+    pub visibility: String,
+    #[diesel(sql_type = Int4)]
+    pub max_upload_mb: i32,
+    #[diesel(sql_type = Array<Text>)]
+    pub allowed_file_types: Vec<String>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub end_date: Option<NaiveDateTime>,
+    #[diesel(sql_type = Array<Text>)]
+    pub tags: Vec<String>,
     #[diesel(sql_type = Timestamp)]
     pub created_at: NaiveDateTime,
     #[diesel(sql_type = Timestamp)]
@@ -36,12 +55,35 @@ pub struct MissionModel {
     pub is_joined: bool,
 }
 
+/// A listed mission together with the value it was sorted by, so the page can hand out a cursor.
+#[derive(Debug, Clone, QueryableByName)]
+pub struct KeyedMissionModel {
+    #[diesel(embed)]
+    pub mission: MissionModel,
+    #[diesel(sql_type = BigInt)]
+    pub sort_key: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MissionPageModel {
+    pub items: Vec<MissionModel>,
+    /// Absent on the last page
+    pub next_cursor: Option<String>,
+    pub limit: i64,
+    /// Missions matching the filter across all pages
+    pub total: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AddMissionModel {
     pub name: String,
     pub description: Option<String>,
     pub max_members: Option<i32>,
-    pub status: Option<String>,
+    pub visibility: Option<MissionVisibility>,
+    pub max_upload_mb: Option<i32>,
+    pub allowed_file_types: Option<Vec<FileType>>,
+    pub end_date: Option<NaiveDateTime>,
+    pub tags: Option<Vec<String>>,
 }
 
 impl AddMissionModel {
@@ -49,9 +91,19 @@ impl AddMissionModel {
         AddMissionEntity {
             name: self.name.clone(),
             description: self.description.clone(),
-            status: self.status.clone().unwrap_or(MissionStatuses::Open.to_string()),
+            // New missions always start Open; later moves go through the state machine
+            status: MissionStatuses::Open.to_string(),
             chief_id,
             max_members: self.max_members.unwrap_or(10),
+            visibility: self.visibility.unwrap_or_default().to_string(),
+            max_upload_mb: self.max_upload_mb.unwrap_or(DEFAULT_MAX_UPLOAD_MB),
+            allowed_file_types: file_type_names(
+                self.allowed_file_types
+                    .clone()
+                    .unwrap_or_else(FileTypePolicy::default_submission_types),
+            ),
+            end_date: self.end_date,
+            tags: normalize_tags(self.tags.clone().unwrap_or_default()),
         }
     }
 }
@@ -61,17 +113,70 @@ pub struct EditMissionModel {
     pub name: Option<String>,
     pub description: Option<String>,
     pub max_members: Option<i32>,
-    pub status: Option<String>,
+    pub visibility: Option<MissionVisibility>,
+    pub max_upload_mb: Option<i32>,
+    pub allowed_file_types: Option<Vec<FileType>>,
+    pub end_date: Option<NaiveDateTime>,
+    pub tags: Option<Vec<String>>,
 }
 
 impl EditMissionModel {
-    pub fn to_entity(&self, chief_id: i32) -> EditMissionEntity {
+    pub fn to_entity(&self) -> EditMissionEntity {
         EditMissionEntity {
             name: self.name.clone(),
             description: self.description.clone(),
             max_members: self.max_members,
-            chief_id,
-            status: self.status.clone(),
+            visibility: self.visibility.map(|visibility| visibility.to_string()),
+            max_upload_mb: self.max_upload_mb,
+            allowed_file_types: self.allowed_file_types.clone().map(file_type_names),
+            end_date: self.end_date,
+            // Moving the deadline re-arms its reminder
+            deadline_reminded_at: self.end_date.map(|_| None),
+            tags: self.tags.clone().map(normalize_tags),
+            updated_at: chrono::Utc::now().naive_utc(),
         }
     }
 }
+
+fn file_type_names(file_types: Vec<FileType>) -> Vec<String> {
+    let mut names: Vec<String> = file_types.iter().map(|file_type| file_type.to_string()).collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Tags are matched exactly, so they are stored trimmed, lowercased and without repeats.
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+impl MissionModel {
+    /// The submission policy the chief configured; names that no longer parse are skipped.
+    pub fn submission_policy(&self) -> FileTypePolicy {
+        FileTypePolicy::new(
+            self.allowed_file_types
+                .iter()
+                .filter_map(|name| name.parse().ok())
+                .collect(),
+        )
+    }
+
+    /// Invite-only missions don't exist for outsiders, only for their chief and crew.
+    pub fn is_visible_to(&self, user_id: Option<i32>) -> bool {
+        let is_insider = self.is_joined || user_id == Some(self.chief_id);
+        self.visibility != MissionVisibility::InviteOnly.to_string() || is_insider
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MissionTransitionModel {
+    pub status: MissionStatuses,
+    pub reason: Option<String>,
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +9,7 @@ pub enum MissionStatuses {
     InProgress,
     Completed,
     Failed,
+    Cancelled,
 }
 
 impl Display for MissionStatuses {
@@ -18,6 +19,57 @@ impl Display for MissionStatuses {
             MissionStatuses::InProgress => write!(f, "InProgress"),
             MissionStatuses::Completed => write!(f, "Completed"),
             MissionStatuses::Failed => write!(f, "Failed"),
+            MissionStatuses::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
+
+impl FromStr for MissionStatuses {
+    type Err = anyhow::Error;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "Open" => Ok(Self::Open),
+            "InProgress" => Ok(Self::InProgress),
+            "Completed" => Ok(Self::Completed),
+            "Failed" => Ok(Self::Failed),
+            "Cancelled" => Ok(Self::Cancelled),
+            _ => Err(anyhow::anyhow!("Invalid mission status: {}", status)),
+        }
+    }
+}
+
+impl MissionStatuses {
+    /// The only moves a mission may make. Completed and Cancelled are final.
+    pub fn allowed_transitions(&self) -> &'static [MissionStatuses] {
+        match self {
+            MissionStatuses::Open => &[MissionStatuses::InProgress, MissionStatuses::Cancelled],
+            MissionStatuses::InProgress => &[
+                MissionStatuses::Completed,
+                MissionStatuses::Failed,
+                MissionStatuses::Cancelled,
+            ],
+            MissionStatuses::Failed => &[
+                MissionStatuses::Open,
+                MissionStatuses::InProgress,
+                MissionStatuses::Cancelled,
+            ],
+            MissionStatuses::Completed | MissionStatuses::Cancelled => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: &MissionStatuses) -> bool {
+        self.allowed_transitions().contains(next)
+    }
+
+    pub fn is_joinable(&self) -> bool {
+        matches!(self, MissionStatuses::Open | MissionStatuses::Failed)
+    }
+
+    pub fn is_leavable(&self) -> bool {
+        matches!(
+            self,
+            MissionStatuses::Open | MissionStatuses::InProgress | MissionStatuses::Failed
+        )
+    }
+}
@@ -27,4 +27,72 @@ pub struct MissionSubmissionModel {
     pub brawler_avatar_url: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub description: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub review_status: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Int4>)]
+    pub reviewed_by: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub review_feedback: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>)]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    #[diesel(sql_type = diesel::sql_types::Int4)]
+    pub version: i32,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub is_current: bool,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>)]
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    #[serde(skip_serializing)]
+    pub storage_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionFieldChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionVersionDiffModel {
+    pub task_id: i32,
+    pub from_version: i32,
+    pub to_version: i32,
+    pub changes: Vec<SubmissionFieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionDiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+impl MissionSubmissionModel {
+    /// Metadata fields that differ between two versions; file contents are not compared.
+    pub fn diff(&self, newer: &MissionSubmissionModel) -> Vec<SubmissionFieldChange> {
+        let fields = [
+            ("file_name", Some(self.file_name.clone()), Some(newer.file_name.clone())),
+            ("file_type", Some(self.file_type.clone()), Some(newer.file_type.clone())),
+            ("file_url", Some(self.file_url.clone()), Some(newer.file_url.clone())),
+            ("description", self.description.clone(), newer.description.clone()),
+            ("brawler_name", Some(self.brawler_name.clone()), Some(newer.brawler_name.clone())),
+            (
+                "submitted_at",
+                Some(self.submitted_at.to_rfc3339()),
+                Some(newer.submitted_at.to_rfc3339()),
+            ),
+            ("review_status", Some(self.review_status.clone()), Some(newer.review_status.clone())),
+            ("review_feedback", self.review_feedback.clone(), newer.review_feedback.clone()),
+        ];
+
+        fields
+            .into_iter()
+            .filter(|(_, from, to)| from != to)
+            .map(|(field, from, to)| SubmissionFieldChange {
+                field: field.to_string(),
+                from,
+                to,
+            })
+            .collect()
+    }
 }
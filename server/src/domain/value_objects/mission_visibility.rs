@@ -0,0 +1,34 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum MissionVisibility {
+    #[default]
+    Public,
+    RequestToJoin,
+    InviteOnly,
+}
+
+impl Display for MissionVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissionVisibility::Public => write!(f, "Public"),
+            MissionVisibility::RequestToJoin => write!(f, "RequestToJoin"),
+            MissionVisibility::InviteOnly => write!(f, "InviteOnly"),
+        }
+    }
+}
+
+impl FromStr for MissionVisibility {
+    type Err = anyhow::Error;
+
+    fn from_str(visibility: &str) -> Result<Self, Self::Err> {
+        match visibility {
+            "Public" => Ok(Self::Public),
+            "RequestToJoin" => Ok(Self::RequestToJoin),
+            "InviteOnly" => Ok(Self::InviteOnly),
+            _ => Err(anyhow::anyhow!("Invalid mission visibility: {}", visibility)),
+        }
+    }
+}
@@ -1,9 +1,27 @@
 pub mod base64_img;
 pub mod brawler_model;
+pub mod chief_transfer_model;
+pub mod comment_model;
+pub mod crew_roles;
+pub mod email_model;
+pub mod file_stores;
+pub mod file_types;
+pub mod mission_admission_model;
+pub mod mission_events;
 pub mod mission_filter;
 pub mod mission_model;
 pub mod mission_statuses;
+pub mod mission_visibility;
+pub mod notification_kinds;
+pub mod notification_model;
+pub mod password;
+pub mod search_model;
 pub mod uploaded_img;
 pub mod task_model;
+pub mod task_statuses;
 pub mod dashboard_model;
 pub mod mission_submission_model;
+pub mod submission_review_model;
+pub mod login_attempt_filter;
+pub mod waitlist_model;
+pub mod webhook_model;
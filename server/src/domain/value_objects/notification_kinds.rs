@@ -0,0 +1,94 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum NotificationKinds {
+    WaitlistPromoted,
+    Mentioned,
+    TaskAssigned,
+    RoleChanged,
+    RemovedFromMission,
+    MissionCompleted,
+    SubmissionReviewed,
+    DeadlineApproaching,
+}
+
+impl NotificationKinds {
+    pub const ALL: [NotificationKinds; 8] = [
+        NotificationKinds::WaitlistPromoted,
+        NotificationKinds::Mentioned,
+        NotificationKinds::TaskAssigned,
+        NotificationKinds::RoleChanged,
+        NotificationKinds::RemovedFromMission,
+        NotificationKinds::MissionCompleted,
+        NotificationKinds::SubmissionReviewed,
+        NotificationKinds::DeadlineApproaching,
+    ];
+
+    /// Kinds that also go out by mail until the brawler says otherwise.
+    pub fn emails_by_default(&self) -> bool {
+        matches!(
+            self,
+            NotificationKinds::TaskAssigned
+                | NotificationKinds::SubmissionReviewed
+                | NotificationKinds::DeadlineApproaching
+        )
+    }
+
+    /// Whether a notice of this kind is mailed given the brawler's stored
+    /// preference. Turning a kind off mutes the mail too, unless mail was
+    /// explicitly switched on for it.
+    pub fn emails(&self, enabled: bool, email_enabled: Option<bool>) -> bool {
+        match email_enabled {
+            Some(email) => email,
+            None => enabled && self.emails_by_default(),
+        }
+    }
+
+    pub fn email_subject(&self) -> &'static str {
+        match self {
+            NotificationKinds::WaitlistPromoted => "You got a seat from the waitlist",
+            NotificationKinds::Mentioned => "You were mentioned",
+            NotificationKinds::TaskAssigned => "A task was assigned to you",
+            NotificationKinds::RoleChanged => "Your crew role changed",
+            NotificationKinds::RemovedFromMission => "You were removed from a mission",
+            NotificationKinds::MissionCompleted => "A mission was completed",
+            NotificationKinds::SubmissionReviewed => "Your submission was reviewed",
+            NotificationKinds::DeadlineApproaching => "A mission deadline is approaching",
+        }
+    }
+}
+
+impl Display for NotificationKinds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationKinds::WaitlistPromoted => write!(f, "WaitlistPromoted"),
+            NotificationKinds::Mentioned => write!(f, "Mentioned"),
+            NotificationKinds::TaskAssigned => write!(f, "TaskAssigned"),
+            NotificationKinds::RoleChanged => write!(f, "RoleChanged"),
+            NotificationKinds::RemovedFromMission => write!(f, "RemovedFromMission"),
+            NotificationKinds::MissionCompleted => write!(f, "MissionCompleted"),
+            NotificationKinds::SubmissionReviewed => write!(f, "SubmissionReviewed"),
+            NotificationKinds::DeadlineApproaching => write!(f, "DeadlineApproaching"),
+        }
+    }
+}
+
+impl FromStr for NotificationKinds {
+    type Err = anyhow::Error;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind {
+            "WaitlistPromoted" => Ok(Self::WaitlistPromoted),
+            "Mentioned" => Ok(Self::Mentioned),
+            "TaskAssigned" => Ok(Self::TaskAssigned),
+            "RoleChanged" => Ok(Self::RoleChanged),
+            "RemovedFromMission" => Ok(Self::RemovedFromMission),
+            "MissionCompleted" => Ok(Self::MissionCompleted),
+            "SubmissionReviewed" => Ok(Self::SubmissionReviewed),
+            "DeadlineApproaching" => Ok(Self::DeadlineApproaching),
+            _ => Err(anyhow::anyhow!("Invalid notification kind: {}", kind)),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    entities::notifications::NotificationEntity,
+    value_objects::notification_kinds::NotificationKinds,
+};
+
+pub const DEFAULT_NOTIFICATION_PAGE_SIZE: i64 = 20;
+pub const MAX_NOTIFICATION_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct NotificationFilter {
+    /// 1-based
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationPageModel {
+    pub items: Vec<NotificationEntity>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnreadCountModel {
+    pub unread: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPreferenceModel {
+    pub kind: NotificationKinds,
+    /// Shown in the in-app inbox
+    pub enabled: bool,
+    /// Also sent by mail once the brawler has a verified address
+    pub email: bool,
+}
+
+/// Channels left out keep their current setting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateNotificationPreferenceModel {
+    pub kind: NotificationKinds,
+    pub enabled: Option<bool>,
+    pub email: Option<bool>,
+}
@@ -0,0 +1,31 @@
+use crate::{
+    config::config_model::PasswordEnv,
+    domain::errors::{DomainError, DomainResult},
+};
+
+#[derive(Debug, Clone)]
+pub struct Password(String);
+
+impl Password {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    pub fn new(data: String, policy: &PasswordEnv) -> DomainResult<Self> {
+        if data.chars().count() < policy.min_length {
+            return Err(DomainError::validation(format!(
+                "Password must be at least {} characters long",
+                policy.min_length
+            )));
+        }
+
+        let lowered = data.to_lowercase();
+        if policy.banned_passwords.contains(&lowered) {
+            return Err(DomainError::validation(
+                "Password is too common, please choose another one",
+            ));
+        }
+
+        Ok(Self(data))
+    }
+}
@@ -0,0 +1,108 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDateTime;
+use diesel::{
+    QueryableByName,
+    sql_types::{Float4, Int4, Text, Timestamp, Varchar},
+};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SEARCH_PAGE_SIZE: i64 = 20;
+pub const MAX_SEARCH_PAGE_SIZE: i64 = 50;
+pub const MAX_SEARCH_QUERY_LENGTH: usize = 200;
+// Must match the StartSel / StopSel given to ts_headline
+pub const HIGHLIGHT_START: &str = "<mark>";
+pub const HIGHLIGHT_STOP: &str = "</mark>";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SearchKinds {
+    Mission,
+    Task,
+    Submission,
+}
+
+impl Display for SearchKinds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchKinds::Mission => write!(f, "Mission"),
+            SearchKinds::Task => write!(f, "Task"),
+            SearchKinds::Submission => write!(f, "Submission"),
+        }
+    }
+}
+
+impl FromStr for SearchKinds {
+    type Err = anyhow::Error;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind {
+            "Mission" => Ok(Self::Mission),
+            "Task" => Ok(Self::Task),
+            "Submission" => Ok(Self::Submission),
+            _ => Err(anyhow::anyhow!("Invalid search kind: {}", kind)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SearchQuery {
+    /// Web search syntax: quoted phrases, `or` and `-excluded` words
+    pub q: String,
+    /// Comma separated `SearchKinds`, everything when absent
+    pub kinds: Option<String>,
+    /// 1-based
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct SearchHitModel {
+    #[diesel(sql_type = Varchar)]
+    pub kind: String,
+    #[diesel(sql_type = Int4)]
+    pub id: i32,
+    #[diesel(sql_type = Int4)]
+    pub mission_id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub mission_name: String,
+    #[diesel(sql_type = Text)]
+    pub title: String,
+    /// HTML-escaped text with matches wrapped in `<mark>`
+    #[diesel(sql_type = Text)]
+    pub snippet: String,
+    #[diesel(sql_type = Float4)]
+    pub rank: f32,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResultsModel {
+    pub items: Vec<SearchHitModel>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+}
+
+/// Escapes a ts_headline fragment for HTML while keeping its highlight markers,
+/// since the surrounding text is user content.
+pub fn escape_snippet(snippet: &str) -> String {
+    snippet
+        .split(HIGHLIGHT_START)
+        .map(|part| {
+            part.split(HIGHLIGHT_STOP)
+                .map(escape_html)
+                .collect::<Vec<_>>()
+                .join(HIGHLIGHT_STOP)
+        })
+        .collect::<Vec<_>>()
+        .join(HIGHLIGHT_START)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
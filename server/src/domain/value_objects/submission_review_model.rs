@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::task_statuses::TaskStatuses;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SubmissionReviewStatuses {
+    Pending,
+    Approved,
+    Rejected,
+    ChangesRequested,
+}
+
+impl Display for SubmissionReviewStatuses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionReviewStatuses::Pending => write!(f, "Pending"),
+            SubmissionReviewStatuses::Approved => write!(f, "Approved"),
+            SubmissionReviewStatuses::Rejected => write!(f, "Rejected"),
+            SubmissionReviewStatuses::ChangesRequested => write!(f, "ChangesRequested"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SubmissionReviewDecisions {
+    Approve,
+    Reject,
+    RequestChanges,
+}
+
+impl SubmissionReviewDecisions {
+    pub fn outcome(&self) -> SubmissionReviewStatuses {
+        match self {
+            SubmissionReviewDecisions::Approve => SubmissionReviewStatuses::Approved,
+            SubmissionReviewDecisions::Reject => SubmissionReviewStatuses::Rejected,
+            SubmissionReviewDecisions::RequestChanges => SubmissionReviewStatuses::ChangesRequested,
+        }
+    }
+
+    /// Where the linked task goes once the review is in.
+    pub fn task_status(&self) -> TaskStatuses {
+        match self {
+            SubmissionReviewDecisions::Approve => TaskStatuses::Done,
+            SubmissionReviewDecisions::Reject => TaskStatuses::Rejected,
+            SubmissionReviewDecisions::RequestChanges => TaskStatuses::InProgress,
+        }
+    }
+
+    pub fn needs_feedback(&self) -> bool {
+        !matches!(self, SubmissionReviewDecisions::Approve)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewSubmissionModel {
+    pub decision: SubmissionReviewDecisions,
+    pub feedback: Option<String>,
+}
@@ -2,6 +2,8 @@ use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::value_objects::task_statuses::TaskStatuses;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable)]
 #[diesel(table_name = crate::infrastructure::database::schema::tasks)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -19,6 +21,17 @@ pub struct TaskModel {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub has_submission: bool,
+    pub parent_task_id: Option<i32>,
+}
+
+/// A task as the workspace board draws it: the row plus its edges in the task graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskNodeModel {
+    #[serde(flatten)]
+    pub task: TaskModel,
+    pub blocked_by: Vec<i32>,
+    pub subtask_count: usize,
+    pub subtasks_done: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +42,7 @@ pub struct CreateTaskModel {
     pub priority: Option<String>,
     pub start_date: Option<NaiveDateTime>,
     pub end_date: Option<NaiveDateTime>,
+    pub parent_task_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,3 +53,13 @@ pub struct UpdateTaskModel {
     pub status: Option<String>,
     pub priority: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddTaskDependencyModel {
+    pub blocked_by_task_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStatusModel {
+    pub status: TaskStatuses,
+}
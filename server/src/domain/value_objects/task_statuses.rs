@@ -0,0 +1,82 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TaskStatuses {
+    Pending,
+    #[serde(rename = "In Progress")]
+    InProgress,
+    Review,
+    Done,
+    Rejected,
+}
+
+impl Display for TaskStatuses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskStatuses::Pending => write!(f, "Pending"),
+            TaskStatuses::InProgress => write!(f, "In Progress"),
+            TaskStatuses::Review => write!(f, "Review"),
+            TaskStatuses::Done => write!(f, "Done"),
+            TaskStatuses::Rejected => write!(f, "Rejected"),
+        }
+    }
+}
+
+impl FromStr for TaskStatuses {
+    type Err = anyhow::Error;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "Pending" => Ok(Self::Pending),
+            "In Progress" => Ok(Self::InProgress),
+            "Review" => Ok(Self::Review),
+            "Done" => Ok(Self::Done),
+            "Rejected" => Ok(Self::Rejected),
+            _ => Err(anyhow::anyhow!("Invalid task status: {}", status)),
+        }
+    }
+}
+
+impl TaskStatuses {
+    pub fn allowed_transitions(&self) -> &'static [TaskStatuses] {
+        match self {
+            TaskStatuses::Pending => &[TaskStatuses::InProgress],
+            TaskStatuses::InProgress => &[TaskStatuses::Review, TaskStatuses::Pending],
+            TaskStatuses::Review => &[
+                TaskStatuses::Done,
+                TaskStatuses::Rejected,
+                TaskStatuses::InProgress,
+            ],
+            TaskStatuses::Done => &[TaskStatuses::InProgress],
+            TaskStatuses::Rejected => &[TaskStatuses::InProgress],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: TaskStatuses) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    /// Moves that judge the work rather than do it; only reviewers make them.
+    pub fn is_review_decision(&self, next: TaskStatuses) -> bool {
+        matches!(
+            (self, next),
+            (TaskStatuses::Review, TaskStatuses::Done)
+                | (TaskStatuses::Review, TaskStatuses::Rejected)
+                | (TaskStatuses::Done, TaskStatuses::InProgress)
+        )
+    }
+
+    /// Work has started, so every blocker must be done first.
+    pub fn needs_blockers_done(&self) -> bool {
+        matches!(
+            self,
+            TaskStatuses::InProgress | TaskStatuses::Review | TaskStatuses::Done
+        )
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self, TaskStatuses::Done)
+    }
+}
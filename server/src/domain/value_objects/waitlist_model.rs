@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitlistStatusModel {
+    // 1-based place in the queue; None when the brawler isn't waiting
+    pub position: Option<i64>,
+    pub waiting: i64,
+}
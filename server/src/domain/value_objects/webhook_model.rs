@@ -0,0 +1,85 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::webhooks::MissionWebhookEntity;
+
+pub const MIN_WEBHOOK_SECRET_LENGTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum WebhookDeliveryStatuses {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl Display for WebhookDeliveryStatuses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebhookDeliveryStatuses::Pending => write!(f, "Pending"),
+            WebhookDeliveryStatuses::Delivered => write!(f, "Delivered"),
+            WebhookDeliveryStatuses::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+impl FromStr for WebhookDeliveryStatuses {
+    type Err = anyhow::Error;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "Pending" => Ok(Self::Pending),
+            "Delivered" => Ok(Self::Delivered),
+            "Failed" => Ok(Self::Failed),
+            _ => Err(anyhow::anyhow!("Invalid webhook delivery status: {}", status)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookModel {
+    pub url: String,
+    /// Generated when left out
+    pub secret: Option<String>,
+    pub event_types: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditWebhookModel {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+/// A webhook as the chief sees it. The secret is only ever shown right after it was set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookModel {
+    pub id: i32,
+    pub mission_id: i32,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+impl WebhookModel {
+    pub fn from_entity(entity: MissionWebhookEntity, reveal_secret: bool) -> Self {
+        Self {
+            id: entity.id,
+            mission_id: entity.mission_id,
+            url: entity.url,
+            event_types: entity.event_types,
+            is_active: entity.is_active,
+            created_by: entity.created_by,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            secret: reveal_secret.then_some(entity.secret),
+        }
+    }
+}
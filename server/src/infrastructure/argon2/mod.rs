@@ -1,3 +1,5 @@
+use std::sync::OnceLock;
+
 use anyhow::{Ok, Result};
 use argon2::{
     Argon2, PasswordHash, PasswordVerifier,
@@ -26,3 +28,13 @@ pub fn verify(password: String, hashed_password: String) -> Result<bool> {
         .is_ok();
     Ok(value)
 }
+
+/// Verifies against a throwaway hash so a login for an unknown account
+/// takes as long as one with a wrong password.
+pub fn verify_dummy(password: String) {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+
+    let hashed_password =
+        DUMMY_HASH.get_or_init(|| hash("dummy-password".to_string()).unwrap_or_default());
+    let _ = verify(password, hashed_password.clone());
+}
@@ -12,9 +12,11 @@ pub struct UploadImageOptions {
     pub folder: Option<String>,
     pub public_id: Option<String>,
     pub transformation: Option<String>,
+    /// Cloudinary's `type`; `None` keeps the public `upload` type
+    pub delivery_type: Option<String>,
 }
 
-fn form_builder(option: UploadImageOptions, cloud_env: &CloudinaryEnv, resource_type: &str) -> Result<Form> {
+pub(crate) fn form_builder(option: UploadImageOptions, cloud_env: &CloudinaryEnv, resource_type: &str) -> Result<Form> {
     let mut form = Form::new();
     let timestamp = Utc::now().timestamp_millis().to_string();
     let mut hasher = Sha1::new();
@@ -31,6 +33,9 @@ fn form_builder(option: UploadImageOptions, cloud_env: &CloudinaryEnv, resource_
     if let Some(transformation) = option.transformation {
         params_to_sign.insert("transformation".to_string(), transformation);
     }
+    if let Some(delivery_type) = option.delivery_type {
+        params_to_sign.insert("type".to_string(), delivery_type);
+    }
 
     let mut sorted_keys: Vec<_> = params_to_sign.keys().collect();
     sorted_keys.sort();
@@ -83,29 +88,3 @@ pub async fn upload(base64_image: Base64Img, option: UploadImageOptions) -> Resu
         serde_json::from_str(&text).context(format!("failed to parse:\n\n {}", text))?;
     Ok(json)
 }
-
-pub async fn upload_auto(base64_data: String, option: UploadImageOptions) -> Result<UploadedImg> {
-    let cloud_env = get_cloudinary_env()?;
-
-    let file = Part::text(base64_data);
-    let form = form_builder(option, &cloud_env, "auto")?;
-    let multipart = form.part("file", file);
-    let client = reqwest::Client::new();
-    let url = format!(
-        "https://api.cloudinary.com/v1_1/{}/auto/upload",
-        cloud_env.cloud_name
-    );
-
-    let response = client
-        .post(&url)
-        .multipart(multipart)
-        .send()
-        .await
-        .context(format!("upload to {}", url))?;
-
-    let text = response.text().await?;
-    println!("Cloudinary response: {}", text);
-    let json: UploadedImg =
-        serde_json::from_str(&text).context(format!("failed to parse:\n\n {}", text))?;
-    Ok(json)
-}
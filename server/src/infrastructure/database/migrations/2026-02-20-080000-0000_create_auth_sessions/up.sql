@@ -0,0 +1,19 @@
+CREATE TABLE auth_sessions (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    expires_at TIMESTAMP NOT NULL,
+    revoked_at TIMESTAMP
+);
+
+-- Every rotation leaves the used token behind so a replay can be detected
+CREATE TABLE refresh_tokens (
+    id SERIAL PRIMARY KEY,
+    session_id INTEGER NOT NULL REFERENCES auth_sessions(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    used_at TIMESTAMP
+);
+
+CREATE INDEX idx_auth_sessions_brawler_id ON auth_sessions(brawler_id);
+CREATE INDEX idx_refresh_tokens_session_id ON refresh_tokens(session_id);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS password_reset_tokens;
+ALTER TABLE brawlers DROP CONSTRAINT IF EXISTS unique_email;
+ALTER TABLE brawlers DROP COLUMN email;
@@ -0,0 +1,13 @@
+ALTER TABLE brawlers ADD COLUMN email VARCHAR(255);
+ALTER TABLE brawlers ADD CONSTRAINT unique_email UNIQUE (email);
+
+CREATE TABLE password_reset_tokens (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP
+);
+
+CREATE INDEX idx_password_reset_tokens_brawler_id ON password_reset_tokens(brawler_id);
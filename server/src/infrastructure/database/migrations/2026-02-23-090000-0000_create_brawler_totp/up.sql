@@ -0,0 +1,16 @@
+CREATE TABLE brawler_totp (
+    brawler_id INTEGER PRIMARY KEY REFERENCES brawlers(id) ON DELETE CASCADE,
+    secret VARCHAR(64) NOT NULL,
+    enabled_at TIMESTAMP,
+    last_used_step BIGINT,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE TABLE totp_recovery_codes (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    code_hash VARCHAR(64) NOT NULL,
+    used_at TIMESTAMP
+);
+
+CREATE INDEX idx_totp_recovery_codes_brawler_id ON totp_recovery_codes(brawler_id);
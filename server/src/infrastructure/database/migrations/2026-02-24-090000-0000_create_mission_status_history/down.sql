@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS mission_status_history;
+
+ALTER TABLE missions DROP CONSTRAINT IF EXISTS missions_status_check;
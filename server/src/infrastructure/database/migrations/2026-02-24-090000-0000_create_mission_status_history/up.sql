@@ -0,0 +1,20 @@
+-- Anything written before the state machine existed falls back to Open
+UPDATE missions
+SET status = 'Open'
+WHERE status NOT IN ('Open', 'InProgress', 'Completed', 'Failed', 'Cancelled');
+
+ALTER TABLE missions
+ADD CONSTRAINT missions_status_check
+CHECK (status IN ('Open', 'InProgress', 'Completed', 'Failed', 'Cancelled'));
+
+CREATE TABLE mission_status_history (
+    id SERIAL PRIMARY KEY,
+    mission_id INTEGER NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    changed_by INTEGER REFERENCES brawlers(id) ON DELETE SET NULL,
+    from_status VARCHAR(255) NOT NULL,
+    to_status VARCHAR(255) NOT NULL,
+    reason TEXT,
+    changed_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_mission_status_history_mission_id ON mission_status_history(mission_id);
@@ -0,0 +1 @@
+ALTER TABLE crew_memberships DROP CONSTRAINT IF EXISTS crew_memberships_role_check;
@@ -0,0 +1,12 @@
+-- Roles used to be free-form titles that granted nothing; keep the ones that
+-- map to a real role and demote the rest to Member
+UPDATE crew_memberships
+SET role = CASE LOWER(role)
+    WHEN 'officer' THEN 'Officer'
+    WHEN 'observer' THEN 'Observer'
+    ELSE 'Member'
+END;
+
+ALTER TABLE crew_memberships
+ADD CONSTRAINT crew_memberships_role_check
+CHECK (role IN ('Officer', 'Member', 'Observer'));
@@ -0,0 +1,16 @@
+CREATE TABLE chief_transfers (
+    id SERIAL PRIMARY KEY,
+    mission_id INTEGER NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    from_brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    to_brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    -- Crew role the old chief keeps afterwards; NULL means they leave the crew
+    old_chief_role VARCHAR(255),
+    status VARCHAR(50) NOT NULL DEFAULT 'Pending'
+        CHECK (status IN ('Pending', 'Completed', 'Declined', 'Cancelled')),
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    resolved_at TIMESTAMP
+);
+
+CREATE INDEX idx_chief_transfers_mission_id ON chief_transfers(mission_id);
+CREATE UNIQUE INDEX idx_chief_transfers_one_pending
+    ON chief_transfers(mission_id) WHERE status = 'Pending';
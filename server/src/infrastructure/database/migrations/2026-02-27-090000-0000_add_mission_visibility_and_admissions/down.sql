@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS mission_join_requests;
+DROP TABLE IF EXISTS mission_invitations;
+ALTER TABLE missions DROP COLUMN IF EXISTS visibility;
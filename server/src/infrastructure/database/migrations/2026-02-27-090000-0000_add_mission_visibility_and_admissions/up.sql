@@ -0,0 +1,35 @@
+ALTER TABLE missions
+    ADD COLUMN visibility VARCHAR(50) NOT NULL DEFAULT 'Public'
+        CHECK (visibility IN ('Public', 'RequestToJoin', 'InviteOnly'));
+
+CREATE TABLE mission_invitations (
+    id SERIAL PRIMARY KEY,
+    mission_id INTEGER NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    code VARCHAR(64) NOT NULL UNIQUE,
+    created_by INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    -- NULL makes the link usable by anyone who has it
+    invitee_id INTEGER REFERENCES brawlers(id) ON DELETE CASCADE,
+    max_uses INTEGER CHECK (max_uses > 0),
+    use_count INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMP NOT NULL,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_mission_invitations_mission_id ON mission_invitations(mission_id);
+
+CREATE TABLE mission_join_requests (
+    id SERIAL PRIMARY KEY,
+    mission_id INTEGER NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    message TEXT,
+    status VARCHAR(50) NOT NULL DEFAULT 'Pending'
+        CHECK (status IN ('Pending', 'Approved', 'Rejected', 'Cancelled')),
+    reviewed_by INTEGER REFERENCES brawlers(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    reviewed_at TIMESTAMP
+);
+
+CREATE INDEX idx_mission_join_requests_mission_id ON mission_join_requests(mission_id);
+CREATE UNIQUE INDEX idx_mission_join_requests_one_pending
+    ON mission_join_requests(mission_id, brawler_id) WHERE status = 'Pending';
@@ -0,0 +1,21 @@
+CREATE TABLE mission_waitlist (
+    id SERIAL PRIMARY KEY,
+    mission_id INTEGER NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    UNIQUE (mission_id, brawler_id)
+);
+
+CREATE INDEX idx_mission_waitlist_mission_id ON mission_waitlist(mission_id, id);
+
+CREATE TABLE notifications (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,
+    mission_id INTEGER REFERENCES missions(id) ON DELETE CASCADE,
+    message TEXT NOT NULL,
+    read_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_notifications_brawler_id ON notifications(brawler_id, created_at DESC);
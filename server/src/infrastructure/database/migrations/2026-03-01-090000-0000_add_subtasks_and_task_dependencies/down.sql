@@ -0,0 +1,6 @@
+ALTER TABLE tasks DROP CONSTRAINT IF EXISTS tasks_status_check;
+UPDATE tasks SET status = 'Success' WHERE status = 'Done';
+UPDATE tasks SET status = 'Failed' WHERE status = 'Rejected';
+
+DROP TABLE IF EXISTS task_dependencies;
+ALTER TABLE tasks DROP COLUMN IF EXISTS parent_task_id;
@@ -0,0 +1,21 @@
+ALTER TABLE tasks ADD COLUMN parent_task_id INTEGER REFERENCES tasks(id) ON DELETE CASCADE;
+CREATE INDEX idx_tasks_parent_task_id ON tasks(parent_task_id);
+
+CREATE TABLE task_dependencies (
+    task_id INTEGER NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    blocked_by_task_id INTEGER NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (task_id, blocked_by_task_id),
+    CHECK (task_id <> blocked_by_task_id)
+);
+
+CREATE INDEX idx_task_dependencies_blocked_by ON task_dependencies(blocked_by_task_id);
+
+-- Task statuses get their final names once: Success becomes Done and Failed becomes Rejected
+UPDATE tasks SET status = 'Done' WHERE status = 'Success';
+UPDATE tasks SET status = 'Rejected' WHERE status = 'Failed';
+UPDATE tasks SET status = 'Pending'
+WHERE status NOT IN ('Pending', 'In Progress', 'Review', 'Done', 'Rejected');
+
+ALTER TABLE tasks ADD CONSTRAINT tasks_status_check
+    CHECK (status IN ('Pending', 'In Progress', 'Review', 'Done', 'Rejected'));
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_mission_submissions_review_queue;
+ALTER TABLE mission_submissions
+    DROP COLUMN IF EXISTS reviewed_at,
+    DROP COLUMN IF EXISTS review_feedback,
+    DROP COLUMN IF EXISTS reviewed_by,
+    DROP COLUMN IF EXISTS review_status;
@@ -0,0 +1,9 @@
+ALTER TABLE mission_submissions
+    ADD COLUMN review_status VARCHAR(50) NOT NULL DEFAULT 'Pending'
+        CHECK (review_status IN ('Pending', 'Approved', 'Rejected', 'ChangesRequested')),
+    ADD COLUMN reviewed_by INTEGER REFERENCES brawlers(id) ON DELETE SET NULL,
+    ADD COLUMN review_feedback TEXT,
+    ADD COLUMN reviewed_at TIMESTAMPTZ;
+
+CREATE INDEX idx_mission_submissions_review_queue
+    ON mission_submissions(mission_id, submitted_at) WHERE review_status = 'Pending';
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_mission_submissions_task_current;
+DROP INDEX IF EXISTS idx_mission_submissions_task_version;
+DELETE FROM mission_submissions WHERE withdrawn_at IS NOT NULL OR (task_id IS NOT NULL AND NOT is_current);
+ALTER TABLE mission_submissions
+    DROP COLUMN IF EXISTS withdrawn_at,
+    DROP COLUMN IF EXISTS is_current,
+    DROP COLUMN IF EXISTS version;
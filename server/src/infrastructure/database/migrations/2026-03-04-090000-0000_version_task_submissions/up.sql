@@ -0,0 +1,22 @@
+ALTER TABLE mission_submissions
+    ADD COLUMN version INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN is_current BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN withdrawn_at TIMESTAMPTZ;
+
+-- Existing rows become v1, v2, ... per task in the order they were submitted
+UPDATE mission_submissions ms
+SET version = numbered.version,
+    is_current = numbered.version = numbered.latest
+FROM (
+    SELECT id,
+           ROW_NUMBER() OVER (PARTITION BY task_id ORDER BY submitted_at, id) AS version,
+           COUNT(*) OVER (PARTITION BY task_id) AS latest
+    FROM mission_submissions
+    WHERE task_id IS NOT NULL
+) numbered
+WHERE ms.id = numbered.id;
+
+CREATE UNIQUE INDEX idx_mission_submissions_task_version
+    ON mission_submissions(task_id, version) WHERE task_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_mission_submissions_task_current
+    ON mission_submissions(task_id) WHERE task_id IS NOT NULL AND is_current;
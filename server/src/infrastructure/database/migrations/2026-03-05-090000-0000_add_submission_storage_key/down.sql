@@ -0,0 +1 @@
+ALTER TABLE mission_submissions DROP COLUMN storage_key;
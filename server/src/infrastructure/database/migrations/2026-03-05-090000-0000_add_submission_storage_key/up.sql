@@ -0,0 +1,3 @@
+-- Where the file lives in the configured storage backend.
+-- Rows uploaded before storage became pluggable only have their Cloudinary URL.
+ALTER TABLE mission_submissions ADD COLUMN storage_key VARCHAR(512);
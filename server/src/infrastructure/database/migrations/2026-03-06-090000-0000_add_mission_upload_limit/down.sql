@@ -0,0 +1 @@
+ALTER TABLE missions DROP COLUMN max_upload_mb;
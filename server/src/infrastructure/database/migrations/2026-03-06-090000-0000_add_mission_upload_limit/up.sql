@@ -0,0 +1,5 @@
+-- Largest single submission a mission accepts, in megabytes.
+-- The server-wide UPLOAD_MAX_MB still caps whatever is set here.
+ALTER TABLE missions
+    ADD COLUMN max_upload_mb INT NOT NULL DEFAULT 25
+    CONSTRAINT missions_max_upload_mb_check CHECK (max_upload_mb BETWEEN 1 AND 1024);
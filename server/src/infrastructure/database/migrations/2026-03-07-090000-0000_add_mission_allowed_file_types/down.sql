@@ -0,0 +1 @@
+ALTER TABLE missions DROP COLUMN allowed_file_types;
@@ -0,0 +1,3 @@
+-- File types the chief accepts as submissions, by FileType name.
+ALTER TABLE missions
+    ADD COLUMN allowed_file_types TEXT[] NOT NULL DEFAULT ARRAY['Png', 'Jpeg', 'Pdf', 'Doc', 'Docx'];
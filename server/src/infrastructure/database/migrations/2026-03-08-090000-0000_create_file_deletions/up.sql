@@ -0,0 +1,14 @@
+-- Stored files waiting to be removed from their backend.
+-- Rows are queued together with the change that orphaned the file and retried until the delete succeeds.
+CREATE TABLE file_deletions (
+    id SERIAL PRIMARY KEY,
+    store VARCHAR(50) NOT NULL CHECK (store IN ('Files', 'Avatars')),
+    storage_key VARCHAR(512) NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (store, storage_key)
+);
+
+CREATE INDEX idx_file_deletions_next_attempt ON file_deletions (next_attempt_at);
@@ -0,0 +1,34 @@
+CREATE TABLE comments (
+    id SERIAL PRIMARY KEY,
+    mission_id INTEGER NOT NULL REFERENCES missions(id) ON DELETE CASCADE,
+    task_id INTEGER REFERENCES tasks(id) ON DELETE CASCADE,
+    submission_id INTEGER REFERENCES mission_submissions(id) ON DELETE CASCADE,
+    parent_id INTEGER REFERENCES comments(id) ON DELETE CASCADE,
+    author_id INTEGER REFERENCES brawlers(id) ON DELETE SET NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at TIMESTAMP,
+    -- Deleted comments stay behind as placeholders so their replies keep a parent
+    deleted_at TIMESTAMP,
+    CHECK ((task_id IS NULL) <> (submission_id IS NULL))
+);
+
+CREATE INDEX idx_comments_task_id ON comments(task_id, created_at) WHERE task_id IS NOT NULL;
+CREATE INDEX idx_comments_submission_id ON comments(submission_id, created_at) WHERE submission_id IS NOT NULL;
+CREATE INDEX idx_comments_parent_id ON comments(parent_id);
+
+CREATE TABLE comment_edits (
+    id SERIAL PRIMARY KEY,
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    -- The body as it was before the edit
+    body TEXT NOT NULL,
+    edited_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_comment_edits_comment_id ON comment_edits(comment_id, edited_at);
+
+CREATE TABLE comment_mentions (
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    PRIMARY KEY (comment_id, brawler_id)
+);
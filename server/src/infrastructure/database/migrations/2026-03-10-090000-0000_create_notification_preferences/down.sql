@@ -0,0 +1,2 @@
+DROP INDEX idx_notifications_unread;
+DROP TABLE notification_preferences;
@@ -0,0 +1,11 @@
+-- Notification kinds a brawler has switched off or back on.
+-- A kind without a row is delivered.
+CREATE TABLE notification_preferences (
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (brawler_id, kind)
+);
+
+CREATE INDEX idx_notifications_unread ON notifications (brawler_id) WHERE read_at IS NULL;
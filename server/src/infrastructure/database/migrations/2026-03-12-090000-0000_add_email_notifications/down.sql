@@ -0,0 +1,7 @@
+DROP TABLE mail_outbox;
+DROP TABLE email_verification_tokens;
+ALTER TABLE missions DROP COLUMN deadline_reminded_at;
+ALTER TABLE notification_preferences DROP COLUMN email_enabled;
+ALTER TABLE brawlers DROP COLUMN last_digest_at;
+ALTER TABLE brawlers DROP COLUMN email_digest;
+ALTER TABLE brawlers DROP COLUMN email_verified_at;
@@ -0,0 +1,45 @@
+-- An address only receives notification mail once its owner proved they read it.
+ALTER TABLE brawlers ADD COLUMN email_verified_at TIMESTAMP;
+ALTER TABLE brawlers ADD COLUMN email_digest BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE brawlers ADD COLUMN last_digest_at TIMESTAMP;
+
+-- NULL follows the kind's default channel.
+ALTER TABLE notification_preferences ADD COLUMN email_enabled BOOLEAN;
+
+-- end_date is the mission deadline. It is in the schema but no earlier migration
+-- creates it, so databases built from migrations alone lack it.
+ALTER TABLE missions ADD COLUMN IF NOT EXISTS end_date TIMESTAMP;
+-- Set once the crew was reminded of end_date; cleared whenever the deadline moves.
+ALTER TABLE missions ADD COLUMN deadline_reminded_at TIMESTAMP;
+
+-- The address being verified lives on the token until it is confirmed,
+-- so a typo never replaces a working address.
+CREATE TABLE email_verification_tokens (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL REFERENCES brawlers(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP
+);
+
+CREATE INDEX idx_email_verification_tokens_brawler_id ON email_verification_tokens (brawler_id);
+
+-- Notification mail waiting to be handed to the mail server.
+-- Pending rows are retried with backoff until they are Sent or give up as Failed.
+CREATE TABLE mail_outbox (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER REFERENCES brawlers(id) ON DELETE CASCADE,
+    to_address VARCHAR(255) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'Pending' CHECK (status IN ('Pending', 'Sent', 'Failed')),
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMP
+);
+
+CREATE INDEX idx_mail_outbox_pending ON mail_outbox (next_attempt_at) WHERE status = 'Pending';
@@ -0,0 +1,4 @@
+DROP INDEX idx_missions_chief_id;
+DROP INDEX idx_missions_listing;
+DROP INDEX idx_missions_tags;
+ALTER TABLE missions DROP COLUMN tags;
@@ -0,0 +1,8 @@
+-- end_date is in the schema but was never created by a migration, so fresh databases lack it
+ALTER TABLE missions ADD COLUMN IF NOT EXISTS end_date TIMESTAMP;
+ALTER TABLE missions ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX idx_missions_tags ON missions USING GIN (tags);
+-- Keyset pages of the default listing walk this index
+CREATE INDEX idx_missions_listing ON missions (created_at DESC, id DESC) WHERE deleted_at IS NULL;
+CREATE INDEX idx_missions_chief_id ON missions (chief_id);
@@ -0,0 +1,7 @@
+DROP INDEX idx_mission_submissions_search;
+DROP INDEX idx_tasks_search;
+DROP INDEX idx_missions_search;
+
+ALTER TABLE mission_submissions DROP COLUMN search_vector;
+ALTER TABLE tasks DROP COLUMN search_vector;
+ALTER TABLE missions DROP COLUMN search_vector;
@@ -0,0 +1,11 @@
+DROP INDEX IF EXISTS unique_verified_email;
+
+-- Unverified copies of an address give way to the account that holds it
+UPDATE brawlers b SET email = NULL
+WHERE b.email_verified_at IS NULL
+  AND EXISTS (
+      SELECT 1 FROM brawlers o
+      WHERE o.email = b.email AND o.id <> b.id AND (o.email_verified_at IS NOT NULL OR o.id < b.id)
+  );
+
+ALTER TABLE brawlers ADD CONSTRAINT unique_email UNIQUE (email);
@@ -0,0 +1,15 @@
+-- Registration stores an address unverified, so only a verified address holds it.
+-- Otherwise anyone could register someone else's address and block its owner from verifying it.
+ALTER TABLE brawlers DROP CONSTRAINT IF EXISTS unique_email;
+
+UPDATE brawlers SET email = LOWER(email) WHERE email <> LOWER(email);
+
+-- Lower-casing can make two verified addresses equal; the older account keeps its verification.
+UPDATE brawlers b SET email_verified_at = NULL
+WHERE b.email_verified_at IS NOT NULL
+  AND EXISTS (
+      SELECT 1 FROM brawlers o
+      WHERE o.email = b.email AND o.email_verified_at IS NOT NULL AND o.id < b.id
+  );
+
+CREATE UNIQUE INDEX unique_verified_email ON brawlers (email) WHERE email_verified_at IS NOT NULL;
@@ -0,0 +1,152 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use diesel::{dsl::now, insert_into, prelude::*, update};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::auth_sessions::{
+            AddAuthSessionEntity, AddRefreshTokenEntity, AuthSessionEntity, RefreshTokenEntity,
+        },
+        repositories::auth_sessions::AuthSessionRepository,
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        schema::{auth_sessions, refresh_tokens},
+    },
+};
+
+pub struct AuthSessionPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl AuthSessionPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl AuthSessionRepository for AuthSessionPostgres {
+    async fn start(
+        &self,
+        add_session_entity: AddAuthSessionEntity,
+        token_hash: String,
+    ) -> Result<AuthSessionEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let session = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let session = insert_into(auth_sessions::table)
+                .values(&add_session_entity)
+                .returning(AuthSessionEntity::as_returning())
+                .get_result::<AuthSessionEntity>(conn)?;
+
+            insert_into(refresh_tokens::table)
+                .values(AddRefreshTokenEntity {
+                    session_id: session.id,
+                    token_hash,
+                })
+                .execute(conn)?;
+
+            Ok(session)
+        })?;
+
+        Ok(session)
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<(RefreshTokenEntity, AuthSessionEntity)>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = refresh_tokens::table
+            .inner_join(auth_sessions::table)
+            .filter(refresh_tokens::token_hash.eq(token_hash))
+            .select((
+                RefreshTokenEntity::as_select(),
+                AuthSessionEntity::as_select(),
+            ))
+            .first::<(RefreshTokenEntity, AuthSessionEntity)>(&mut conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn rotate(
+        &self,
+        used_token_id: i32,
+        session_id: i32,
+        token_hash: String,
+        expires_at: NaiveDateTime,
+    ) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rotated = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let rows_affected = update(refresh_tokens::table)
+                .filter(refresh_tokens::id.eq(used_token_id))
+                .filter(refresh_tokens::used_at.is_null())
+                .set(refresh_tokens::used_at.eq(now))
+                .execute(conn)?;
+
+            if rows_affected == 0 {
+                return Ok(false);
+            }
+
+            insert_into(refresh_tokens::table)
+                .values(AddRefreshTokenEntity {
+                    session_id,
+                    token_hash,
+                })
+                .execute(conn)?;
+
+            update(auth_sessions::table)
+                .filter(auth_sessions::id.eq(session_id))
+                .set(auth_sessions::expires_at.eq(expires_at))
+                .execute(conn)?;
+
+            Ok(true)
+        })?;
+
+        Ok(rotated)
+    }
+
+    async fn revoke(&self, session_id: i32) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        update(auth_sessions::table)
+            .filter(auth_sessions::id.eq(session_id))
+            .filter(auth_sessions::revoked_at.is_null())
+            .set(auth_sessions::revoked_at.eq(now))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn revoke_all(&self, brawler_id: i32) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        update(auth_sessions::table)
+            .filter(auth_sessions::brawler_id.eq(brawler_id))
+            .filter(auth_sessions::revoked_at.is_null())
+            .set(auth_sessions::revoked_at.eq(now))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn is_active(&self, session_id: i32, brawler_id: i32) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let count: i64 = auth_sessions::table
+            .filter(auth_sessions::id.eq(session_id))
+            .filter(auth_sessions::brawler_id.eq(brawler_id))
+            .filter(auth_sessions::revoked_at.is_null())
+            .filter(auth_sessions::expires_at.gt(Utc::now().naive_utc()))
+            .count()
+            .get_result(&mut conn)?;
+
+        Ok(count > 0)
+    }
+}
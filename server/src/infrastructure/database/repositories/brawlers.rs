@@ -1,7 +1,7 @@
 ﻿use anyhow::{Ok, Result};
 use async_trait::async_trait;
 use diesel::{
-    ExpressionMethods, RunQueryDsl, SelectableHelper, insert_into,
+    Connection, ExpressionMethods, NullableExpressionMethods, QueryResult, RunQueryDsl, SelectableHelper, insert_into,
     QueryDsl,
 };
 use std::sync::Arc;
@@ -15,13 +15,15 @@ use crate::{
         repositories::brawlers::BrawlerRepository,
         value_objects::{
             base64_img::Base64Img, uploaded_img::UploadedImg, brawler_model::UpdateBrawlerModel,
+            file_stores::FileStores,
         },
     },
     infrastructure::{
         cloudinary::{self, UploadImageOptions},
-        database::{postgresql_connection::PgPoolSquad, schema::{brawlers, crew_memberships, missions}},
-        jwt::{
-            jwt_model::Passport,
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::file_deletions::enqueue_in,
+            schema::{brawlers, crew_memberships, missions},
         },
     },
 };
@@ -38,20 +40,14 @@ impl BrawlerPostgres {
 
 #[async_trait]
 impl BrawlerRepository for BrawlerPostgres {
-    async fn register(&self, register_brawler_entity: RegisterBrawlerEntity) -> Result<Passport> {
+    async fn register(&self, register_brawler_entity: RegisterBrawlerEntity) -> Result<BrawlerEntity> {
         let mut connection = Arc::clone(&self.db_pool).get()?;
 
         let brawler = insert_into(brawlers::table)
             .values(&register_brawler_entity)
             .get_result::<BrawlerEntity>(&mut connection)?;
 
-        Passport::new(
-            brawler.id,
-            brawler.display_name,
-            brawler.avatar_url,
-            brawler.bio,
-            Some(brawler.created_at.to_string()),
-        )
+        Ok(brawler)
     }
 
     async fn find_by_username(&self, username: String) -> Result<BrawlerEntity> {
@@ -86,13 +82,30 @@ impl BrawlerRepository for BrawlerPostgres {
 
         let mut conn = Arc::clone(&self.db_pool).get()?;
 
-        diesel::update(brawlers::table)
-            .filter(brawlers::id.eq(user_id))
-            .set((
-                brawlers::avatar_url.eq(uploaded_img.url.clone()),
-                brawlers::avatar_public_id.eq(uploaded_img.public_id.clone()),
-            ))
-            .execute(&mut conn)?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let previous = brawlers::table
+                .find(user_id)
+                .select(brawlers::avatar_public_id)
+                .for_update()
+                .first::<Option<String>>(conn)?;
+
+            diesel::update(brawlers::table)
+                .filter(brawlers::id.eq(user_id))
+                .set((
+                    brawlers::avatar_url.eq(uploaded_img.url.clone()),
+                    brawlers::avatar_public_id.eq(uploaded_img.public_id.clone()),
+                ))
+                .execute(conn)?;
+
+            // A replaced image under a different public id would otherwise linger
+            if let Some(previous) = previous
+                && previous != uploaded_img.public_id
+            {
+                enqueue_in(conn, FileStores::Avatars, vec![format!("image/{}", previous)])?;
+            }
+
+            QueryResult::Ok(())
+        })?;
 
         Ok(uploaded_img)
     }
@@ -108,6 +121,20 @@ impl BrawlerRepository for BrawlerPostgres {
         Ok(count as u32)
     }
 
+    async fn is_led_by(&self, brawler_id: i32, chief_id: i32) -> Result<bool> {
+        let mut connection = Arc::clone(&self.db_pool).get()?;
+
+        let count: i64 = crew_memberships::table
+            .inner_join(missions::table)
+            .filter(crew_memberships::brawler_id.eq(brawler_id))
+            .filter(missions::chief_id.eq(chief_id))
+            .filter(missions::deleted_at.is_null())
+            .count()
+            .get_result(&mut connection)?;
+
+        Ok(count > 0)
+    }
+
     async fn get_missions(&self, brawler_id: i32) -> Result<Vec<MissionEntity>> {
         let mut connection = Arc::clone(&self.db_pool).get()?;
 
@@ -130,4 +157,37 @@ impl BrawlerRepository for BrawlerPostgres {
 
         Ok(())
     }
+
+    async fn update_password(&self, brawler_id: i32, hashed_password: String) -> Result<()> {
+        let mut connection = Arc::clone(&self.db_pool).get()?;
+
+        diesel::update(brawlers::table)
+            .filter(brawlers::id.eq(brawler_id))
+            .set(brawlers::password.eq(hashed_password))
+            .execute(&mut connection)?;
+
+        Ok(())
+    }
+
+    async fn set_email_digest(&self, brawler_id: i32, enabled: bool) -> Result<()> {
+        let mut connection = Arc::clone(&self.db_pool).get()?;
+
+        diesel::update(brawlers::table)
+            .filter(brawlers::id.eq(brawler_id))
+            .set(brawlers::email_digest.eq(enabled))
+            .execute(&mut connection)?;
+
+        Ok(())
+    }
+
+    async fn avatar_public_ids(&self) -> Result<Vec<String>> {
+        let mut connection = Arc::clone(&self.db_pool).get()?;
+
+        let result = brawlers::table
+            .filter(brawlers::avatar_public_id.is_not_null())
+            .select(brawlers::avatar_public_id.assume_not_null())
+            .load::<String>(&mut connection)?;
+
+        Ok(result)
+    }
 }
@@ -0,0 +1,257 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, PgConnection,
+    QueryDsl, RunQueryDsl, SelectableHelper,
+    dsl::{delete, exists, now, update},
+    insert_into,
+    sql_types::Text,
+};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::{
+            comments::{AddCommentEntity, CommentEditEntity, CommentEntity},
+            notifications::AddNotificationEntity,
+        },
+        repositories::comments::CommentRepository,
+        value_objects::{
+            comment_model::{CommentModel, CommentTarget},
+            notification_kinds::NotificationKinds,
+        },
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        repositories::notifications::notify_in,
+        schema::{brawlers, comment_edits, comment_mentions, comments, crew_memberships, missions},
+    },
+};
+
+diesel::define_sql_function! {
+    fn lower(value: Text) -> Text;
+}
+
+pub struct CommentPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl CommentPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+/// Records the mentions and tells each mentioned brawler, skipping the author.
+fn notify_mentions(conn: &mut PgConnection, comment: &CommentEntity, mentioned: &[i32]) -> diesel::QueryResult<()> {
+    let mentioned: Vec<i32> = mentioned
+        .iter()
+        .copied()
+        .filter(|brawler_id| Some(*brawler_id) != comment.author_id)
+        .collect();
+    if mentioned.is_empty() {
+        return diesel::QueryResult::Ok(());
+    }
+
+    let rows: Vec<_> = mentioned
+        .iter()
+        .map(|brawler_id| {
+            (
+                comment_mentions::comment_id.eq(comment.id),
+                comment_mentions::brawler_id.eq(*brawler_id),
+            )
+        })
+        .collect();
+    insert_into(comment_mentions::table)
+        .values(&rows)
+        .on_conflict_do_nothing()
+        .execute(conn)?;
+
+    let author = match comment.author_id {
+        Some(author_id) => brawlers::table
+            .find(author_id)
+            .select(brawlers::display_name)
+            .first::<String>(conn)
+            .optional()?,
+        None => None,
+    };
+    let message = format!(
+        "{} mentioned you in a comment",
+        author.unwrap_or("Someone".to_string())
+    );
+
+    let notices: Vec<AddNotificationEntity> = mentioned
+        .into_iter()
+        .map(|brawler_id| AddNotificationEntity {
+            brawler_id,
+            kind: NotificationKinds::Mentioned.to_string(),
+            mission_id: Some(comment.mission_id),
+            message: message.clone(),
+        })
+        .collect();
+    notify_in(conn, notices)?;
+
+    diesel::QueryResult::Ok(())
+}
+
+#[async_trait]
+impl CommentRepository for CommentPostgres {
+    async fn create(&self, entity: AddCommentEntity, mentions: Vec<i32>) -> Result<CommentEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let comment = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let comment = insert_into(comments::table)
+                .values(&entity)
+                .returning(CommentEntity::as_returning())
+                .get_result::<CommentEntity>(conn)?;
+
+            notify_mentions(conn, &comment, &mentions)?;
+
+            diesel::QueryResult::Ok(comment)
+        })?;
+
+        Ok(comment)
+    }
+
+    async fn edit(&self, id: i32, body: String, mentions: Vec<i32>) -> Result<CommentEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let comment = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let previous = comments::table
+                .find(id)
+                .for_update()
+                .select(CommentEntity::as_select())
+                .first::<CommentEntity>(conn)?;
+
+            insert_into(comment_edits::table)
+                .values((
+                    comment_edits::comment_id.eq(id),
+                    comment_edits::body.eq(&previous.body),
+                ))
+                .execute(conn)?;
+
+            let comment = update(comments::table.find(id))
+                .set((comments::body.eq(body), comments::edited_at.eq(now)))
+                .returning(CommentEntity::as_returning())
+                .get_result::<CommentEntity>(conn)?;
+
+            let already_mentioned = comment_mentions::table
+                .filter(comment_mentions::comment_id.eq(id))
+                .select(comment_mentions::brawler_id)
+                .load::<i32>(conn)?;
+
+            // Names taken out of the body stop counting as mentions
+            delete(
+                comment_mentions::table
+                    .filter(comment_mentions::comment_id.eq(id))
+                    .filter(comment_mentions::brawler_id.ne_all(&mentions)),
+            )
+            .execute(conn)?;
+
+            let added: Vec<i32> = mentions
+                .into_iter()
+                .filter(|brawler_id| !already_mentioned.contains(brawler_id))
+                .collect();
+            notify_mentions(conn, &comment, &added)?;
+
+            diesel::QueryResult::Ok(comment)
+        })?;
+
+        Ok(comment)
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            delete(comment_edits::table.filter(comment_edits::comment_id.eq(id))).execute(conn)?;
+            delete(comment_mentions::table.filter(comment_mentions::comment_id.eq(id))).execute(conn)?;
+
+            update(comments::table.find(id))
+                .set((comments::body.eq(""), comments::deleted_at.eq(now)))
+                .execute(conn)?;
+
+            diesel::QueryResult::Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<Option<CommentEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = comments::table
+            .find(id)
+            .select(CommentEntity::as_select())
+            .first::<CommentEntity>(&mut conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn get_by_target(&self, target: CommentTarget) -> Result<Vec<CommentModel>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let sql = r#"
+            SELECT c.id, c.mission_id, c.task_id, c.submission_id, c.parent_id, c.author_id,
+                   b.display_name AS author_name, b.avatar_url AS author_avatar_url,
+                   c.body,
+                   ARRAY(
+                       SELECT cm.brawler_id FROM comment_mentions cm
+                       WHERE cm.comment_id = c.id
+                       ORDER BY cm.brawler_id
+                   ) AS mentions,
+                   c.created_at, c.edited_at, c.deleted_at
+            FROM comments c
+            LEFT JOIN brawlers b ON b.id = c.author_id
+            WHERE c.task_id IS NOT DISTINCT FROM $1
+              AND c.submission_id IS NOT DISTINCT FROM $2
+            ORDER BY c.created_at ASC, c.id ASC
+        "#;
+
+        let result = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Int4>, _>(target.task_id())
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Int4>, _>(target.submission_id())
+            .load::<CommentModel>(&mut conn)?;
+
+        Ok(result)
+    }
+
+    async fn get_edits(&self, id: i32) -> Result<Vec<CommentEditEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = comment_edits::table
+            .filter(comment_edits::comment_id.eq(id))
+            .order((comment_edits::edited_at.asc(), comment_edits::id.asc()))
+            .select(CommentEditEntity::as_select())
+            .load::<CommentEditEntity>(&mut conn)?;
+
+        Ok(result)
+    }
+
+    async fn find_members_by_username(&self, mission_id: i32, usernames: Vec<String>) -> Result<Vec<i32>> {
+        if usernames.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let is_chief = exists(
+            missions::table
+                .filter(missions::id.eq(mission_id))
+                .filter(missions::chief_id.eq(brawlers::id)),
+        );
+        let is_crew = exists(
+            crew_memberships::table
+                .filter(crew_memberships::mission_id.eq(mission_id))
+                .filter(crew_memberships::brawler_id.eq(brawlers::id)),
+        );
+
+        let result = brawlers::table
+            .filter(lower(brawlers::username).eq_any(usernames))
+            .filter(is_chief.or(is_crew))
+            .select(brawlers::id)
+            .load::<i32>(&mut conn)?;
+
+        Ok(result)
+    }
+}
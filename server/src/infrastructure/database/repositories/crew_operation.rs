@@ -1,14 +1,38 @@
 use anyhow::{Ok, Result};
 use async_trait::async_trait;
-use diesel::{ExpressionMethods, RunQueryDsl, dsl::delete, dsl::update, insert_into};
+use chrono::Utc;
+use diesel::{
+    Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper,
+    dsl::{delete, now, update},
+    insert_into,
+};
 use std::sync::Arc;
 
 use crate::{
     domain::{
-        entities::crew_memberships::CrewMemberShips,
+        entities::{
+            chief_transfers::{AddChiefTransferEntity, ChiefTransferEntity},
+            crew_memberships::CrewMemberShips,
+            mission_invitations::{AddMissionInvitationEntity, MissionInvitationEntity},
+            mission_join_requests::{AddMissionJoinRequestEntity, MissionJoinRequestEntity},
+            mission_waitlist::{AddMissionWaitlistEntity, MissionWaitlistEntity},
+            notifications::AddNotificationEntity,
+        },
         repositories::crew_operation::CrewOperationRepository,
+        value_objects::{
+            chief_transfer_model::ChiefTransferStatuses, crew_roles::CrewRole,
+            mission_admission_model::JoinRequestStatuses, mission_visibility::MissionVisibility,
+            notification_kinds::NotificationKinds,
+        },
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        repositories::notifications::notify_in,
+        schema::{
+            chief_transfers, crew_memberships, mission_invitations, mission_join_requests,
+            mission_waitlist, missions,
+        },
     },
-    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::crew_memberships},
 };
 
 pub struct CrewOperationPostgres {
@@ -51,4 +75,493 @@ impl CrewOperationRepository for CrewOperationPostgres {
             .execute(&mut conn)?;
         Ok(())
     }
+
+    async fn create_chief_transfer(
+        &self,
+        add_chief_transfer_entity: AddChiefTransferEntity,
+    ) -> Result<ChiefTransferEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let transfer = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            update(chief_transfers::table)
+                .filter(chief_transfers::mission_id.eq(add_chief_transfer_entity.mission_id))
+                .filter(chief_transfers::status.eq(ChiefTransferStatuses::Pending.to_string()))
+                .set((
+                    chief_transfers::status.eq(ChiefTransferStatuses::Cancelled.to_string()),
+                    chief_transfers::resolved_at.eq(now),
+                ))
+                .execute(conn)?;
+
+            insert_into(chief_transfers::table)
+                .values(&add_chief_transfer_entity)
+                .returning(ChiefTransferEntity::as_returning())
+                .get_result::<ChiefTransferEntity>(conn)
+        })?;
+
+        Ok(transfer)
+    }
+
+    async fn find_chief_transfer(&self, transfer_id: i32) -> Result<ChiefTransferEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let transfer = chief_transfers::table
+            .find(transfer_id)
+            .select(ChiefTransferEntity::as_select())
+            .first::<ChiefTransferEntity>(&mut conn)?;
+
+        Ok(transfer)
+    }
+
+    async fn chief_transfers(&self, mission_id: i32) -> Result<Vec<ChiefTransferEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let transfers = chief_transfers::table
+            .filter(chief_transfers::mission_id.eq(mission_id))
+            .order(chief_transfers::created_at.desc())
+            .select(ChiefTransferEntity::as_select())
+            .load::<ChiefTransferEntity>(&mut conn)?;
+
+        Ok(transfers)
+    }
+
+    async fn resolve_chief_transfer(&self, transfer_id: i32, status: String) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rows_affected = update(chief_transfers::table)
+            .filter(chief_transfers::id.eq(transfer_id))
+            .filter(chief_transfers::status.eq(ChiefTransferStatuses::Pending.to_string()))
+            .set((
+                chief_transfers::status.eq(status),
+                chief_transfers::resolved_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn complete_chief_transfer(&self, transfer_id: i32) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let completed = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let transfer = chief_transfers::table
+                .find(transfer_id)
+                .for_update()
+                .select(ChiefTransferEntity::as_select())
+                .first::<ChiefTransferEntity>(conn)?;
+            if transfer.status != ChiefTransferStatuses::Pending.to_string() {
+                return diesel::QueryResult::Ok(false);
+            }
+
+            // Check every precondition under lock before writing anything
+            let current_chief = missions::table
+                .find(transfer.mission_id)
+                .filter(missions::deleted_at.is_null())
+                .for_update()
+                .select(missions::chief_id)
+                .first::<i32>(conn)
+                .optional()?;
+            if current_chief != Some(transfer.from_brawler_id) {
+                return diesel::QueryResult::Ok(false);
+            }
+
+            let target_in_crew = crew_memberships::table
+                .filter(crew_memberships::mission_id.eq(transfer.mission_id))
+                .filter(crew_memberships::brawler_id.eq(transfer.to_brawler_id))
+                .for_update()
+                .select(crew_memberships::brawler_id)
+                .first::<i32>(conn)
+                .optional()?
+                .is_some();
+            if !target_in_crew {
+                return diesel::QueryResult::Ok(false);
+            }
+
+            // The chief never holds a crew seat, so the new one gives theirs up
+            delete(crew_memberships::table)
+                .filter(crew_memberships::mission_id.eq(transfer.mission_id))
+                .filter(crew_memberships::brawler_id.eq(transfer.to_brawler_id))
+                .execute(conn)?;
+
+            update(missions::table.find(transfer.mission_id))
+                .set((
+                    missions::chief_id.eq(transfer.to_brawler_id),
+                    missions::updated_at.eq(now),
+                ))
+                .execute(conn)?;
+
+            if let Some(role) = transfer.old_chief_role {
+                insert_into(crew_memberships::table)
+                    .values(CrewMemberShips {
+                        brawler_id: transfer.from_brawler_id,
+                        mission_id: transfer.mission_id,
+                        joined_at: Utc::now().naive_utc(),
+                        role,
+                    })
+                    .execute(conn)?;
+            }
+
+            update(chief_transfers::table.find(transfer_id))
+                .set((
+                    chief_transfers::status.eq(ChiefTransferStatuses::Completed.to_string()),
+                    chief_transfers::resolved_at.eq(now),
+                ))
+                .execute(conn)?;
+
+            diesel::QueryResult::Ok(true)
+        })?;
+
+        Ok(completed)
+    }
+
+    async fn create_invitation(
+        &self,
+        add_mission_invitation_entity: AddMissionInvitationEntity,
+    ) -> Result<MissionInvitationEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let invitation = insert_into(mission_invitations::table)
+            .values(&add_mission_invitation_entity)
+            .returning(MissionInvitationEntity::as_returning())
+            .get_result::<MissionInvitationEntity>(&mut conn)?;
+
+        Ok(invitation)
+    }
+
+    async fn find_invitation_by_code(&self, code: &str) -> Result<Option<MissionInvitationEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let invitation = mission_invitations::table
+            .filter(mission_invitations::code.eq(code))
+            .select(MissionInvitationEntity::as_select())
+            .first::<MissionInvitationEntity>(&mut conn)
+            .optional()?;
+
+        Ok(invitation)
+    }
+
+    async fn invitations(&self, mission_id: i32) -> Result<Vec<MissionInvitationEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let invitations = mission_invitations::table
+            .filter(mission_invitations::mission_id.eq(mission_id))
+            .order(mission_invitations::created_at.desc())
+            .select(MissionInvitationEntity::as_select())
+            .load::<MissionInvitationEntity>(&mut conn)?;
+
+        Ok(invitations)
+    }
+
+    async fn revoke_invitation(&self, mission_id: i32, invitation_id: i32) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rows_affected = update(mission_invitations::table)
+            .filter(mission_invitations::id.eq(invitation_id))
+            .filter(mission_invitations::mission_id.eq(mission_id))
+            .filter(mission_invitations::revoked_at.is_null())
+            .set(mission_invitations::revoked_at.eq(now))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn redeem_invitation(
+        &self,
+        invitation_id: i32,
+        crew_member_ships: CrewMemberShips,
+    ) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let redeemed = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let invitation = mission_invitations::table
+                .find(invitation_id)
+                .for_update()
+                .select(MissionInvitationEntity::as_select())
+                .first::<MissionInvitationEntity>(conn)?;
+            if !invitation.is_usable(Utc::now().naive_utc()) {
+                return diesel::QueryResult::Ok(false);
+            }
+
+            let joined = insert_into(crew_memberships::table)
+                .values(crew_member_ships)
+                .on_conflict((crew_memberships::mission_id, crew_memberships::brawler_id))
+                .do_nothing()
+                .execute(conn)?;
+
+            // Someone already in the crew doesn't burn a use
+            if joined > 0 {
+                update(mission_invitations::table.find(invitation_id))
+                    .set(mission_invitations::use_count.eq(mission_invitations::use_count + 1))
+                    .execute(conn)?;
+            }
+
+            diesel::QueryResult::Ok(true)
+        })?;
+
+        Ok(redeemed)
+    }
+
+    async fn create_join_request(
+        &self,
+        add_mission_join_request_entity: AddMissionJoinRequestEntity,
+    ) -> Result<MissionJoinRequestEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let request = insert_into(mission_join_requests::table)
+            .values(&add_mission_join_request_entity)
+            .returning(MissionJoinRequestEntity::as_returning())
+            .get_result::<MissionJoinRequestEntity>(&mut conn)?;
+
+        Ok(request)
+    }
+
+    async fn find_join_request(&self, request_id: i32) -> Result<MissionJoinRequestEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let request = mission_join_requests::table
+            .find(request_id)
+            .select(MissionJoinRequestEntity::as_select())
+            .first::<MissionJoinRequestEntity>(&mut conn)?;
+
+        Ok(request)
+    }
+
+    async fn join_requests(
+        &self,
+        mission_id: i32,
+        status: Option<String>,
+    ) -> Result<Vec<MissionJoinRequestEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let mut query = mission_join_requests::table
+            .filter(mission_join_requests::mission_id.eq(mission_id))
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(mission_join_requests::status.eq(status));
+        }
+
+        let requests = query
+            .order(mission_join_requests::created_at.asc())
+            .select(MissionJoinRequestEntity::as_select())
+            .load::<MissionJoinRequestEntity>(&mut conn)?;
+
+        Ok(requests)
+    }
+
+    async fn approve_join_request(
+        &self,
+        request_id: i32,
+        reviewer_id: i32,
+        crew_member_ships: CrewMemberShips,
+    ) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let approved = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let rows_affected = update(mission_join_requests::table)
+                .filter(mission_join_requests::id.eq(request_id))
+                .filter(mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()))
+                .set((
+                    mission_join_requests::status.eq(JoinRequestStatuses::Approved.to_string()),
+                    mission_join_requests::reviewed_by.eq(reviewer_id),
+                    mission_join_requests::reviewed_at.eq(now),
+                ))
+                .execute(conn)?;
+            if rows_affected == 0 {
+                return diesel::QueryResult::Ok(false);
+            }
+
+            insert_into(crew_memberships::table)
+                .values(crew_member_ships)
+                .on_conflict((crew_memberships::mission_id, crew_memberships::brawler_id))
+                .do_nothing()
+                .execute(conn)?;
+
+            diesel::QueryResult::Ok(true)
+        })?;
+
+        Ok(approved)
+    }
+
+    async fn resolve_join_request(
+        &self,
+        request_id: i32,
+        reviewer_id: Option<i32>,
+        status: String,
+    ) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rows_affected = update(mission_join_requests::table)
+            .filter(mission_join_requests::id.eq(request_id))
+            .filter(mission_join_requests::status.eq(JoinRequestStatuses::Pending.to_string()))
+            .set((
+                mission_join_requests::status.eq(status),
+                mission_join_requests::reviewed_by.eq(reviewer_id),
+                mission_join_requests::reviewed_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn join_waitlist(
+        &self,
+        add_mission_waitlist_entity: AddMissionWaitlistEntity,
+    ) -> Result<MissionWaitlistEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let entry = insert_into(mission_waitlist::table)
+            .values(&add_mission_waitlist_entity)
+            .returning(MissionWaitlistEntity::as_returning())
+            .get_result::<MissionWaitlistEntity>(&mut conn)?;
+
+        Ok(entry)
+    }
+
+    async fn leave_waitlist(&self, mission_id: i32, brawler_id: i32) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rows_affected = delete(mission_waitlist::table)
+            .filter(mission_waitlist::mission_id.eq(mission_id))
+            .filter(mission_waitlist::brawler_id.eq(brawler_id))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn waitlist(&self, mission_id: i32) -> Result<Vec<MissionWaitlistEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let entries = mission_waitlist::table
+            .filter(mission_waitlist::mission_id.eq(mission_id))
+            .order(mission_waitlist::id.asc())
+            .select(MissionWaitlistEntity::as_select())
+            .load::<MissionWaitlistEntity>(&mut conn)?;
+
+        Ok(entries)
+    }
+
+    async fn waitlist_position(&self, mission_id: i32, brawler_id: i32) -> Result<Option<i64>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let entry_id = mission_waitlist::table
+            .filter(mission_waitlist::mission_id.eq(mission_id))
+            .filter(mission_waitlist::brawler_id.eq(brawler_id))
+            .select(mission_waitlist::id)
+            .first::<i32>(&mut conn)
+            .optional()?;
+
+        let Some(entry_id) = entry_id else {
+            return Ok(None);
+        };
+
+        let position = mission_waitlist::table
+            .filter(mission_waitlist::mission_id.eq(mission_id))
+            .filter(mission_waitlist::id.le(entry_id))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+
+        Ok(Some(position))
+    }
+
+    async fn waitlist_size(&self, mission_id: i32) -> Result<i64> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let size = mission_waitlist::table
+            .filter(mission_waitlist::mission_id.eq(mission_id))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+
+        Ok(size)
+    }
+
+    async fn clear_waitlist(&self, mission_id: i32) -> Result<usize> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rows_affected = delete(mission_waitlist::table)
+            .filter(mission_waitlist::mission_id.eq(mission_id))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected)
+    }
+
+    async fn promote_from_waitlist(&self, mission_id: i32) -> Result<Vec<i32>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let promoted = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            // Locking the mission serialises promotions against each other and against edits
+            let mission = missions::table
+                .find(mission_id)
+                .filter(missions::deleted_at.is_null())
+                .for_update()
+                .select((missions::max_members, missions::name, missions::visibility))
+                .first::<(i32, String, String)>(conn)
+                .optional()?;
+            let Some((max_members, mission_name, visibility)) = mission else {
+                return diesel::QueryResult::Ok(Vec::new());
+            };
+            // Anyone else gets in through an invitation or an approved request
+            if visibility != MissionVisibility::Public.to_string() {
+                return diesel::QueryResult::Ok(Vec::new());
+            }
+
+            let member_count = crew_memberships::table
+                .filter(crew_memberships::mission_id.eq(mission_id))
+                .count()
+                .get_result::<i64>(conn)?;
+            let mut free_seats = i64::from(max_members) - member_count;
+            if free_seats <= 0 {
+                return diesel::QueryResult::Ok(Vec::new());
+            }
+
+            let entries = mission_waitlist::table
+                .filter(mission_waitlist::mission_id.eq(mission_id))
+                .order(mission_waitlist::id.asc())
+                .for_update()
+                .select(MissionWaitlistEntity::as_select())
+                .load::<MissionWaitlistEntity>(conn)?;
+
+            let mut promoted = Vec::new();
+            for entry in entries {
+                if free_seats == 0 {
+                    break;
+                }
+
+                delete(mission_waitlist::table.find(entry.id)).execute(conn)?;
+
+                // Brawlers who got in another way (invite, approval) just leave the queue
+                let joined = insert_into(crew_memberships::table)
+                    .values(CrewMemberShips {
+                        mission_id,
+                        brawler_id: entry.brawler_id,
+                        joined_at: Utc::now().naive_utc(),
+                        role: CrewRole::Member.to_string(),
+                    })
+                    .on_conflict((crew_memberships::mission_id, crew_memberships::brawler_id))
+                    .do_nothing()
+                    .execute(conn)?;
+                if joined == 0 {
+                    continue;
+                }
+
+                notify_in(
+                    conn,
+                    vec![AddNotificationEntity {
+                        brawler_id: entry.brawler_id,
+                        kind: NotificationKinds::WaitlistPromoted.to_string(),
+                        mission_id: Some(mission_id),
+                        message: format!(
+                            "A seat opened up in \"{}\" and you are now part of the crew",
+                            mission_name
+                        ),
+                    }],
+                )?;
+
+                free_seats -= 1;
+                promoted.push(entry.brawler_id);
+            }
+
+            diesel::QueryResult::Ok(promoted)
+        })?;
+
+        Ok(promoted)
+    }
 }
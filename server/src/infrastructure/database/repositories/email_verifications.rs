@@ -0,0 +1,117 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use diesel::{insert_into, prelude::*, update};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::email_verifications::{AddEmailVerificationEntity, EmailVerificationEntity},
+        repositories::email_verifications::EmailVerificationRepository,
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        schema::{brawlers, email_verification_tokens},
+    },
+};
+
+pub struct EmailVerificationPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl EmailVerificationPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl EmailVerificationRepository for EmailVerificationPostgres {
+    async fn create(&self, add_email_verification_entity: AddEmailVerificationEntity) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            // Kept rather than deleted, so they still count towards the brawler's limit
+            update(email_verification_tokens::table)
+                .filter(
+                    email_verification_tokens::brawler_id
+                        .eq(add_email_verification_entity.brawler_id),
+                )
+                .filter(email_verification_tokens::used_at.is_null())
+                .set(email_verification_tokens::used_at.eq(Utc::now().naive_utc()))
+                .execute(conn)?;
+
+            insert_into(email_verification_tokens::table)
+                .values(&add_email_verification_entity)
+                .execute(conn)?;
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    async fn count_since(&self, brawler_id: i32, since: NaiveDateTime) -> Result<i64> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let count = email_verification_tokens::table
+            .filter(email_verification_tokens::brawler_id.eq(brawler_id))
+            .filter(email_verification_tokens::created_at.ge(since))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+
+        Ok(count)
+    }
+
+    async fn find_by_token_hash(&self, token_hash: String) -> Result<Option<EmailVerificationEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = email_verification_tokens::table
+            .filter(email_verification_tokens::token_hash.eq(token_hash))
+            .select(EmailVerificationEntity::as_select())
+            .first::<EmailVerificationEntity>(&mut conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn confirm(&self, id: i32) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let confirmed = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let now = Utc::now().naive_utc();
+            let Some((brawler_id, email)) = update(email_verification_tokens::table.find(id))
+                .filter(email_verification_tokens::used_at.is_null())
+                .set(email_verification_tokens::used_at.eq(now))
+                .returning((
+                    email_verification_tokens::brawler_id,
+                    email_verification_tokens::email,
+                ))
+                .get_result::<(i32, String)>(conn)
+                .optional()?
+            else {
+                return Ok(false);
+            };
+
+            // An unverified copy of the address on another account never blocks its owner
+            update(brawlers::table)
+                .filter(brawlers::email.eq(&email))
+                .filter(brawlers::id.ne(brawler_id))
+                .filter(brawlers::email_verified_at.is_null())
+                .set(brawlers::email.eq(None::<String>))
+                .execute(conn)?;
+
+            update(brawlers::table.find(brawler_id))
+                .set((
+                    brawlers::email.eq(Some(email)),
+                    brawlers::email_verified_at.eq(Some(now)),
+                    brawlers::updated_at.eq(now),
+                ))
+                .execute(conn)?;
+
+            Ok(true)
+        })?;
+
+        Ok(confirmed)
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::{pg::PgConnection, prelude::*};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::file_deletions::{AddFileDeletionEntity, FileDeletionEntity},
+        repositories::file_deletions::FileDeletionRepository,
+        value_objects::file_stores::FileStores,
+    },
+    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::file_deletions},
+};
+
+pub struct FileDeletionPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl FileDeletionPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+/// Queues deletions on an open connection, so repositories can do it in the
+/// same transaction that drops the rows pointing at the files.
+pub fn enqueue_in(
+    conn: &mut PgConnection,
+    store: FileStores,
+    storage_keys: Vec<String>,
+) -> QueryResult<usize> {
+    if storage_keys.is_empty() {
+        return QueryResult::Ok(0);
+    }
+
+    let rows: Vec<AddFileDeletionEntity> = storage_keys
+        .into_iter()
+        .map(|storage_key| AddFileDeletionEntity {
+            store: store.to_string(),
+            storage_key,
+        })
+        .collect();
+
+    diesel::insert_into(file_deletions::table)
+        .values(&rows)
+        .on_conflict((file_deletions::store, file_deletions::storage_key))
+        .do_nothing()
+        .execute(conn)
+}
+
+#[async_trait]
+impl FileDeletionRepository for FileDeletionPostgres {
+    async fn enqueue(&self, store: FileStores, storage_keys: Vec<String>) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+        enqueue_in(&mut conn, store, storage_keys)?;
+        Ok(())
+    }
+
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<FileDeletionEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let sql = r#"
+            UPDATE file_deletions
+            SET next_attempt_at = $2
+            WHERE id IN (
+                SELECT id FROM file_deletions
+                WHERE next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+        "#;
+
+        let claimed = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::BigInt, _>(limit)
+            .bind::<diesel::sql_types::Timestamp, _>(Utc::now().naive_utc() + lease)
+            .load::<FileDeletionEntity>(&mut conn)?;
+
+        Ok(claimed)
+    }
+
+    async fn complete(&self, id: i32) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        diesel::delete(file_deletions::table.find(id)).execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn retry_at(&self, id: i32, error: String, next_attempt_at: NaiveDateTime) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        diesel::update(file_deletions::table.find(id))
+            .set((
+                file_deletions::attempts.eq(file_deletions::attempts + 1),
+                file_deletions::last_error.eq(error),
+                file_deletions::next_attempt_at.eq(next_attempt_at),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn queued_keys(&self, store: FileStores) -> Result<Vec<String>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let keys = file_deletions::table
+            .filter(file_deletions::store.eq(store.to_string()))
+            .select(file_deletions::storage_key)
+            .load::<String>(&mut conn)?;
+
+        Ok(keys)
+    }
+}
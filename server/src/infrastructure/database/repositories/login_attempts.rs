@@ -0,0 +1,60 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::{insert_into, prelude::*};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::login_attempts::{AddLoginAttemptEntity, LoginAttemptEntity},
+        repositories::login_attempts::LoginAttemptRepository,
+        value_objects::login_attempt_filter::LoginAttemptFilter,
+    },
+    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::login_attempts},
+};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+pub struct LoginAttemptPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl LoginAttemptPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl LoginAttemptRepository for LoginAttemptPostgres {
+    async fn record(&self, add_login_attempt_entity: AddLoginAttemptEntity) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        insert_into(login_attempts::table)
+            .values(&add_login_attempt_entity)
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn search(&self, filter: &LoginAttemptFilter) -> Result<Vec<LoginAttemptEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let mut query = login_attempts::table.into_boxed();
+
+        if let Some(username) = &filter.username {
+            query = query.filter(login_attempts::username.eq(username.trim().to_lowercase()));
+        }
+        if let Some(ip_address) = &filter.ip_address {
+            query = query.filter(login_attempts::ip_address.eq(ip_address.trim().to_string()));
+        }
+
+        let result = query
+            .order(login_attempts::attempted_at.desc())
+            .limit(filter.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT))
+            .select(LoginAttemptEntity::as_select())
+            .load::<LoginAttemptEntity>(&mut conn)?;
+
+        Ok(result)
+    }
+}
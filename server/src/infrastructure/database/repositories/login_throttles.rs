@@ -0,0 +1,95 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use diesel::{delete, insert_into, prelude::*, update};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::login_attempts::LoginThrottleEntity,
+        services::login_throttle::{FailureRecord, LoginThrottle},
+    },
+    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::login_throttles},
+};
+
+pub struct LoginThrottlePostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl LoginThrottlePostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+impl From<LoginThrottleEntity> for FailureRecord {
+    fn from(entity: LoginThrottleEntity) -> Self {
+        Self {
+            failures: entity.failures.max(0) as u32,
+            last_failure_at: entity.last_failure_at,
+        }
+    }
+}
+
+#[async_trait]
+impl LoginThrottle for LoginThrottlePostgres {
+    async fn get(&self, key: &str) -> Result<Option<FailureRecord>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = login_throttles::table
+            .find(key)
+            .select(LoginThrottleEntity::as_select())
+            .first::<LoginThrottleEntity>(&mut conn)
+            .optional()?;
+
+        Ok(result.map(FailureRecord::from))
+    }
+
+    async fn register_failure(&self, key: &str, window: Duration) -> Result<FailureRecord> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+        let now = Utc::now().naive_utc();
+
+        let record = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            insert_into(login_throttles::table)
+                .values(LoginThrottleEntity {
+                    throttle_key: key.to_string(),
+                    failures: 0,
+                    last_failure_at: now,
+                })
+                .on_conflict(login_throttles::throttle_key)
+                .do_nothing()
+                .execute(conn)?;
+
+            // Lock the row so concurrent failures for the same key all get counted
+            let current = login_throttles::table
+                .find(key)
+                .for_update()
+                .select(LoginThrottleEntity::as_select())
+                .first::<LoginThrottleEntity>(conn)?;
+
+            let failures = if now - current.last_failure_at > window {
+                1
+            } else {
+                current.failures + 1
+            };
+
+            update(login_throttles::table.find(key))
+                .set((
+                    login_throttles::failures.eq(failures),
+                    login_throttles::last_failure_at.eq(now),
+                ))
+                .returning(LoginThrottleEntity::as_returning())
+                .get_result::<LoginThrottleEntity>(conn)
+        })?;
+
+        Ok(record.into())
+    }
+
+    async fn reset(&self, key: &str) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        delete(login_throttles::table.find(key)).execute(&mut conn)?;
+
+        Ok(())
+    }
+}
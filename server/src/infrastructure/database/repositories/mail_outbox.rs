@@ -0,0 +1,137 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::{
+    dsl::{insert_into, update},
+    pg::PgConnection,
+    prelude::*,
+};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::mail_outbox::{AddMailOutboxEntity, MailOutboxEntity},
+        repositories::mail_outbox::MailOutboxRepository,
+        value_objects::email_model::{DigestRecipientModel, MailOutboxStatuses},
+    },
+    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::mail_outbox},
+};
+
+pub struct MailOutboxPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl MailOutboxPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+/// Queues mail on an open connection, so it is only sent if the caller's transaction commits.
+pub fn queue_in(conn: &mut PgConnection, mails: Vec<AddMailOutboxEntity>) -> QueryResult<usize> {
+    if mails.is_empty() {
+        return QueryResult::Ok(0);
+    }
+
+    insert_into(mail_outbox::table).values(&mails).execute(conn)
+}
+
+#[async_trait]
+impl MailOutboxRepository for MailOutboxPostgres {
+    async fn enqueue(&self, mails: Vec<AddMailOutboxEntity>) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+        queue_in(&mut conn, mails)?;
+        Ok(())
+    }
+
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<MailOutboxEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let sql = r#"
+            UPDATE mail_outbox
+            SET next_attempt_at = $3
+            WHERE id IN (
+                SELECT id FROM mail_outbox
+                WHERE status = $1 AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+        "#;
+
+        let claimed = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::Text, _>(MailOutboxStatuses::Pending.to_string())
+            .bind::<diesel::sql_types::BigInt, _>(limit)
+            .bind::<diesel::sql_types::Timestamp, _>(Utc::now().naive_utc() + lease)
+            .load::<MailOutboxEntity>(&mut conn)?;
+
+        Ok(claimed)
+    }
+
+    async fn mark_sent(&self, id: i32) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        update(mail_outbox::table.find(id))
+            .set((
+                mail_outbox::status.eq(MailOutboxStatuses::Sent.to_string()),
+                mail_outbox::attempts.eq(mail_outbox::attempts + 1),
+                mail_outbox::last_error.eq(None::<String>),
+                mail_outbox::sent_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: i32,
+        error: String,
+        next_attempt_at: Option<NaiveDateTime>,
+    ) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let status = match next_attempt_at {
+            Some(_) => MailOutboxStatuses::Pending,
+            None => MailOutboxStatuses::Failed,
+        };
+        update(mail_outbox::table.find(id))
+            .set((
+                mail_outbox::status.eq(status.to_string()),
+                mail_outbox::attempts.eq(mail_outbox::attempts + 1),
+                mail_outbox::last_error.eq(Some(error)),
+                mail_outbox::next_attempt_at
+                    .eq(next_attempt_at.unwrap_or_else(|| Utc::now().naive_utc())),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn claim_digests(&self, cutoff: NaiveDateTime) -> Result<Vec<DigestRecipientModel>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let sql = r#"
+            UPDATE brawlers b
+            SET last_digest_at = $2
+            FROM (
+                SELECT id, last_digest_at FROM brawlers
+                WHERE email_digest
+                  AND email IS NOT NULL
+                  AND email_verified_at IS NOT NULL
+                  AND (last_digest_at IS NULL OR last_digest_at < $1)
+                FOR UPDATE SKIP LOCKED
+            ) previous
+            WHERE b.id = previous.id
+            RETURNING b.id AS brawler_id, b.email, b.display_name, previous.last_digest_at AS since
+        "#;
+
+        let recipients = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::Timestamp, _>(cutoff)
+            .bind::<diesel::sql_types::Timestamp, _>(Utc::now().naive_utc())
+            .load::<DigestRecipientModel>(&mut conn)?;
+
+        Ok(recipients)
+    }
+}
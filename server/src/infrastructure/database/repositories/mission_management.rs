@@ -1,6 +1,6 @@
 use crate::{
     domain::{
-        entities::missions::{AddMissionEntity, EditMissionEntity},
+        entities::missions::{AddMissionEntity, EditMissionEntity, MissionEntity},
         repositories::mission_management::MissionManagementRepository,
         value_objects::mission_statuses::MissionStatuses,
     },
@@ -8,7 +8,10 @@ use crate::{
 };
 use anyhow::{Ok, Result};
 use async_trait::async_trait;
-use diesel::{ExpressionMethods, RunQueryDsl, dsl::now, dsl::update, insert_into};
+use chrono::{NaiveDateTime, Utc};
+use diesel::{
+    ExpressionMethods, RunQueryDsl, SelectableHelper, dsl::now, dsl::update, insert_into,
+};
 use std::sync::Arc;
 
 pub struct MissionManagementPostgres {
@@ -62,11 +65,32 @@ impl MissionManagementRepository for MissionManagementPostgres {
 
         if rows_affected == 0 {
             tracing::warn!("No mission found to remove (id: {}, chief: {})", mission_id, chief_id);
-            return Err(anyhow::anyhow!("Mission not found or you are not the chief").into());
+            return Err(anyhow::anyhow!("Mission not found or you are not the chief"));
         }
 
         tracing::info!("Mission {} soft-deleted. Rows affected: {}", mission_id, rows_affected);
 
         Ok(())
     }
+
+    async fn claim_deadline_reminders(&self, until: NaiveDateTime) -> Result<Vec<MissionEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+        let current = Utc::now().naive_utc();
+
+        // Re-checked under the row lock, so two workers never remind the same crew
+        let missions = update(missions::table)
+            .filter(missions::deleted_at.is_null())
+            .filter(missions::deadline_reminded_at.is_null())
+            .filter(missions::end_date.gt(current))
+            .filter(missions::end_date.le(until))
+            .filter(missions::status.eq_any([
+                MissionStatuses::Open.to_string(),
+                MissionStatuses::InProgress.to_string(),
+            ]))
+            .set(missions::deadline_reminded_at.eq(current))
+            .returning(MissionEntity::as_returning())
+            .get_results::<MissionEntity>(&mut conn)?;
+
+        Ok(missions)
+    }
 }
@@ -2,14 +2,23 @@ use std::sync::Arc;
 
 use anyhow::{Context, Ok, Result};
 use async_trait::async_trait;
-use diesel::{ExpressionMethods, RunQueryDsl, dsl::update};
+use diesel::{
+    Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper,
+    dsl::{now, update},
+    insert_into,
+};
 
 use crate::{
     domain::{
+        entities::mission_status_history::{
+            AddMissionStatusHistoryEntity, MissionStatusHistoryEntity,
+        },
         repositories::mission_operation::MissionOperationRepository,
-        value_objects::mission_statuses::MissionStatuses,
     },
-    infrastructure::database::{postgresql_connection::PgPoolSquad, schema::missions},
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        schema::{mission_status_history, missions},
+    },
 };
 pub struct MissionOperationPostgres {
     db_pool: Arc<PgPoolSquad>,
@@ -19,55 +28,54 @@ impl MissionOperationPostgres {
     pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
         Self { db_pool }
     }
+}
 
-    async fn set_status(
-        &self,
-        mission_id: i32,
-        chief_id: i32,
-        status: MissionStatuses,
-    ) -> Result<i32> {
+#[async_trait]
+impl MissionOperationRepository for MissionOperationPostgres {
+    async fn transition(&self, change: AddMissionStatusHistoryEntity) -> Result<bool> {
         let db_pool = Arc::clone(&self.db_pool);
-        let status_string = status.to_string();
-        let id = tokio::task::spawn_blocking(move || -> Result<i32> {
+        let changed = tokio::task::spawn_blocking(move || -> Result<bool> {
             let mut conn = db_pool.get().context("Failed to get DB connection")?;
 
-            update(missions::table)
-                .filter(missions::id.eq(mission_id))
-                .filter(missions::chief_id.eq(chief_id))
-                .filter(missions::deleted_at.is_null())
-                .set((missions::status.eq(status_string),))
-                .returning(missions::id)
-                .get_result::<i32>(&mut conn)
-                .context("Failed to execute mission update query")
-        })
-        .await??;
+            let changed = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                // Matching on the old status keeps two racing transitions from both winning
+                let rows_affected = update(missions::table)
+                    .filter(missions::id.eq(change.mission_id))
+                    .filter(missions::status.eq(&change.from_status))
+                    .filter(missions::deleted_at.is_null())
+                    .set((
+                        missions::status.eq(&change.to_status),
+                        missions::updated_at.eq(now),
+                    ))
+                    .execute(conn)?;
 
-        Ok(id)
-    }
-}
+                if rows_affected == 0 {
+                    return diesel::QueryResult::Ok(false);
+                }
 
-#[async_trait]
-impl MissionOperationRepository for MissionOperationPostgres {
-    async fn to_progress(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let result = self
-            .set_status(mission_id, chief_id, MissionStatuses::InProgress)
-            .await?;
+                insert_into(mission_status_history::table)
+                    .values(&change)
+                    .execute(conn)?;
 
-        Ok(result)
-    }
+                diesel::QueryResult::Ok(true)
+            })
+            .context("Failed to execute mission transition")?;
 
-    async fn to_completed(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let result = self
-            .set_status(mission_id, chief_id, MissionStatuses::Completed)
-            .await?;
+            Ok(changed)
+        })
+        .await??;
 
-        Ok(result)
+        Ok(changed)
     }
 
-    async fn to_failed(&self, mission_id: i32, chief_id: i32) -> Result<i32> {
-        let result = self
-            .set_status(mission_id, chief_id, MissionStatuses::Failed)
-            .await?;
+    async fn status_history(&self, mission_id: i32) -> Result<Vec<MissionStatusHistoryEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = mission_status_history::table
+            .filter(mission_status_history::mission_id.eq(mission_id))
+            .order(mission_status_history::changed_at.asc())
+            .select(MissionStatusHistoryEntity::as_select())
+            .load::<MissionStatusHistoryEntity>(&mut conn)?;
 
         Ok(result)
     }
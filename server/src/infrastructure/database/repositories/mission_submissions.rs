@@ -1,8 +1,13 @@
-use crate::domain::entities::mission_submissions::{MissionSubmission, NewMissionSubmission};
+use crate::domain::entities::mission_submissions::{
+    MissionSubmission, NewMissionSubmission, ReviewMissionSubmission,
+};
 use crate::domain::repositories::mission_submissions::MissionSubmissionsRepository;
 use crate::domain::value_objects::mission_submission_model::MissionSubmissionModel;
+use crate::domain::value_objects::file_stores::FileStores;
+use crate::domain::value_objects::submission_review_model::SubmissionReviewStatuses;
 use crate::infrastructure::database::postgresql_connection::PgPoolSquad;
-use crate::infrastructure::database::schema::mission_submissions;
+use crate::infrastructure::database::repositories::file_deletions::enqueue_in;
+use crate::infrastructure::database::schema::{mission_submissions, tasks};
 use anyhow::Result;
 use async_trait::async_trait;
 use diesel::prelude::*;
@@ -23,10 +28,41 @@ impl MissionSubmissionsRepository for MissionSubmissionsPostgres {
     async fn create(&self, new_submission: NewMissionSubmission<'_>) -> Result<MissionSubmission> {
         let mut conn = self.pool.get()?;
 
-        let result = diesel::insert_into(mission_submissions::table)
-            .values(&new_submission)
-            .get_result(&mut conn)?;
-        
+        let Some(task_id) = new_submission.task_id else {
+            let result = diesel::insert_into(mission_submissions::table)
+                .values(&new_submission)
+                .get_result(&mut conn)?;
+
+            return Ok(result);
+        };
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            // Locking the task keeps two uploads from claiming the same version number
+            tasks::table
+                .find(task_id)
+                .for_update()
+                .select(tasks::id)
+                .first::<i32>(conn)?;
+
+            let latest = mission_submissions::table
+                .filter(mission_submissions::task_id.eq(task_id))
+                .select(diesel::dsl::max(mission_submissions::version))
+                .first::<Option<i32>>(conn)?;
+
+            diesel::update(mission_submissions::table)
+                .filter(mission_submissions::task_id.eq(task_id))
+                .filter(mission_submissions::is_current.eq(true))
+                .set(mission_submissions::is_current.eq(false))
+                .execute(conn)?;
+
+            diesel::insert_into(mission_submissions::table)
+                .values((
+                    &new_submission,
+                    mission_submissions::version.eq(latest.unwrap_or(0) + 1),
+                ))
+                .get_result(conn)
+        })?;
+
         Ok(result)
     }
 
@@ -38,6 +74,7 @@ impl MissionSubmissionsRepository for MissionSubmissionsPostgres {
             FROM mission_submissions ms
             JOIN brawlers b ON ms.brawler_id = b.id
             WHERE ms.mission_id = $1
+              AND (ms.task_id IS NULL OR ms.is_current)
             ORDER BY ms.submitted_at DESC
         "#;
 
@@ -55,7 +92,7 @@ impl MissionSubmissionsRepository for MissionSubmissionsPostgres {
             SELECT ms.*, b.display_name as brawler_name, b.avatar_url as brawler_avatar_url
             FROM mission_submissions ms
             JOIN brawlers b ON ms.brawler_id = b.id
-            WHERE ms.task_id = $1
+            WHERE ms.task_id = $1 AND ms.is_current
             LIMIT 1
         "#;
 
@@ -70,12 +107,18 @@ impl MissionSubmissionsRepository for MissionSubmissionsPostgres {
     async fn delete_all_by_member(&self, mission_id: i32, brawler_id: i32) -> Result<()> {
         let mut conn = self.pool.get()?;
 
-        diesel::delete(
-            mission_submissions::table
-                .filter(mission_submissions::mission_id.eq(mission_id))
-                .filter(mission_submissions::brawler_id.eq(brawler_id)),
-        )
-        .execute(&mut conn)?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let keys = diesel::delete(
+                mission_submissions::table
+                    .filter(mission_submissions::mission_id.eq(mission_id))
+                    .filter(mission_submissions::brawler_id.eq(brawler_id)),
+            )
+            .returning(mission_submissions::storage_key)
+            .get_results::<Option<String>>(conn)?;
+
+            enqueue_in(conn, FileStores::Files, keys.into_iter().flatten().collect())?;
+            Ok(())
+        })?;
 
         Ok(())
     }
@@ -83,15 +126,73 @@ impl MissionSubmissionsRepository for MissionSubmissionsPostgres {
     async fn delete_all_by_task(&self, task_id: i32) -> Result<()> {
         let mut conn = self.pool.get()?;
 
-        diesel::delete(
-            mission_submissions::table
-                .filter(mission_submissions::task_id.eq(task_id)),
-        )
-        .execute(&mut conn)?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let keys = diesel::delete(
+                mission_submissions::table
+                    .filter(mission_submissions::task_id.eq(task_id)),
+            )
+            .returning(mission_submissions::storage_key)
+            .get_results::<Option<String>>(conn)?;
+
+            enqueue_in(conn, FileStores::Files, keys.into_iter().flatten().collect())?;
+            Ok(())
+        })?;
 
         Ok(())
     }
 
+    async fn get_versions(&self, task_id: i32) -> Result<Vec<MissionSubmissionModel>> {
+        let mut conn = self.pool.get()?;
+
+        let sql = r#"
+            SELECT ms.*, b.display_name as brawler_name, b.avatar_url as brawler_avatar_url
+            FROM mission_submissions ms
+            JOIN brawlers b ON ms.brawler_id = b.id
+            WHERE ms.task_id = $1
+            ORDER BY ms.version ASC
+        "#;
+
+        let results = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::Int4, _>(task_id)
+            .load::<MissionSubmissionModel>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    async fn get_version(&self, task_id: i32, version: i32) -> Result<Option<MissionSubmissionModel>> {
+        let mut conn = self.pool.get()?;
+
+        let sql = r#"
+            SELECT ms.*, b.display_name as brawler_name, b.avatar_url as brawler_avatar_url
+            FROM mission_submissions ms
+            JOIN brawlers b ON ms.brawler_id = b.id
+            WHERE ms.task_id = $1 AND ms.version = $2
+            LIMIT 1
+        "#;
+
+        let result = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::Int4, _>(task_id)
+            .bind::<diesel::sql_types::Int4, _>(version)
+            .get_result::<MissionSubmissionModel>(&mut conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn withdraw(&self, id: i32) -> Result<bool> {
+        let mut conn = self.pool.get()?;
+
+        let rows_affected = diesel::update(mission_submissions::table.find(id))
+            .filter(mission_submissions::is_current.eq(true))
+            .set((
+                mission_submissions::is_current.eq(false),
+                mission_submissions::withdrawn_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
+
     async fn get_by_id(&self, id: i32) -> Result<Option<MissionSubmissionModel>> {
         let mut conn = self.pool.get()?;
 
@@ -124,8 +225,57 @@ impl MissionSubmissionsRepository for MissionSubmissionsPostgres {
     async fn delete(&self, id: i32) -> Result<()> {
         let mut conn = self.pool.get()?;
 
-        diesel::delete(mission_submissions::table.find(id)).execute(&mut conn)?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let keys = diesel::delete(mission_submissions::table.find(id))
+                .returning(mission_submissions::storage_key)
+                .get_results::<Option<String>>(conn)?;
+
+            enqueue_in(conn, FileStores::Files, keys.into_iter().flatten().collect())?;
+            Ok(())
+        })?;
 
         Ok(())
     }
+
+    async fn referenced_storage_keys(&self) -> Result<Vec<String>> {
+        let mut conn = self.pool.get()?;
+
+        let keys = mission_submissions::table
+            .filter(mission_submissions::storage_key.is_not_null())
+            .select(mission_submissions::storage_key.assume_not_null())
+            .load::<String>(&mut conn)?;
+
+        Ok(keys)
+    }
+
+    async fn get_pending_review(&self, mission_id: i32) -> Result<Vec<MissionSubmissionModel>> {
+        let mut conn = self.pool.get()?;
+
+        let sql = r#"
+            SELECT ms.*, b.display_name as brawler_name, b.avatar_url as brawler_avatar_url
+            FROM mission_submissions ms
+            JOIN brawlers b ON ms.brawler_id = b.id
+            WHERE ms.mission_id = $1 AND ms.review_status = $2
+              AND (ms.task_id IS NULL OR ms.is_current)
+            ORDER BY ms.submitted_at ASC
+        "#;
+
+        let results = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::Int4, _>(mission_id)
+            .bind::<diesel::sql_types::Text, _>(SubmissionReviewStatuses::Pending.to_string())
+            .load::<MissionSubmissionModel>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    async fn review(&self, id: i32, review: ReviewMissionSubmission) -> Result<bool> {
+        let mut conn = self.pool.get()?;
+
+        let rows_affected = diesel::update(mission_submissions::table.find(id))
+            .filter(mission_submissions::review_status.eq(SubmissionReviewStatuses::Pending.to_string()))
+            .set(&review)
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
 }
@@ -2,12 +2,20 @@ use std::sync::Arc;
 
 use anyhow::{Ok, Result};
 use async_trait::async_trait;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use diesel::{
+    ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl,
+    pg::Pg,
+    query_builder::{BoxedSqlQuery, SqlQuery},
+};
 
 use crate::{
     domain::{
         repositories::mission_viewing::MissionViewingRepository,
-        value_objects::{brawler_model::BrawlerModel, mission_filter::MissionFilter, mission_model::MissionModel},
+        value_objects::{
+            brawler_model::BrawlerModel,
+            mission_filter::{MissionCursor, MissionFilter, MissionSort},
+            mission_model::{KeyedMissionModel, MissionModel},
+        },
     },
     infrastructure::database::{
         postgresql_connection::PgPoolSquad,
@@ -45,7 +53,7 @@ impl MissionViewingRepository for MissionViewingPostgres {
             SELECT m.id, m.name, m.description, m.status, m.chief_id, 
                    b.display_name AS chief_display_name,
                    (SELECT COUNT(*) FROM crew_memberships cm WHERE cm.mission_id = m.id) AS member_count,
-                   m.max_members,
+                   m.max_members, m.visibility, m.max_upload_mb, m.allowed_file_types, m.end_date, m.tags,
                    m.created_at, m.updated_at,
                    EXISTS (SELECT 1 FROM crew_memberships cm2 WHERE cm2.mission_id = m.id AND cm2.brawler_id = $2) AS is_joined
             FROM missions m
@@ -62,39 +70,71 @@ impl MissionViewingRepository for MissionViewingPostgres {
         Ok(result)
     }
 
-    async fn gets(&self, filter: &MissionFilter, user_id: Option<i32>) -> Result<Vec<MissionModel>> {
-        use diesel::sql_types::{Nullable, Varchar, Int4};
+    async fn gets(
+        &self,
+        filter: &MissionFilter,
+        cursor: Option<MissionCursor>,
+        limit: i64,
+        user_id: Option<i32>,
+    ) -> Result<Vec<KeyedMissionModel>> {
+        use diesel::sql_types::{BigInt, Int4, Nullable};
 
         let mut conn = Arc::clone(&self.db_pool).get()?;
 
-        let sql = r#"
-            SELECT m.id, m.name, m.description, m.status, m.chief_id, 
-                   b.display_name AS chief_display_name,
-                   (SELECT COUNT(*) FROM crew_memberships cm WHERE cm.mission_id = m.id) AS member_count,
-                   m.max_members,
-                   m.created_at, m.updated_at,
-                   EXISTS (SELECT 1 FROM crew_memberships cm2 WHERE cm2.mission_id = m.id AND cm2.brawler_id = $3) AS is_joined
-            FROM missions m
-            INNER JOIN brawlers b ON b.id = m.chief_id
-            WHERE m.deleted_at IS NULL
-              AND ($1 IS NULL OR m.status = $1)
-              AND ($2 IS NULL OR m.name ILIKE $2)
-            ORDER BY m.created_at DESC
-        "#;
-
-        // Prepare optional bind values
-        let status_bind: Option<String> = filter.status.as_ref().map(|s| s.to_string());
-        let name_bind: Option<String> = filter.name.as_ref().map(|n| format!("%{}%", n));
+        let (order_column, cursor_value, direction, comparison) = match filter.sort {
+            // Compares on the column itself so the listing index can serve the default order
+            MissionSort::Newest => (
+                "created_at",
+                "TIMESTAMP 'epoch' + $13 * INTERVAL '1 microsecond'",
+                "DESC",
+                "<",
+            ),
+            MissionSort::MostMembers | MissionSort::FreeSlots => ("sort_key", "$13", "DESC", "<"),
+            MissionSort::EndingSoon => ("sort_key", "$13", "ASC", ">"),
+        };
+        let sql = format!(
+            r#"
+            {listing}
+            SELECT * FROM keyed
+            WHERE $13::BIGINT IS NULL OR ({order_column}, id) {comparison} ({cursor_value}, $14)
+            ORDER BY {order_column} {direction}, id {direction}
+            LIMIT $15
+            "#,
+            listing = listing_sql(filter.sort),
+        );
 
-        let rows = diesel::sql_query(sql)
-            .bind::<Nullable<Varchar>, _>(status_bind)
-            .bind::<Nullable<Varchar>, _>(name_bind)
-            .bind::<Nullable<Int4>, _>(user_id)
-            .load::<MissionModel>(&mut conn)?;
+        let rows = bind_filter(diesel::sql_query(sql).into_boxed(), filter, user_id)
+            .bind::<Nullable<BigInt>, _>(cursor.map(|cursor| cursor.sort_key))
+            .bind::<Nullable<Int4>, _>(cursor.map(|cursor| cursor.id))
+            .bind::<BigInt, _>(limit)
+            .load::<KeyedMissionModel>(&mut conn)?;
 
         Ok(rows)
     }
 
+    async fn count(&self, filter: &MissionFilter, user_id: Option<i32>) -> Result<i64> {
+        use diesel::sql_types::BigInt;
+
+        #[derive(diesel::QueryableByName)]
+        struct CountResult {
+            #[diesel(sql_type = BigInt)]
+            count: i64,
+        }
+
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let sql = format!(
+            "{} SELECT COUNT(*) AS count FROM keyed",
+            listing_sql(filter.sort)
+        );
+
+        let total = bind_filter(diesel::sql_query(sql).into_boxed(), filter, user_id)
+            .get_result::<CountResult>(&mut conn)?
+            .count;
+
+        Ok(total)
+    }
+
     async fn get_mission_crew(&self, mission_id: i32) -> Result<Vec<BrawlerModel>> {
         let sql = r#"
             SELECT b.id,
@@ -110,7 +150,7 @@ impl MissionViewingRepository for MissionViewingPostgres {
                 SELECT cm2.brawler_id, COUNT(*) AS success_count
                 FROM crew_memberships cm2
                 INNER JOIN missions m2 ON m2.id = cm2.mission_id
-                WHERE m2.status = 'success'
+                WHERE m2.status = 'Completed'
                 GROUP BY cm2.brawler_id
             ) s ON s.brawler_id = b.id
             LEFT JOIN (
@@ -136,7 +176,7 @@ impl MissionViewingRepository for MissionViewingPostgres {
             SELECT m.id, m.name, m.description, m.status, m.chief_id,
                    b.display_name AS chief_display_name,
                    (SELECT COUNT(*) FROM crew_memberships cm WHERE cm.mission_id = m.id) AS member_count,
-                   m.max_members,
+                   m.max_members, m.visibility, m.max_upload_mb, m.allowed_file_types, m.end_date, m.tags,
                    m.created_at, m.updated_at,
                    true AS is_joined
             FROM crew_memberships cm
@@ -154,4 +194,91 @@ impl MissionViewingRepository for MissionViewingPostgres {
 
         Ok(missions)
     }
+
+    async fn member_role(&self, mission_id: i32, brawler_id: i32) -> Result<Option<String>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let role = crew_memberships::table
+            .filter(crew_memberships::mission_id.eq(mission_id))
+            .filter(crew_memberships::brawler_id.eq(brawler_id))
+            .select(crew_memberships::role)
+            .first::<String>(&mut conn)
+            .optional()?;
+
+        Ok(role)
+    }
+}
+
+/// Missions visible to `user_id` that pass every filter, as `keyed` with the
+/// sort key of `sort`. Expects the binds of `bind_filter`.
+fn listing_sql(sort: MissionSort) -> String {
+    let sort_key = match sort {
+        MissionSort::Newest => "(EXTRACT(EPOCH FROM created_at) * 1000000)::BIGINT",
+        MissionSort::MostMembers => "member_count",
+        MissionSort::FreeSlots => "(max_members - member_count)::BIGINT",
+        // Missions without a deadline come last
+        MissionSort::EndingSoon => {
+            "COALESCE((EXTRACT(EPOCH FROM end_date) * 1000000)::BIGINT, 9223372036854775807)"
+        }
+    };
+
+    format!(
+        r#"
+        WITH listed AS (
+            SELECT m.id, m.name, m.description, m.status, m.chief_id,
+                   b.display_name AS chief_display_name,
+                   (SELECT COUNT(*) FROM crew_memberships cm WHERE cm.mission_id = m.id) AS member_count,
+                   m.max_members, m.visibility, m.max_upload_mb, m.allowed_file_types, m.end_date, m.tags,
+                   m.created_at, m.updated_at,
+                   EXISTS (SELECT 1 FROM crew_memberships cm2 WHERE cm2.mission_id = m.id AND cm2.brawler_id = $3) AS is_joined
+            FROM missions m
+            INNER JOIN brawlers b ON b.id = m.chief_id
+            WHERE m.deleted_at IS NULL
+              AND ($1 IS NULL OR m.status = $1)
+              AND ($2 IS NULL OR m.name ILIKE $2)
+              AND (m.visibility <> 'InviteOnly'
+                   OR m.chief_id = $3
+                   OR EXISTS (SELECT 1 FROM crew_memberships cm3 WHERE cm3.mission_id = m.id AND cm3.brawler_id = $3))
+              AND ($4 IS NULL OR m.chief_id = $4)
+              AND ($8 IS NULL OR m.created_at >= $8)
+              AND ($9 IS NULL OR m.created_at < $9)
+              AND ($10 IS NULL OR m.end_date >= $10)
+              AND ($11 IS NULL OR m.end_date < $11)
+              AND ($12 IS NULL OR m.tags @> $12)
+        ), keyed AS (
+            SELECT listed.*, {sort_key} AS sort_key
+            FROM listed
+            WHERE ($5 IS NULL OR member_count >= $5)
+              AND ($6 IS NULL OR member_count <= $6)
+              AND ($7 IS NULL OR (member_count < max_members) = $7)
+        )
+        "#
+    )
+}
+
+fn bind_filter<'a>(
+    query: BoxedSqlQuery<'a, Pg, SqlQuery>,
+    filter: &MissionFilter,
+    user_id: Option<i32>,
+) -> BoxedSqlQuery<'a, Pg, SqlQuery> {
+    use diesel::sql_types::{Array, BigInt, Bool, Int4, Nullable, Text, Timestamp, Varchar};
+
+    let status_bind: Option<String> = filter.status.as_ref().map(|s| s.to_string());
+    let name_bind: Option<String> = filter.name.as_ref().map(|n| format!("%{}%", n));
+    let tags = filter.tag_list();
+    let tags_bind: Option<Vec<String>> = (!tags.is_empty()).then_some(tags);
+
+    query
+        .bind::<Nullable<Varchar>, _>(status_bind)
+        .bind::<Nullable<Varchar>, _>(name_bind)
+        .bind::<Nullable<Int4>, _>(user_id)
+        .bind::<Nullable<Int4>, _>(filter.chief_id)
+        .bind::<Nullable<BigInt>, _>(filter.min_member_count)
+        .bind::<Nullable<BigInt>, _>(filter.max_member_count)
+        .bind::<Nullable<Bool>, _>(filter.has_free_slots)
+        .bind::<Nullable<Timestamp>, _>(filter.created_after)
+        .bind::<Nullable<Timestamp>, _>(filter.created_before)
+        .bind::<Nullable<Timestamp>, _>(filter.ends_after)
+        .bind::<Nullable<Timestamp>, _>(filter.ends_before)
+        .bind::<Nullable<Array<Text>>, _>(tags_bind)
 }
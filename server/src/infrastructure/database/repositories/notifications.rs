@@ -0,0 +1,240 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::{
+    dsl::{insert_into, update},
+    pg::PgConnection,
+    prelude::*,
+    upsert::excluded,
+};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    domain::{
+        entities::{
+            mail_outbox::AddMailOutboxEntity,
+            notifications::{
+                AddNotificationEntity, NotificationEntity, NotificationPreferenceEntity,
+            },
+        },
+        repositories::notifications::NotificationRepository,
+        value_objects::{
+            email_model::notification_mail_body, notification_kinds::NotificationKinds,
+        },
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        repositories::mail_outbox::queue_in,
+        schema::{brawlers, notification_preferences, notifications},
+    },
+};
+
+pub struct NotificationPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl NotificationPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+/// Inserts notices on an open connection, skipping the kinds each recipient
+/// switched off, so repositories can notify in the transaction that caused it.
+/// Kinds the recipient gets by mail are queued for their verified address as well.
+pub fn notify_in(conn: &mut PgConnection, notices: Vec<AddNotificationEntity>) -> QueryResult<usize> {
+    if notices.is_empty() {
+        return QueryResult::Ok(0);
+    }
+
+    let recipients: Vec<i32> = notices.iter().map(|notice| notice.brawler_id).collect();
+    let preferences: HashMap<(i32, String), (bool, Option<bool>)> = notification_preferences::table
+        .filter(notification_preferences::brawler_id.eq_any(&recipients))
+        .select((
+            notification_preferences::brawler_id,
+            notification_preferences::kind,
+            notification_preferences::enabled,
+            notification_preferences::email_enabled,
+        ))
+        .load::<(i32, String, bool, Option<bool>)>(conn)?
+        .into_iter()
+        .map(|(brawler_id, kind, enabled, email_enabled)| ((brawler_id, kind), (enabled, email_enabled)))
+        .collect();
+
+    let mailed: Vec<&AddNotificationEntity> = notices
+        .iter()
+        .filter(|notice| {
+            let (enabled, email_enabled) = preferences
+                .get(&(notice.brawler_id, notice.kind.clone()))
+                .copied()
+                .unwrap_or((true, None));
+            notice
+                .kind
+                .parse::<NotificationKinds>()
+                .is_ok_and(|kind| kind.emails(enabled, email_enabled))
+        })
+        .collect();
+    if !mailed.is_empty() {
+        let addresses: HashMap<i32, (String, String)> = brawlers::table
+            .filter(brawlers::id.eq_any(&recipients))
+            .filter(brawlers::email_verified_at.is_not_null())
+            .filter(brawlers::email.is_not_null())
+            .select((brawlers::id, brawlers::email.assume_not_null(), brawlers::display_name))
+            .load::<(i32, String, String)>(conn)?
+            .into_iter()
+            .map(|(id, email, display_name)| (id, (email, display_name)))
+            .collect();
+
+        let mails = mailed
+            .into_iter()
+            .filter_map(|notice| {
+                let (email, display_name) = addresses.get(&notice.brawler_id)?;
+                let subject = notice
+                    .kind
+                    .parse::<NotificationKinds>()
+                    .map(|kind| kind.email_subject())
+                    .unwrap_or("New notification");
+                Some(AddMailOutboxEntity {
+                    brawler_id: Some(notice.brawler_id),
+                    to_address: email.clone(),
+                    subject: subject.to_string(),
+                    body: notification_mail_body(display_name, &notice.message),
+                })
+            })
+            .collect();
+        queue_in(conn, mails)?;
+    }
+
+    let notices: Vec<AddNotificationEntity> = notices
+        .into_iter()
+        .filter(|notice| {
+            preferences
+                .get(&(notice.brawler_id, notice.kind.clone()))
+                .is_none_or(|(enabled, _)| *enabled)
+        })
+        .collect();
+    if notices.is_empty() {
+        return QueryResult::Ok(0);
+    }
+
+    insert_into(notifications::table)
+        .values(&notices)
+        .execute(conn)
+}
+
+#[async_trait]
+impl NotificationRepository for NotificationPostgres {
+    async fn notify(&self, notices: Vec<AddNotificationEntity>) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+        notify_in(&mut conn, notices)?;
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        brawler_id: i32,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<NotificationEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let mut query = notifications::table
+            .filter(notifications::brawler_id.eq(brawler_id))
+            .into_boxed();
+        if unread_only {
+            query = query.filter(notifications::read_at.is_null());
+        }
+
+        let results = query
+            .order((notifications::created_at.desc(), notifications::id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .select(NotificationEntity::as_select())
+            .load::<NotificationEntity>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    async fn count(&self, brawler_id: i32, unread_only: bool) -> Result<i64> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let mut query = notifications::table
+            .filter(notifications::brawler_id.eq(brawler_id))
+            .into_boxed();
+        if unread_only {
+            query = query.filter(notifications::read_at.is_null());
+        }
+
+        let count = query.count().get_result::<i64>(&mut conn)?;
+
+        Ok(count)
+    }
+
+    async fn set_read(
+        &self,
+        id: i32,
+        brawler_id: i32,
+        read: bool,
+    ) -> Result<Option<NotificationEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let read_at = read.then(|| Utc::now().naive_utc());
+        let result = update(notifications::table.find(id))
+            .filter(notifications::brawler_id.eq(brawler_id))
+            .set(notifications::read_at.eq(read_at))
+            .returning(NotificationEntity::as_returning())
+            .get_result::<NotificationEntity>(&mut conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn mark_all_read(&self, brawler_id: i32) -> Result<usize> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let marked = update(notifications::table)
+            .filter(notifications::brawler_id.eq(brawler_id))
+            .filter(notifications::read_at.is_null())
+            .set(notifications::read_at.eq(Utc::now().naive_utc()))
+            .execute(&mut conn)?;
+
+        Ok(marked)
+    }
+
+    async fn preferences(&self, brawler_id: i32) -> Result<Vec<NotificationPreferenceEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let results = notification_preferences::table
+            .filter(notification_preferences::brawler_id.eq(brawler_id))
+            .select(NotificationPreferenceEntity::as_select())
+            .load::<NotificationPreferenceEntity>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    async fn set_preferences(&self, preferences: Vec<NotificationPreferenceEntity>) -> Result<()> {
+        if preferences.is_empty() {
+            return Ok(());
+        }
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        insert_into(notification_preferences::table)
+            .values(&preferences)
+            .on_conflict((
+                notification_preferences::brawler_id,
+                notification_preferences::kind,
+            ))
+            .do_update()
+            .set((
+                notification_preferences::enabled.eq(excluded(notification_preferences::enabled)),
+                notification_preferences::updated_at
+                    .eq(excluded(notification_preferences::updated_at)),
+                notification_preferences::email_enabled
+                    .eq(excluded(notification_preferences::email_enabled)),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+}
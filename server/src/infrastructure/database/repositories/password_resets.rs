@@ -0,0 +1,72 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::{dsl::now, insert_into, prelude::*, update};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::password_resets::{AddPasswordResetEntity, PasswordResetEntity},
+        repositories::password_resets::PasswordResetRepository,
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad, schema::password_reset_tokens,
+    },
+};
+
+pub struct PasswordResetPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl PasswordResetPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl PasswordResetRepository for PasswordResetPostgres {
+    async fn create(&self, add_password_reset_entity: AddPasswordResetEntity) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::delete(
+                password_reset_tokens::table
+                    .filter(password_reset_tokens::brawler_id.eq(add_password_reset_entity.brawler_id))
+                    .filter(password_reset_tokens::used_at.is_null()),
+            )
+            .execute(conn)?;
+
+            insert_into(password_reset_tokens::table)
+                .values(&add_password_reset_entity)
+                .execute(conn)?;
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    async fn find_by_token_hash(&self, token_hash: String) -> Result<Option<PasswordResetEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = password_reset_tokens::table
+            .filter(password_reset_tokens::token_hash.eq(token_hash))
+            .select(PasswordResetEntity::as_select())
+            .first::<PasswordResetEntity>(&mut conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn consume(&self, id: i32) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rows_affected = update(password_reset_tokens::table)
+            .filter(password_reset_tokens::id.eq(id))
+            .filter(password_reset_tokens::used_at.is_null())
+            .set(password_reset_tokens::used_at.eq(now))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::RunQueryDsl;
+
+use crate::{
+    domain::{
+        repositories::search::SearchRepository,
+        value_objects::search_model::{SearchHitModel, SearchKinds},
+    },
+    infrastructure::database::postgresql_connection::PgPoolSquad,
+};
+
+/// Matches of `$1` among the kinds in `$3` that brawler `$2` may see: missions
+/// as the public listing shows them, tasks and submissions only to the crew.
+const HITS_SQL: &str = r#"
+    WITH query AS (
+        SELECT websearch_to_tsquery('english', $1) AS q
+    ), workspaces AS (
+        SELECT m.id, m.name
+        FROM missions m
+        WHERE m.deleted_at IS NULL
+          AND (m.chief_id = $2
+               OR EXISTS (SELECT 1 FROM crew_memberships cm WHERE cm.mission_id = m.id AND cm.brawler_id = $2))
+    ), hits AS (
+        SELECT 'Mission'::VARCHAR AS kind, m.id, m.id AS mission_id, m.name AS mission_name,
+               m.name::TEXT AS title, COALESCE(m.description, '') AS body,
+               ts_rank_cd(m.search_vector, query.q) AS rank, m.created_at
+        FROM missions m, query
+        WHERE 'Mission' = ANY($3)
+          AND m.search_vector @@ query.q
+          AND m.deleted_at IS NULL
+          AND (m.visibility <> 'InviteOnly' OR m.id IN (SELECT id FROM workspaces))
+
+        UNION ALL
+
+        SELECT 'Task'::VARCHAR, t.id, t.mission_id, w.name,
+               t.title::TEXT, COALESCE(t.description, ''),
+               ts_rank_cd(t.search_vector, query.q), t.created_at
+        FROM tasks t
+        INNER JOIN workspaces w ON w.id = t.mission_id, query
+        WHERE 'Task' = ANY($3)
+          AND t.search_vector @@ query.q
+
+        UNION ALL
+
+        SELECT 'Submission'::VARCHAR, ms.id, ms.mission_id, w.name,
+               ms.file_name, COALESCE(ms.description, ''),
+               ts_rank_cd(ms.search_vector, query.q), ms.submitted_at AT TIME ZONE 'UTC'
+        FROM mission_submissions ms
+        INNER JOIN workspaces w ON w.id = ms.mission_id, query
+        WHERE 'Submission' = ANY($3)
+          AND ms.search_vector @@ query.q
+          AND (ms.task_id IS NULL OR ms.is_current)
+    )
+"#;
+
+pub struct SearchPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl SearchPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+fn kind_binds(kinds: &[SearchKinds]) -> Vec<String> {
+    kinds.iter().map(|kind| kind.to_string()).collect()
+}
+
+#[async_trait]
+impl SearchRepository for SearchPostgres {
+    async fn search(
+        &self,
+        brawler_id: i32,
+        query: &str,
+        kinds: &[SearchKinds],
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SearchHitModel>> {
+        use diesel::sql_types::{Array, BigInt, Int4, Text};
+
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        // Headlines are costly, so they are only built for the rows of the page
+        let sql = format!(
+            r#"
+            {HITS_SQL},
+            page AS (
+                SELECT * FROM hits
+                ORDER BY rank DESC, created_at DESC, kind, id
+                LIMIT $4 OFFSET $5
+            )
+            SELECT page.kind, page.id, page.mission_id, page.mission_name, page.title, page.rank, page.created_at,
+                   ts_headline('english', CASE WHEN page.body = '' THEN page.title ELSE page.body END, query.q,
+                               'StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2') AS snippet
+            FROM page, query
+            ORDER BY page.rank DESC, page.created_at DESC, page.kind, page.id
+            "#
+        );
+
+        let hits = diesel::sql_query(sql)
+            .bind::<Text, _>(query)
+            .bind::<Int4, _>(brawler_id)
+            .bind::<Array<Text>, _>(kind_binds(kinds))
+            .bind::<BigInt, _>(limit)
+            .bind::<BigInt, _>(offset)
+            .load::<SearchHitModel>(&mut conn)?;
+
+        Ok(hits)
+    }
+
+    async fn count(&self, brawler_id: i32, query: &str, kinds: &[SearchKinds]) -> Result<i64> {
+        use diesel::sql_types::{Array, BigInt, Int4, Text};
+
+        #[derive(diesel::QueryableByName)]
+        struct CountResult {
+            #[diesel(sql_type = BigInt)]
+            count: i64,
+        }
+
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let sql = format!("{HITS_SQL} SELECT COUNT(*) AS count FROM hits");
+
+        let total = diesel::sql_query(sql)
+            .bind::<Text, _>(query)
+            .bind::<Int4, _>(brawler_id)
+            .bind::<Array<Text>, _>(kind_binds(kinds))
+            .get_result::<CountResult>(&mut conn)?
+            .count;
+
+        Ok(total)
+    }
+}
@@ -1,10 +1,16 @@
 use crate::domain::{
-    entities::tasks::{CreateTaskEntity, UpdateTaskEntity},
+    entities::{
+        task_dependencies::{AddTaskDependencyEntity, TaskDependencyEntity},
+        tasks::{CreateTaskEntity, UpdateTaskEntity},
+    },
     repositories::tasks::TaskRepository,
-    value_objects::task_model::TaskModel,
+    value_objects::{
+        task_model::{TaskModel, TaskNodeModel},
+        task_statuses::TaskStatuses,
+    },
 };
 use crate::infrastructure::database::postgresql_connection::PgPoolSquad;
-use crate::infrastructure::database::schema::tasks;
+use crate::infrastructure::database::schema::{missions, task_dependencies, tasks};
 use anyhow::Result;
 use async_trait::async_trait;
 use diesel::prelude::*;
@@ -44,12 +50,51 @@ impl TaskRepository for TaskPostgres {
         Ok(())
     }
 
-    async fn get_by_mission_id(&self, mission_id: i32) -> Result<Vec<TaskModel>> {
+    async fn get_by_mission_id(&self, mission_id: i32) -> Result<Vec<TaskNodeModel>> {
         let mut conn = self.db_pool.get()?;
         let result = tasks::table
             .filter(tasks::mission_id.eq(mission_id))
             .order(tasks::created_at.desc())
             .load::<TaskModel>(&mut conn)?;
+
+        let dependencies = task_dependencies::table
+            .inner_join(tasks::table.on(tasks::id.eq(task_dependencies::task_id)))
+            .filter(tasks::mission_id.eq(mission_id))
+            .select(TaskDependencyEntity::as_select())
+            .load::<TaskDependencyEntity>(&mut conn)?;
+
+        let done = TaskStatuses::Done.to_string();
+        let nodes = result
+            .iter()
+            .map(|task| {
+                let subtasks = result
+                    .iter()
+                    .filter(|subtask| subtask.parent_task_id == Some(task.id));
+                TaskNodeModel {
+                    task: task.clone(),
+                    blocked_by: dependencies
+                        .iter()
+                        .filter(|dependency| dependency.task_id == task.id)
+                        .map(|dependency| dependency.blocked_by_task_id)
+                        .collect(),
+                    subtask_count: subtasks.clone().count(),
+                    subtasks_done: subtasks.filter(|subtask| subtask.status == done).count(),
+                }
+            })
+            .collect();
+        Ok(nodes)
+    }
+
+    async fn claim(&self, task_id: i32, member_id: i32) -> Result<Option<TaskModel>> {
+        let mut conn = self.db_pool.get()?;
+        let result = diesel::update(tasks::table.find(task_id))
+            .filter(tasks::member_id.is_null())
+            .set((
+                tasks::member_id.eq(member_id),
+                tasks::updated_at.eq(diesel::dsl::now),
+            ))
+            .get_result::<TaskModel>(&mut conn)
+            .optional()?;
         Ok(result)
     }
 
@@ -69,4 +114,81 @@ impl TaskRepository for TaskPostgres {
             .get_result::<TaskModel>(&mut conn)?;
         Ok(result)
     }
+
+    async fn get_subtasks(&self, task_id: i32) -> Result<Vec<TaskModel>> {
+        let mut conn = self.db_pool.get()?;
+        let result = tasks::table
+            .filter(tasks::parent_task_id.eq(task_id))
+            .order(tasks::created_at.asc())
+            .load::<TaskModel>(&mut conn)?;
+        Ok(result)
+    }
+
+    async fn get_open_blockers(&self, task_id: i32) -> Result<Vec<TaskModel>> {
+        let mut conn = self.db_pool.get()?;
+        let result = task_dependencies::table
+            .inner_join(tasks::table.on(tasks::id.eq(task_dependencies::blocked_by_task_id)))
+            .filter(task_dependencies::task_id.eq(task_id))
+            .filter(tasks::status.ne(TaskStatuses::Done.to_string()))
+            .select(TaskModel::as_select())
+            .load::<TaskModel>(&mut conn)?;
+        Ok(result)
+    }
+
+    async fn add_dependency(&self, mission_id: i32, entity: AddTaskDependencyEntity) -> Result<bool> {
+        let mut conn = self.db_pool.get()?;
+
+        let added = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            // One writer per mission at a time, so two opposite edges can't both pass the check
+            missions::table
+                .find(mission_id)
+                .for_update()
+                .select(missions::id)
+                .first::<i32>(conn)?;
+
+            // The new edge closes a cycle if the blocker already waits on the task somehow
+            let closes_cycle = diesel::sql_query(
+                r#"
+                WITH RECURSIVE upstream(id) AS (
+                    SELECT blocked_by_task_id FROM task_dependencies WHERE task_id = $1
+                    UNION
+                    SELECT d.blocked_by_task_id
+                    FROM task_dependencies d
+                    INNER JOIN upstream u ON d.task_id = u.id
+                )
+                SELECT EXISTS (SELECT 1 FROM upstream WHERE id = $2) AS closes_cycle
+                "#,
+            )
+            .bind::<diesel::sql_types::Int4, _>(entity.blocked_by_task_id)
+            .bind::<diesel::sql_types::Int4, _>(entity.task_id)
+            .get_result::<CycleCheck>(conn)?
+            .closes_cycle;
+            if closes_cycle {
+                return diesel::QueryResult::Ok(false);
+            }
+
+            diesel::insert_into(task_dependencies::table)
+                .values(&entity)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            diesel::QueryResult::Ok(true)
+        })?;
+
+        Ok(added)
+    }
+
+    async fn remove_dependency(&self, task_id: i32, blocked_by_task_id: i32) -> Result<bool> {
+        let mut conn = self.db_pool.get()?;
+        let rows_affected = diesel::delete(
+            task_dependencies::table.find((task_id, blocked_by_task_id)),
+        )
+        .execute(&mut conn)?;
+        Ok(rows_affected > 0)
+    }
+}
+
+#[derive(QueryableByName)]
+struct CycleCheck {
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    closes_cycle: bool,
 }
@@ -0,0 +1,130 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::{delete, dsl::now, insert_into, prelude::*, update};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::two_factor::{AddBrawlerTotpEntity, AddRecoveryCodeEntity, BrawlerTotpEntity},
+        repositories::two_factor::TwoFactorRepository,
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        schema::{brawler_totp, totp_recovery_codes},
+    },
+};
+
+pub struct TwoFactorPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl TwoFactorPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for TwoFactorPostgres {
+    async fn find(&self, brawler_id: i32) -> Result<Option<BrawlerTotpEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = brawler_totp::table
+            .find(brawler_id)
+            .select(BrawlerTotpEntity::as_select())
+            .first::<BrawlerTotpEntity>(&mut conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn start_enrollment(
+        &self,
+        add_totp_entity: AddBrawlerTotpEntity,
+        recovery_code_hashes: Vec<String>,
+    ) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+        let brawler_id = add_totp_entity.brawler_id;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            delete(brawler_totp::table.find(brawler_id)).execute(conn)?;
+            delete(totp_recovery_codes::table)
+                .filter(totp_recovery_codes::brawler_id.eq(brawler_id))
+                .execute(conn)?;
+
+            insert_into(brawler_totp::table)
+                .values(&add_totp_entity)
+                .execute(conn)?;
+
+            let codes: Vec<AddRecoveryCodeEntity> = recovery_code_hashes
+                .into_iter()
+                .map(|code_hash| AddRecoveryCodeEntity {
+                    brawler_id,
+                    code_hash,
+                })
+                .collect();
+            insert_into(totp_recovery_codes::table)
+                .values(&codes)
+                .execute(conn)?;
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    async fn enable(&self, brawler_id: i32, step: i64) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        update(brawler_totp::table.find(brawler_id))
+            .set((
+                brawler_totp::enabled_at.eq(now),
+                brawler_totp::last_used_step.eq(step),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn use_step(&self, brawler_id: i32, step: i64) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rows_affected = update(brawler_totp::table.find(brawler_id))
+            .filter(
+                brawler_totp::last_used_step
+                    .is_null()
+                    .or(brawler_totp::last_used_step.lt(step)),
+            )
+            .set(brawler_totp::last_used_step.eq(step))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn consume_recovery_code(&self, brawler_id: i32, code_hash: String) -> Result<bool> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let rows_affected = update(totp_recovery_codes::table)
+            .filter(totp_recovery_codes::brawler_id.eq(brawler_id))
+            .filter(totp_recovery_codes::code_hash.eq(code_hash))
+            .filter(totp_recovery_codes::used_at.is_null())
+            .set(totp_recovery_codes::used_at.eq(now))
+            .execute(&mut conn)?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn disable(&self, brawler_id: i32) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            delete(brawler_totp::table.find(brawler_id)).execute(conn)?;
+            delete(totp_recovery_codes::table)
+                .filter(totp_recovery_codes::brawler_id.eq(brawler_id))
+                .execute(conn)?;
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::{
+    dsl::{delete, insert_into, update},
+    prelude::*,
+    PgArrayExpressionMethods,
+};
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::webhooks::{
+            AddMissionWebhookEntity, AddWebhookDeliveryEntity, EditMissionWebhookEntity,
+            MissionWebhookEntity, WebhookDeliveryEntity,
+        },
+        repositories::webhooks::WebhookRepository,
+        value_objects::webhook_model::WebhookDeliveryStatuses,
+    },
+    infrastructure::database::{
+        postgresql_connection::PgPoolSquad,
+        schema::{mission_webhooks, webhook_deliveries},
+    },
+};
+
+pub struct WebhookPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl WebhookPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for WebhookPostgres {
+    async fn create(&self, entity: AddMissionWebhookEntity) -> Result<MissionWebhookEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = insert_into(mission_webhooks::table)
+            .values(&entity)
+            .returning(MissionWebhookEntity::as_returning())
+            .get_result::<MissionWebhookEntity>(&mut conn)?;
+
+        Ok(result)
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<MissionWebhookEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = mission_webhooks::table
+            .find(id)
+            .select(MissionWebhookEntity::as_select())
+            .first::<MissionWebhookEntity>(&mut conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn list_by_mission(&self, mission_id: i32) -> Result<Vec<MissionWebhookEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let results = mission_webhooks::table
+            .filter(mission_webhooks::mission_id.eq(mission_id))
+            .order(mission_webhooks::id.asc())
+            .select(MissionWebhookEntity::as_select())
+            .load::<MissionWebhookEntity>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    async fn edit(&self, id: i32, entity: EditMissionWebhookEntity) -> Result<MissionWebhookEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = update(mission_webhooks::table.find(id))
+            .set(&entity)
+            .returning(MissionWebhookEntity::as_returning())
+            .get_result::<MissionWebhookEntity>(&mut conn)?;
+
+        Ok(result)
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        delete(mission_webhooks::table.find(id)).execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn subscribed(&self, mission_id: i32, event_type: &str) -> Result<Vec<MissionWebhookEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let results = mission_webhooks::table
+            .filter(mission_webhooks::mission_id.eq(mission_id))
+            .filter(mission_webhooks::is_active.eq(true))
+            .filter(mission_webhooks::event_types.contains(vec![event_type.to_string()]))
+            .select(MissionWebhookEntity::as_select())
+            .load::<MissionWebhookEntity>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    async fn enqueue(&self, deliveries: Vec<AddWebhookDeliveryEntity>) -> Result<Vec<WebhookDeliveryEntity>> {
+        if deliveries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let results = insert_into(webhook_deliveries::table)
+            .values(&deliveries)
+            .returning(WebhookDeliveryEntity::as_returning())
+            .get_results::<WebhookDeliveryEntity>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<WebhookDeliveryEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let sql = r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = $3
+            WHERE id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = $1 AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+        "#;
+
+        let claimed = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::Text, _>(WebhookDeliveryStatuses::Pending.to_string())
+            .bind::<diesel::sql_types::BigInt, _>(limit)
+            .bind::<diesel::sql_types::Timestamp, _>(Utc::now().naive_utc() + lease)
+            .load::<WebhookDeliveryEntity>(&mut conn)?;
+
+        Ok(claimed)
+    }
+
+    async fn mark_delivered(&self, id: i32, response_status: i32) -> Result<WebhookDeliveryEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let result = update(webhook_deliveries::table.find(id))
+            .set((
+                webhook_deliveries::status.eq(WebhookDeliveryStatuses::Delivered.to_string()),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::response_status.eq(Some(response_status)),
+                webhook_deliveries::last_error.eq(None::<String>),
+                webhook_deliveries::delivered_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .returning(WebhookDeliveryEntity::as_returning())
+            .get_result::<WebhookDeliveryEntity>(&mut conn)?;
+
+        Ok(result)
+    }
+
+    async fn mark_failed(
+        &self,
+        id: i32,
+        response_status: Option<i32>,
+        error: String,
+        next_attempt_at: Option<NaiveDateTime>,
+    ) -> Result<WebhookDeliveryEntity> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let status = match next_attempt_at {
+            Some(_) => WebhookDeliveryStatuses::Pending,
+            None => WebhookDeliveryStatuses::Failed,
+        };
+        let result = update(webhook_deliveries::table.find(id))
+            .set((
+                webhook_deliveries::status.eq(status.to_string()),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::response_status.eq(response_status),
+                webhook_deliveries::last_error.eq(Some(error)),
+                webhook_deliveries::next_attempt_at
+                    .eq(next_attempt_at.unwrap_or_else(|| Utc::now().naive_utc())),
+            ))
+            .returning(WebhookDeliveryEntity::as_returning())
+            .get_result::<WebhookDeliveryEntity>(&mut conn)?;
+
+        Ok(result)
+    }
+
+    async fn deliveries(&self, webhook_id: i32, limit: i64) -> Result<Vec<WebhookDeliveryEntity>> {
+        let mut conn = Arc::clone(&self.db_pool).get()?;
+
+        let results = webhook_deliveries::table
+            .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+            .order((webhook_deliveries::created_at.desc(), webhook_deliveries::id.desc()))
+            .limit(limit)
+            .select(WebhookDeliveryEntity::as_select())
+            .load::<WebhookDeliveryEntity>(&mut conn)?;
+
+        Ok(results)
+    }
+}
@@ -1,5 +1,26 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    auth_sessions (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    brawler_totp (brawler_id) {
+        brawler_id -> Int4,
+        #[max_length = 64]
+        secret -> Varchar,
+        enabled_at -> Nullable<Timestamp>,
+        last_used_step -> Nullable<Int8>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     brawlers (id) {
         id -> Int4,
@@ -16,6 +37,57 @@ diesel::table! {
         #[max_length = 255]
         avatar_public_id -> Nullable<Varchar>,
         bio -> Nullable<Text>,
+        #[max_length = 255]
+        email -> Nullable<Varchar>,
+        email_verified_at -> Nullable<Timestamp>,
+        email_digest -> Bool,
+        last_digest_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    chief_transfers (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        from_brawler_id -> Int4,
+        to_brawler_id -> Int4,
+        #[max_length = 255]
+        old_chief_role -> Nullable<Varchar>,
+        #[max_length = 50]
+        status -> Varchar,
+        created_at -> Timestamp,
+        resolved_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    comment_edits (id) {
+        id -> Int4,
+        comment_id -> Int4,
+        body -> Text,
+        edited_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    comment_mentions (comment_id, brawler_id) {
+        comment_id -> Int4,
+        brawler_id -> Int4,
+    }
+}
+
+diesel::table! {
+    comments (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        task_id -> Nullable<Int4>,
+        submission_id -> Nullable<Int4>,
+        parent_id -> Nullable<Int4>,
+        author_id -> Nullable<Int4>,
+        body -> Text,
+        created_at -> Timestamp,
+        edited_at -> Nullable<Timestamp>,
+        deleted_at -> Nullable<Timestamp>,
     }
 }
 
@@ -30,6 +102,121 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    email_verification_tokens (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 255]
+        email -> Varchar,
+        #[max_length = 64]
+        token_hash -> Varchar,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    file_deletions (id) {
+        id -> Int4,
+        #[max_length = 50]
+        store -> Varchar,
+        #[max_length = 512]
+        storage_key -> Varchar,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    login_attempts (id) {
+        id -> Int4,
+        #[max_length = 255]
+        username -> Varchar,
+        brawler_id -> Nullable<Int4>,
+        #[max_length = 64]
+        ip_address -> Varchar,
+        success -> Bool,
+        #[max_length = 50]
+        reason -> Nullable<Varchar>,
+        attempted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    login_throttles (throttle_key) {
+        #[max_length = 320]
+        throttle_key -> Varchar,
+        failures -> Int4,
+        last_failure_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mail_outbox (id) {
+        id -> Int4,
+        brawler_id -> Nullable<Int4>,
+        #[max_length = 255]
+        to_address -> Varchar,
+        #[max_length = 255]
+        subject -> Varchar,
+        body -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamp,
+        created_at -> Timestamp,
+        sent_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    mission_invitations (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        #[max_length = 64]
+        code -> Varchar,
+        created_by -> Int4,
+        invitee_id -> Nullable<Int4>,
+        max_uses -> Nullable<Int4>,
+        use_count -> Int4,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mission_join_requests (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        brawler_id -> Int4,
+        message -> Nullable<Text>,
+        #[max_length = 50]
+        status -> Varchar,
+        reviewed_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+        reviewed_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    mission_status_history (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        changed_by -> Nullable<Int4>,
+        #[max_length = 255]
+        from_status -> Varchar,
+        #[max_length = 255]
+        to_status -> Varchar,
+        reason -> Nullable<Text>,
+        changed_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     mission_submissions (id) {
         id -> Int4,
@@ -41,6 +228,40 @@ diesel::table! {
         submitted_at -> Timestamptz,
         task_id -> Nullable<Int4>,
         description -> Nullable<Text>,
+        #[max_length = 50]
+        review_status -> Varchar,
+        reviewed_by -> Nullable<Int4>,
+        review_feedback -> Nullable<Text>,
+        reviewed_at -> Nullable<Timestamptz>,
+        version -> Int4,
+        is_current -> Bool,
+        withdrawn_at -> Nullable<Timestamptz>,
+        #[max_length = 512]
+        storage_key -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    mission_waitlist (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        brawler_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mission_webhooks (id) {
+        id -> Int4,
+        mission_id -> Int4,
+        url -> Text,
+        #[max_length = 255]
+        secret -> Varchar,
+        event_types -> Array<Text>,
+        is_active -> Bool,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -59,6 +280,67 @@ diesel::table! {
         max_members -> Int4,
         start_date -> Nullable<Timestamp>,
         end_date -> Nullable<Timestamp>,
+        #[max_length = 50]
+        visibility -> Varchar,
+        max_upload_mb -> Int4,
+        allowed_file_types -> Array<Text>,
+        deadline_reminded_at -> Nullable<Timestamp>,
+        tags -> Array<Text>,
+    }
+}
+
+diesel::table! {
+    notification_preferences (brawler_id, kind) {
+        brawler_id -> Int4,
+        #[max_length = 50]
+        kind -> Varchar,
+        enabled -> Bool,
+        updated_at -> Timestamp,
+        email_enabled -> Nullable<Bool>,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 50]
+        kind -> Varchar,
+        mission_id -> Nullable<Int4>,
+        message -> Text,
+        read_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    password_reset_tokens (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 64]
+        token_hash -> Varchar,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    refresh_tokens (id) {
+        id -> Int4,
+        session_id -> Int4,
+        #[max_length = 64]
+        token_hash -> Varchar,
+        created_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    task_dependencies (task_id, blocked_by_task_id) {
+        task_id -> Int4,
+        blocked_by_task_id -> Int4,
+        created_at -> Timestamp,
     }
 }
 
@@ -80,20 +362,100 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         has_submission -> Bool,
+        parent_task_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    totp_recovery_codes (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 64]
+        code_hash -> Varchar,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        #[max_length = 50]
+        event_type -> Varchar,
+        payload -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        response_status -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamp,
+        created_at -> Timestamp,
+        delivered_at -> Nullable<Timestamp>,
     }
 }
 
+diesel::joinable!(auth_sessions -> brawlers (brawler_id));
+diesel::joinable!(brawler_totp -> brawlers (brawler_id));
+diesel::joinable!(chief_transfers -> missions (mission_id));
+diesel::joinable!(comment_edits -> comments (comment_id));
+diesel::joinable!(comment_mentions -> brawlers (brawler_id));
+diesel::joinable!(comment_mentions -> comments (comment_id));
+diesel::joinable!(comments -> brawlers (author_id));
+diesel::joinable!(comments -> mission_submissions (submission_id));
+diesel::joinable!(comments -> missions (mission_id));
+diesel::joinable!(comments -> tasks (task_id));
 diesel::joinable!(crew_memberships -> missions (mission_id));
+diesel::joinable!(email_verification_tokens -> brawlers (brawler_id));
+diesel::joinable!(login_attempts -> brawlers (brawler_id));
+diesel::joinable!(mail_outbox -> brawlers (brawler_id));
+diesel::joinable!(mission_invitations -> missions (mission_id));
+diesel::joinable!(mission_join_requests -> missions (mission_id));
+diesel::joinable!(mission_status_history -> brawlers (changed_by));
+diesel::joinable!(mission_status_history -> missions (mission_id));
 diesel::joinable!(mission_submissions -> brawlers (brawler_id));
 diesel::joinable!(mission_submissions -> missions (mission_id));
 diesel::joinable!(mission_submissions -> tasks (task_id));
+diesel::joinable!(mission_waitlist -> brawlers (brawler_id));
+diesel::joinable!(mission_waitlist -> missions (mission_id));
+diesel::joinable!(mission_webhooks -> brawlers (created_by));
+diesel::joinable!(mission_webhooks -> missions (mission_id));
 diesel::joinable!(missions -> brawlers (chief_id));
+diesel::joinable!(notification_preferences -> brawlers (brawler_id));
+diesel::joinable!(notifications -> brawlers (brawler_id));
+diesel::joinable!(notifications -> missions (mission_id));
+diesel::joinable!(password_reset_tokens -> brawlers (brawler_id));
+diesel::joinable!(refresh_tokens -> auth_sessions (session_id));
 diesel::joinable!(tasks -> missions (mission_id));
+diesel::joinable!(totp_recovery_codes -> brawlers (brawler_id));
+diesel::joinable!(webhook_deliveries -> mission_webhooks (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    auth_sessions,
+    brawler_totp,
     brawlers,
+    chief_transfers,
+    comment_edits,
+    comment_mentions,
+    comments,
     crew_memberships,
+    email_verification_tokens,
+    file_deletions,
+    login_attempts,
+    login_throttles,
+    mail_outbox,
+    mission_invitations,
+    mission_join_requests,
+    mission_status_history,
     mission_submissions,
+    mission_waitlist,
+    mission_webhooks,
     missions,
+    notification_preferences,
+    notifications,
+    password_reset_tokens,
+    refresh_tokens,
+    task_dependencies,
     tasks,
+    totp_recovery_codes,
+    webhook_deliveries,
 );
@@ -0,0 +1,61 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    config::{config_loader::get_stage, stage::Stage},
+    domain::errors::DomainError,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub code: String,
+    pub message: String,
+    pub details: Option<Value>,
+}
+
+impl DomainError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            DomainError::NotFound(_) => StatusCode::NOT_FOUND,
+            DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
+            DomainError::Conflict(_) => StatusCode::CONFLICT,
+            DomainError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            DomainError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            DomainError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            DomainError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for DomainError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+
+        let (message, details) = match &self {
+            DomainError::Internal(e) => {
+                tracing::error!("Internal error: {:?}", e);
+                // Raw error chains are only exposed outside production
+                let details = match get_stage() {
+                    Stage::Production => None,
+                    _ => Some(Value::String(format!("{:#}", e))),
+                };
+                ("Internal server error".to_string(), details)
+            }
+            other => (other.to_string(), None),
+        };
+
+        let body = ErrorBody {
+            code: self.code().to_string(),
+            message,
+            details,
+        };
+
+        (status, Json(body)).into_response()
+    }
+}
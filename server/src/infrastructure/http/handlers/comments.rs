@@ -0,0 +1,98 @@
+use crate::{
+    domain::value_objects::comment_model::{CommentTarget, CreateCommentModel, EditCommentModel},
+    infrastructure::http::routers::mission_workspace::AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+
+async fn list(state: AppState, mission_id: i32, target: CommentTarget, user_id: i32) -> axum::response::Response {
+    match state.comment_case.list(mission_id, target, user_id).await {
+        Ok(threads) => (StatusCode::OK, Json(threads)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn create(
+    state: AppState,
+    mission_id: i32,
+    target: CommentTarget,
+    user_id: i32,
+    payload: CreateCommentModel,
+) -> axum::response::Response {
+    match state.comment_case.create(mission_id, target, user_id, payload).await {
+        Ok(comment) => (StatusCode::CREATED, Json(comment)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_task_comments(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    list(state, mission_id, CommentTarget::Task(task_id), user_id).await
+}
+
+pub async fn add_task_comment(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id)): Path<(i32, i32)>,
+    Json(payload): Json<CreateCommentModel>,
+) -> impl IntoResponse {
+    create(state, mission_id, CommentTarget::Task(task_id), user_id, payload).await
+}
+
+pub async fn get_submission_comments(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, submission_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    list(state, mission_id, CommentTarget::Submission(submission_id), user_id).await
+}
+
+pub async fn add_submission_comment(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, submission_id)): Path<(i32, i32)>,
+    Json(payload): Json<CreateCommentModel>,
+) -> impl IntoResponse {
+    create(state, mission_id, CommentTarget::Submission(submission_id), user_id, payload).await
+}
+
+pub async fn edit_comment(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, comment_id)): Path<(i32, i32)>,
+    Json(payload): Json<EditCommentModel>,
+) -> impl IntoResponse {
+    match state.comment_case.edit(mission_id, comment_id, user_id, payload).await {
+        Ok(comment) => (StatusCode::OK, Json(comment)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_comment(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, comment_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.comment_case.delete(mission_id, comment_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Comment deleted successfully" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_comment_history(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, comment_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.comment_case.history(mission_id, comment_id, user_id).await {
+        Ok(edits) => (StatusCode::OK, Json(edits)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
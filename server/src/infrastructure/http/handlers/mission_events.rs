@@ -0,0 +1,119 @@
+use std::{convert::Infallible, time::Duration};
+
+use crate::{
+    domain::{
+        errors::DomainError,
+        value_objects::mission_events::{MissionEvent, MissionEventEnvelope},
+    },
+    infrastructure::{
+        http::{middlewares::auth::AccessSession, routers::mission_workspace::AppState},
+        jwt::jwt_model::StreamClaims,
+    },
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
+    Extension, Json,
+};
+use chrono::Utc;
+use futures_util::stream;
+use tokio::{
+    sync::broadcast::{Receiver, error::RecvError},
+    time::{Instant, Interval, interval_at, sleep_until},
+};
+
+/// How often an open stream checks that its session has not been revoked.
+const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+pub async fn issue_stream_ticket(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Extension(session): Extension<AccessSession>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state
+        .event_case
+        .issue_ticket(mission_id, user_id, session.session_id, session.expires_at)
+        .await
+    {
+        Ok(ticket) => (StatusCode::OK, Json(ticket)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+struct Subscription {
+    receiver: Receiver<MissionEventEnvelope>,
+    session_check: Interval,
+}
+
+pub async fn stream_events(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Extension(ticket): Extension<StreamClaims>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    if ticket.mission_id != mission_id {
+        return DomainError::unauthorized("Stream ticket is for another mission").into_response();
+    }
+
+    let receiver = match state.event_case.subscribe(mission_id, user_id).await {
+        Ok(receiver) => receiver,
+        Err(e) => return e.into_response(),
+    };
+
+    let session_id = ticket.sid;
+    let remaining = (ticket.session_exp as i64 - Utc::now().timestamp()).max(0) as u64;
+    let session_ends = Instant::now() + Duration::from_secs(remaining);
+    let subscription = Subscription {
+        receiver,
+        session_check: interval_at(Instant::now() + SESSION_CHECK_INTERVAL, SESSION_CHECK_INTERVAL),
+    };
+
+    // The stream ends with the event that takes the subscriber out of the mission,
+    // when the access token the ticket was issued for expires, or when its session is revoked
+    let events = stream::unfold(Some(subscription), move |subscription| {
+        let state = state.clone();
+        async move {
+            let mut subscription = subscription?;
+            let envelope = loop {
+                tokio::select! {
+                    received = subscription.receiver.recv() => break match received {
+                        Ok(envelope) => envelope,
+                        Err(RecvError::Lagged(_)) => MissionEventEnvelope::new(mission_id, MissionEvent::Resync),
+                        Err(RecvError::Closed) => return None,
+                    },
+                    _ = sleep_until(session_ends) => return None,
+                    _ = subscription.session_check.tick() => {
+                        match state.event_case.session_active(session_id, user_id).await {
+                            Ok(true) => continue,
+                            Ok(false) => return None,
+                            Err(e) => {
+                                tracing::warn!("Closing event stream, session check failed: {:?}", e);
+                                return None;
+                            }
+                        }
+                    }
+                }
+            };
+            let next = (!envelope.event.ends_access_for(user_id)).then_some(subscription);
+            Some((Ok::<_, Infallible>(to_sse_event(&envelope)), next))
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+}
+
+fn to_sse_event(envelope: &MissionEventEnvelope) -> Event {
+    let event = Event::default().event(envelope.event.kind());
+    match event.clone().json_data(envelope) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Failed to serialize mission event: {}", e);
+            event.data("{}")
+        }
+    }
+}
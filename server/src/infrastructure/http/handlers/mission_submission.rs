@@ -1,17 +1,42 @@
-use crate::infrastructure::http::routers::mission_workspace::AppState;
+use crate::{
+    domain::{
+        errors::DomainError,
+        services::file_storage::ByteSource,
+        value_objects::{
+            mission_submission_model::SubmissionDiffQuery,
+            submission_review_model::ReviewSubmissionModel,
+        },
+    },
+    infrastructure::http::routers::mission_workspace::AppState,
+};
+use async_trait::async_trait;
 use axum::{
-    extract::{Multipart, Path, State},
+    extract::{Multipart, Path, State, multipart::Field},
     http::StatusCode,
     response::IntoResponse,
     Extension, Json,
 };
-use base64::{engine::general_purpose, Engine};
 
 #[derive(serde::Deserialize)]
 pub struct SubmitQuery {
     pub task_id: Option<i32>,
 }
 
+/// Feeds a multipart field to storage as it arrives instead of buffering it.
+struct MultipartUpload<'a> {
+    field: Field<'a>,
+}
+
+#[async_trait]
+impl ByteSource for MultipartUpload<'_> {
+    async fn next_chunk(&mut self) -> anyhow::Result<Option<Vec<u8>>> {
+        match self.field.chunk().await {
+            Ok(chunk) => Ok(chunk.map(|chunk| chunk.to_vec())),
+            Err(e) => Err(anyhow::anyhow!(e.body_text())),
+        }
+    }
+}
+
 pub async fn submit_work(
     State(state): State<AppState>,
     Extension(user_id): Extension<i32>,
@@ -19,40 +44,70 @@ pub async fn submit_work(
     axum::extract::Query(query): axum::extract::Query<SubmitQuery>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
-    let mut file_data = None;
-    let mut file_name = String::new();
-    let mut file_type = String::new();
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return DomainError::validation(e.body_text()).into_response(),
+        };
 
-    while let Some(field) = multipart.next_field().await.unwrap() {
-        let name = field.name().unwrap().to_string();
+        if field.name() == Some("file") {
+            let file_name = field.file_name().unwrap_or("unknown").to_string();
+            let declared_type = field.content_type().map(str::to_string);
+            let mut upload = MultipartUpload { field };
 
-        if name == "file" {
-            file_name = field.file_name().unwrap_or("unknown").to_string();
-            file_type = field.content_type().unwrap_or("application/octet-stream").to_string();
-            let data = field.bytes().await.unwrap();
-            file_data = Some(data);
+            return match state
+                .submission_case
+                .submit_work(
+                    mission_id,
+                    user_id,
+                    query.task_id,
+                    &mut upload,
+                    file_name,
+                    declared_type,
+                )
+                .await
+            {
+                Ok(submission) => (StatusCode::OK, Json(submission)).into_response(),
+                Err(e) => e.into_response(),
+            };
         }
     }
 
-    if let Some(data) = file_data {
-        let base64_data = general_purpose::STANDARD.encode(data);
-        match state
-            .submission_case
-            .submit_work(
-                mission_id,
-                user_id,
-                query.task_id,
-                base64_data,
-                file_name,
-                file_type,
-            )
-            .await
-        {
-            Ok(submission) => (StatusCode::OK, Json(submission)).into_response(),
-            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
-        }
-    } else {
-        (StatusCode::BAD_REQUEST, "No file uploaded").into_response()
+    DomainError::validation("No file uploaded").into_response()
+}
+
+pub async fn get_task_versions(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.submission_case.task_versions(mission_id, task_id, user_id).await {
+        Ok(versions) => (StatusCode::OK, Json(versions)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_task_version(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id, version)): Path<(i32, i32, i32)>,
+) -> impl IntoResponse {
+    match state.submission_case.task_version(mission_id, task_id, version, user_id).await {
+        Ok(submission) => (StatusCode::OK, Json(submission)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn diff_task_versions(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id)): Path<(i32, i32)>,
+    axum::extract::Query(query): axum::extract::Query<SubmissionDiffQuery>,
+) -> impl IntoResponse {
+    match state.submission_case.diff_versions(mission_id, task_id, query, user_id).await {
+        Ok(diff) => (StatusCode::OK, Json(diff)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -63,8 +118,8 @@ pub async fn get_task_submission(
 ) -> impl IntoResponse {
     match state.submission_case.get_task_submission(task_id, user_id).await {
         Ok(Some(submission)) => (StatusCode::OK, Json(submission)).into_response(),
-        Ok(None) => (StatusCode::NOT_FOUND, "No submission found for this task").into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Ok(None) => DomainError::not_found("No submission found for this task").into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -75,7 +130,7 @@ pub async fn get_mission_submissions(
 ) -> impl IntoResponse {
     match state.submission_case.get_submissions(mission_id, user_id).await {
         Ok(submissions) => (StatusCode::OK, Json(submissions)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 pub async fn delete_submission(
@@ -85,7 +140,7 @@ pub async fn delete_submission(
 ) -> impl IntoResponse {
     match state.submission_case.delete_submission(submission_id, user_id).await {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Submission deleted successfully" }))).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 #[derive(serde::Deserialize)]
@@ -101,6 +156,29 @@ pub async fn update_submission_details(
 ) -> impl IntoResponse {
     match state.submission_case.update_description(submission_id, user_id, payload.description).await {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Submission details updated successfully" }))).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_pending_review(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.submission_case.pending_review(mission_id, user_id).await {
+        Ok(submissions) => (StatusCode::OK, Json(submissions)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn review_submission(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, submission_id)): Path<(i32, i32)>,
+    Json(payload): Json<ReviewSubmissionModel>,
+) -> impl IntoResponse {
+    match state.submission_case.review(mission_id, submission_id, user_id, payload).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Submission reviewed successfully" }))).into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -1 +1,4 @@
+pub mod comments;
+pub mod mission_events;
 pub mod mission_submission;
+pub mod webhooks;
@@ -0,0 +1,78 @@
+use crate::{
+    domain::value_objects::webhook_model::{CreateWebhookModel, EditWebhookModel},
+    infrastructure::http::routers::mission_workspace::AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+
+pub async fn get_webhooks(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.webhook_case.list(mission_id, user_id).await {
+        Ok(webhooks) => (StatusCode::OK, Json(webhooks)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Json(payload): Json<CreateWebhookModel>,
+) -> impl IntoResponse {
+    match state.webhook_case.create(mission_id, user_id, payload).await {
+        Ok(webhook) => (StatusCode::CREATED, Json(webhook)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn edit_webhook(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, webhook_id)): Path<(i32, i32)>,
+    Json(payload): Json<EditWebhookModel>,
+) -> impl IntoResponse {
+    match state.webhook_case.edit(mission_id, webhook_id, user_id, payload).await {
+        Ok(webhook) => (StatusCode::OK, Json(webhook)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, webhook_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.webhook_case.delete(mission_id, webhook_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Webhook deleted successfully" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_webhook_deliveries(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, webhook_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.webhook_case.deliveries(mission_id, webhook_id, user_id).await {
+        Ok(deliveries) => (StatusCode::OK, Json(deliveries)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn send_test_event(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, webhook_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.webhook_case.send_test(mission_id, webhook_id, user_id).await {
+        Ok(delivery) => (StatusCode::OK, Json(delivery)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
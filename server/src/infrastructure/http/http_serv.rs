@@ -3,6 +3,7 @@ use std::{net::SocketAddr, sync::Arc, time::Duration};
 use anyhow::{Ok, Result};
 use axum::{
     Router,
+    extract::DefaultBodyLimit,
     http::{
         Method, StatusCode,
         header::{AUTHORIZATION, CONTENT_TYPE},
@@ -11,13 +12,13 @@ use axum::{
 use tokio::net::TcpListener;
 use tower_http::{
     cors::{Any, CorsLayer},
-    limit::RequestBodyLimitLayer,
     services::{ServeDir, ServeFile},
     trace::TraceLayer,
 };
 use tracing::info;
 
 use crate::{
+    application::services::mission_events::MissionEventHub,
     config::config_model::DotEnvyConfig,
     infrastructure::{
         database::postgresql_connection::PgPoolSquad,
@@ -33,7 +34,7 @@ fn static_serve() -> Router {
     Router::new().fallback_service(service)
 }
 
-fn api_serve(db_pool: Arc<PgPoolSquad>) -> Router {
+fn api_serve(db_pool: Arc<PgPoolSquad>, events: Arc<MissionEventHub>) -> Router {
     Router::new()
         .nest("/brawler", routers::brawlers::routes(Arc::clone(&db_pool)))
         .nest(
@@ -42,30 +43,37 @@ fn api_serve(db_pool: Arc<PgPoolSquad>) -> Router {
         )
         .nest(
             "/mission",
-            routers::mission_operation::routes(Arc::clone(&db_pool)),
+            routers::mission_operation::routes(Arc::clone(&db_pool), Arc::clone(&events)),
         )
         .nest(
             "/crew",
-            routers::crew_operation::routes(Arc::clone(&db_pool)),
+            routers::crew_operation::routes(Arc::clone(&db_pool), Arc::clone(&events)),
         )
         .nest(
             "/mission-management",
-            routers::mission_management::routes(Arc::clone(&db_pool)),
+            routers::mission_management::routes(Arc::clone(&db_pool), Arc::clone(&events)),
         )
         .nest(
             "/authentication",
             routers::authentication::routes(Arc::clone(&db_pool)),
         )
         .nest("/util", routers::default_router::routes())
-        .nest("/v1/missions", routers::mission_workspace::routes(Arc::clone(&db_pool)))
+        .nest("/files", routers::files::routes())
+        .nest("/v1/missions", routers::mission_workspace::routes(Arc::clone(&db_pool), Arc::clone(&events)))
         .nest("/dashboard", routers::dashboard::routes(Arc::clone(&db_pool)))
+        .nest("/notifications", routers::notifications::routes(Arc::clone(&db_pool)))
+        .nest("/search", routers::search::routes(Arc::clone(&db_pool)))
         .fallback(|| async { (StatusCode::NOT_FOUND, "API not found") })
 }
 
-pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Result<()> {
+pub async fn start(
+    config: Arc<DotEnvyConfig>,
+    db_pool: Arc<PgPoolSquad>,
+    events: Arc<MissionEventHub>,
+) -> Result<()> {
     let app = Router::new()
         .merge(static_serve())
-        .nest("/api", api_serve(db_pool))
+        .nest("/api", api_serve(db_pool, events))
         // .fallback(default_router::health_check)
         // .route("/health_check", get(default_router::health_check)
         // .route("/make-error", get(default_router::make_error)
@@ -73,7 +81,8 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(config.server.timeout),
         ))
-        .layer(RequestBodyLimitLayer::new(
+        // Applies to every extractor; the submission upload route opts out and enforces its own cap
+        .layer(DefaultBodyLimit::max(
             (config.server.body_limit * 1024 * 1024).try_into()?,
         ))
         .layer(
@@ -95,7 +104,10 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
     let listener = TcpListener::bind(addr).await?;
 
     info!("Server start on port {}", config.server.port);
-    axum::serve(listener, app)
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
@@ -1,35 +1,132 @@
+use std::sync::Arc;
+
 use axum::{
-    extract::Request,
-    http::{StatusCode, header},
+    extract::{Request, State},
+    http::header,
     middleware::Next,
     response::Response,
 };
 
-use crate::{config::config_loader::get_jwt_env, infrastructure::jwt::verify_token};
+use crate::{
+    config::config_loader::get_jwt_env,
+    domain::{errors::DomainError, repositories::auth_sessions::AuthSessionRepository},
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad, repositories::auth_sessions::AuthSessionPostgres,
+        },
+        jwt::{verify_stream_ticket, verify_token},
+    },
+};
+
+pub async fn authorization(
+    State(db_pool): State<Arc<PgPoolSquad>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, DomainError> {
+    let token = bearer_token(&req)?;
+    let claims = authenticate(db_pool, token).await?;
+
+    req.extensions_mut().insert(claims.user_id);
+    req.extensions_mut().insert(claims.session);
+
+    Ok(next.run(req).await)
+}
+
+/// For event streams, which browsers open with `EventSource` and so cannot send
+/// headers. Instead of the access token they carry a stream ticket as the
+/// `ticket` query parameter; the ticket's claims are passed on so the stream
+/// can check its mission and end with the session.
+pub async fn stream_authorization(
+    State(db_pool): State<Arc<PgPoolSquad>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, DomainError> {
+    let ticket =
+        query_ticket(&req).ok_or_else(|| DomainError::unauthorized("Missing stream ticket"))?;
+
+    let jwt_env = get_jwt_env()?;
+    let claims = verify_stream_ticket(jwt_env.secret, ticket)
+        .map_err(|_| DomainError::unauthorized("Invalid stream ticket"))?;
+    let user_id = claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| DomainError::unauthorized("Invalid stream ticket"))?;
+    ensure_session_active(db_pool, claims.sid, user_id).await?;
+
+    req.extensions_mut().insert(user_id);
+    req.extensions_mut().insert(claims);
+
+    Ok(next.run(req).await)
+}
+
+/// The session behind an access token, for handlers that hand out tokens derived from it.
+#[derive(Debug, Clone, Copy)]
+pub struct AccessSession {
+    pub session_id: i32,
+    pub expires_at: usize,
+}
 
-pub async fn authorization(mut req: Request, next: Next) -> Result<Response, StatusCode> {
+struct Authenticated {
+    user_id: i32,
+    session: AccessSession,
+}
+
+fn bearer_token(req: &Request) -> Result<String, DomainError> {
     let header = req
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|value| value.to_str().ok())
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+        .ok_or_else(|| DomainError::unauthorized("Missing authorization header"))?;
 
     let token = header
         .strip_prefix("Bearer ")
-        .ok_or(StatusCode::UNAUTHORIZED)?
+        .ok_or_else(|| DomainError::unauthorized("Invalid authorization scheme"))?
         .to_string();
 
-    let jwt_env = get_jwt_env().unwrap();
+    Ok(token)
+}
+
+fn query_ticket(req: &Request) -> Option<String> {
+    req.uri().query()?.split('&').find_map(|pair| {
+        let value = pair.strip_prefix("ticket=")?;
+        urlencoding::decode(value).ok().map(|value| value.into_owned())
+    })
+}
+
+async fn authenticate(db_pool: Arc<PgPoolSquad>, token: String) -> Result<Authenticated, DomainError> {
+    let jwt_env = get_jwt_env()?;
     let secret = jwt_env.secret;
 
-    let claims = verify_token(secret, token).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let claims =
+        verify_token(secret, token).map_err(|_| DomainError::unauthorized("Invalid token"))?;
 
     let user_id = claims
         .sub
         .parse::<i32>()
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+        .map_err(|_| DomainError::unauthorized("Invalid token"))?;
 
-    req.extensions_mut().insert(user_id);
+    ensure_session_active(db_pool, claims.sid, user_id).await?;
 
-    Ok(next.run(req).await)
+    Ok(Authenticated {
+        user_id,
+        session: AccessSession {
+            session_id: claims.sid,
+            expires_at: claims.exp,
+        },
+    })
+}
+
+async fn ensure_session_active(
+    db_pool: Arc<PgPoolSquad>,
+    session_id: i32,
+    user_id: i32,
+) -> Result<(), DomainError> {
+    let session_active = AuthSessionPostgres::new(db_pool)
+        .is_active(session_id, user_id)
+        .await?;
+    if !session_active {
+        return Err(DomainError::unauthorized("Session has been revoked"));
+    }
+
+    Ok(())
 }
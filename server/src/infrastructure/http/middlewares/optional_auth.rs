@@ -1,27 +1,47 @@
+use std::sync::Arc;
+
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::{StatusCode, header},
     middleware::Next,
     response::Response,
 };
 
-use crate::{config::config_loader::get_jwt_env, infrastructure::jwt::verify_token};
+use crate::{
+    config::config_loader::get_jwt_env,
+    domain::repositories::auth_sessions::AuthSessionRepository,
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad, repositories::auth_sessions::AuthSessionPostgres,
+        },
+        jwt::verify_token,
+    },
+};
 
-pub async fn optional_authorization(mut req: Request, next: Next) -> Result<Response, StatusCode> {
+pub async fn optional_authorization(
+    State(db_pool): State<Arc<PgPoolSquad>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
     let header = req
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|value| value.to_str().ok());
 
-    if let Some(auth_header) = header {
-        if let Some(token) = auth_header.strip_prefix("Bearer ") {
-            let jwt_env = get_jwt_env().unwrap();
-            let secret = jwt_env.secret;
+    if let Some(token) = header.and_then(|value| value.strip_prefix("Bearer ")) {
+        let jwt_env = get_jwt_env().unwrap();
+        let secret = jwt_env.secret;
 
-            if let Ok(claims) = verify_token(secret, token.to_string()) {
-                if let Ok(user_id) = claims.sub.parse::<i32>() {
-                    req.extensions_mut().insert(user_id);
-                }
+        if let Ok(claims) = verify_token(secret, token.to_string())
+            && let Ok(user_id) = claims.sub.parse::<i32>()
+        {
+            // A revoked session is treated like an anonymous visitor
+            let session_active = AuthSessionPostgres::new(db_pool)
+                .is_active(claims.sid, user_id)
+                .await
+                .unwrap_or(false);
+            if session_active {
+                req.extensions_mut().insert(user_id);
             }
         }
     }
@@ -1,51 +1,264 @@
-﻿use std::sync::Arc;
+﻿use std::{net::SocketAddr, sync::Arc};
 
-use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
+use axum::{
+    Extension, Json, Router,
+    extract::{ConnectInfo, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, patch, post},
+};
 
 use crate::{
     application::use_cases::authentication::AuthenticationUseCase,
-    domain::{
-        repositories::brawlers::BrawlerRepository, value_objects::brawler_model::RegisterBrawlerModel,
+    config::{
+        config_loader::{get_login_security_env, get_mail_env},
+        config_model::LoginThrottleBackend,
+    },
+    domain::value_objects::{
+        brawler_model::{
+            ChangePasswordModel, PasswordResetConfirmModel, PasswordResetRequestModel,
+            RegisterBrawlerModel,
+        },
+        login_attempt_filter::LoginAttemptFilter,
     },
     infrastructure::{
-        database::{postgresql_connection::PgPoolSquad, repositories::brawlers::BrawlerPostgres},
-        jwt::authentication_model::LoginModel,
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                auth_sessions::AuthSessionPostgres, brawlers::BrawlerPostgres,
+                login_attempts::LoginAttemptPostgres, login_throttles::LoginThrottlePostgres,
+                password_resets::PasswordResetPostgres, two_factor::TwoFactorPostgres,
+            },
+        },
+        http::middlewares::auth::authorization,
+        jwt::authentication_model::{
+            LoginModel, RefreshTokenModel, TwoFactorCodeModel, TwoFactorVerifyModel,
+        },
+        login_throttle::{InMemoryLoginThrottle, LoginThrottleStore},
+        mail::MailerStore,
     },
 };
 
-pub async fn login<T>(
-    State(user_case): State<Arc<AuthenticationUseCase<T>>>,       
+pub type AuthenticationState = Arc<
+    AuthenticationUseCase<
+        BrawlerPostgres,
+        AuthSessionPostgres,
+        PasswordResetPostgres,
+        MailerStore,
+        LoginAttemptPostgres,
+        LoginThrottleStore,
+        TwoFactorPostgres,
+    >,
+>;
+
+fn client_ip(headers: &HeaderMap, peer: SocketAddr) -> String {
+    let trust_proxy_headers = get_login_security_env()
+        .map(|env| env.trust_proxy_headers)
+        .unwrap_or(false);
+
+    // The left-most entry is the client as seen by the first proxy
+    if trust_proxy_headers
+        && let Some(forwarded) = headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    {
+        return forwarded.to_string();
+    }
+
+    peer.ip().to_string()
+}
+
+pub async fn login(
+    State(user_case): State<AuthenticationState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(model): Json<LoginModel>,
-) -> impl IntoResponse
-where
-    T: BrawlerRepository + Send + Sync,
-{
-    match user_case.login(model).await {
+) -> impl IntoResponse {
+    match user_case.login(model, client_ip(&headers, peer)).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn verify_two_factor(
+    State(user_case): State<AuthenticationState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(model): Json<TwoFactorVerifyModel>,
+) -> impl IntoResponse {
+    match user_case
+        .verify_two_factor(model, client_ip(&headers, peer))
+        .await
+    {
         Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn enroll_two_factor(
+    State(user_case): State<AuthenticationState>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match user_case.enroll_two_factor(user_id).await {
+        Ok(enrollment) => (StatusCode::CREATED, Json(enrollment)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
 
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+pub async fn activate_two_factor(
+    State(user_case): State<AuthenticationState>,
+    Extension(user_id): Extension<i32>,
+    Json(model): Json<TwoFactorCodeModel>,
+) -> impl IntoResponse {
+    match user_case.activate_two_factor(user_id, model.code).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn register<T>(
-    State(user_case): State<Arc<AuthenticationUseCase<T>>>,       
+pub async fn disable_two_factor(
+    State(user_case): State<AuthenticationState>,
+    Extension(user_id): Extension<i32>,
+    Json(model): Json<TwoFactorCodeModel>,
+) -> impl IntoResponse {
+    match user_case.disable_two_factor(user_id, model.code).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn register(
+    State(user_case): State<AuthenticationState>,
     Json(model): Json<RegisterBrawlerModel>,
-) -> impl IntoResponse
-where
-    T: BrawlerRepository + Send + Sync,
-{
+) -> impl IntoResponse {
     match user_case.register(model).await {
         Ok(passport) => (StatusCode::CREATED, Json(passport)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn refresh(
+    State(user_case): State<AuthenticationState>,
+    Json(model): Json<RefreshTokenModel>,
+) -> impl IntoResponse {
+    match user_case.refresh(model.refresh_token).await {
+        Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn logout(
+    State(user_case): State<AuthenticationState>,
+    Json(model): Json<RefreshTokenModel>,
+) -> impl IntoResponse {
+    match user_case.logout(model.refresh_token).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn logout_all(
+    State(user_case): State<AuthenticationState>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match user_case.logout_all(user_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn change_password(
+    State(user_case): State<AuthenticationState>,
+    Extension(user_id): Extension<i32>,
+    Json(model): Json<ChangePasswordModel>,
+) -> impl IntoResponse {
+    match user_case.change_password(user_id, model).await {
+        Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn request_password_reset(
+    State(user_case): State<AuthenticationState>,
+    Json(model): Json<PasswordResetRequestModel>,
+) -> impl IntoResponse {
+    match user_case.request_password_reset(model.username).await {
+        Ok(_) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn confirm_password_reset(
+    State(user_case): State<AuthenticationState>,
+    Json(model): Json<PasswordResetConfirmModel>,
+) -> impl IntoResponse {
+    match user_case.confirm_password_reset(model).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn login_attempts(
+    State(user_case): State<AuthenticationState>,
+    Extension(user_id): Extension<i32>,
+    Query(filter): Query<LoginAttemptFilter>,
+) -> impl IntoResponse {
+    match user_case.search_login_attempts(user_id, filter).await {
+        Ok(attempts) => (StatusCode::OK, Json(attempts)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
-    let repository = BrawlerPostgres::new(db_pool);
-    let user_case = AuthenticationUseCase::new(Arc::new(repository));
+    let repository = BrawlerPostgres::new(Arc::clone(&db_pool));
+    let session_repository = AuthSessionPostgres::new(Arc::clone(&db_pool));
+    let password_reset_repository = PasswordResetPostgres::new(Arc::clone(&db_pool));
+    let mailer =
+        MailerStore::new(get_mail_env().expect("MAIL env is valid")).expect("MAIL transport is valid");
+    let login_attempt_repository = LoginAttemptPostgres::new(Arc::clone(&db_pool));
+    let login_throttle = match get_login_security_env()
+        .expect("LOGIN security env is valid")
+        .throttle_backend
+    {
+        LoginThrottleBackend::Memory => LoginThrottleStore::Memory(InMemoryLoginThrottle::new()),
+        LoginThrottleBackend::Postgres => {
+            LoginThrottleStore::Postgres(LoginThrottlePostgres::new(Arc::clone(&db_pool)))
+        }
+    };
+    let user_case: AuthenticationState = Arc::new(AuthenticationUseCase::new(
+        Arc::new(repository),
+        Arc::new(session_repository),
+        Arc::new(password_reset_repository),
+        Arc::new(mailer),
+        Arc::new(login_attempt_repository),
+        Arc::new(login_throttle),
+        Arc::new(TwoFactorPostgres::new(Arc::clone(&db_pool))),
+    ));
+
+    let protected_routes = Router::new()
+        .route("/logout-all", post(logout_all))
+        .route("/password", patch(change_password))
+        .route("/login-attempts", get(login_attempts))
+        .route("/2fa/enroll", post(enroll_two_factor))
+        .route("/2fa/activate", post(activate_two_factor))
+        .route("/2fa/disable", post(disable_two_factor))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            authorization,
+        ));
 
     Router::new()
         .route("/login", post(login))
+        .route("/2fa/verify", post(verify_two_factor))
         .route("/register", post(register))
-        .with_state(Arc::new(user_case))
+        .route("/refresh", post(refresh))
+        .route("/logout", post(logout))
+        .route("/password-reset/request", post(request_password_reset))
+        .route("/password-reset/confirm", post(confirm_password_reset))
+        .merge(protected_routes)
+        .with_state(user_case)
 }
@@ -7,72 +7,129 @@ use axum::{
 
 use crate::{
     application::use_cases::brawlers::BrawlersUseCase,
-    domain::{
-        repositories::brawlers::BrawlerRepository,
-        value_objects::{uploaded_img::UploadBase64Img, brawler_model::UpdateBrawlerModel},
+    config::config_loader::get_mail_env,
+    domain::value_objects::{
+        brawler_model::UpdateBrawlerModel,
+        email_model::{ChangeEmailModel, EmailDigestModel, VerifyEmailModel},
+        uploaded_img::UploadBase64Img,
     },
     infrastructure::{
-        database::{postgresql_connection::PgPoolSquad, repositories::brawlers::BrawlerPostgres},
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                brawlers::BrawlerPostgres, email_verifications::EmailVerificationPostgres,
+            },
+        },
         http::middlewares::auth::authorization,
+        mail::MailerStore,
     },
 };
 
+pub type BrawlersState =
+    Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationPostgres, MailerStore>>;
+
 pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
-    let repository = BrawlerPostgres::new(db_pool);
-    let user_case = BrawlersUseCase::new(Arc::new(repository));
+    let repository = BrawlerPostgres::new(Arc::clone(&db_pool));
+    let email_verification_repository = EmailVerificationPostgres::new(Arc::clone(&db_pool));
+    let mailer =
+        MailerStore::new(get_mail_env().expect("MAIL env is valid")).expect("MAIL transport is valid");
+    let user_case: BrawlersState = Arc::new(BrawlersUseCase::new(
+        Arc::new(repository),
+        Arc::new(email_verification_repository),
+        Arc::new(mailer),
+    ));
 
     let protected_routes = Router::new()
         .route("/avatar", post(upload_avatar))
         .route("/profile", patch(update_profile))
         .route("/my-missions", get(get_missions))
-        .route_layer(axum::middleware::from_fn(authorization));
+        .route("/email", get(email_settings).post(change_email))
+        .route("/email/digest", patch(set_email_digest))
+        .route_layer(axum::middleware::from_fn_with_state(Arc::clone(&db_pool), authorization));
+
+    // The token alone proves the mailbox, so the link also works from another device
+    let public_routes = Router::new().route("/email/verify", post(verify_email));
 
     Router::new()
         .merge(protected_routes)
-        .with_state(Arc::new(user_case))
+        .merge(public_routes)
+        .with_state(user_case)
 }
 
-pub async fn upload_avatar<T>(
-    State(user_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn upload_avatar(
+    State(user_case): State<BrawlersState>,
     Extension(user_id): Extension<i32>,
     Json(model): Json<UploadBase64Img>,
-) -> impl IntoResponse
-where
-    T: BrawlerRepository + Send + Sync,
-{
+) -> impl IntoResponse {
     match user_case
         .upload_base64img(user_id, model.base64_string)
         .await
     {
         Ok(upload_img) => (StatusCode::OK, Json(upload_img)).into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn get_missions<T>(
-    State(user_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn get_missions(
+    State(user_case): State<BrawlersState>,
     Extension(user_id): Extension<i32>,
-) -> impl IntoResponse
-where
-    T: BrawlerRepository + Send + Sync,
-{
+) -> impl IntoResponse {
     match user_case.get_missions_by_brawler(user_id).await {
         Ok(missions) => (StatusCode::OK, Json(missions)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn update_profile<T>(
-    State(user_case): State<Arc<BrawlersUseCase<T>>>,
+pub async fn update_profile(
+    State(user_case): State<BrawlersState>,
     Extension(user_id): Extension<i32>,
     Json(model): Json<UpdateBrawlerModel>,
-) -> impl IntoResponse
-where
-    T: BrawlerRepository + Send + Sync,
-{
+) -> impl IntoResponse {
     match user_case.update_profile(user_id, model).await {
         Ok(_) => StatusCode::OK.into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn email_settings(
+    State(user_case): State<BrawlersState>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match user_case.email_settings(user_id).await {
+        Ok(settings) => (StatusCode::OK, Json(settings)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn change_email(
+    State(user_case): State<BrawlersState>,
+    Extension(user_id): Extension<i32>,
+    Json(model): Json<ChangeEmailModel>,
+) -> impl IntoResponse {
+    match user_case.change_email(user_id, &model.email).await {
+        Ok(_) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn verify_email(
+    State(user_case): State<BrawlersState>,
+    Json(model): Json<VerifyEmailModel>,
+) -> impl IntoResponse {
+    match user_case.verify_email(model.token).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn set_email_digest(
+    State(user_case): State<BrawlersState>,
+    Extension(user_id): Extension<i32>,
+    Json(model): Json<EmailDigestModel>,
+) -> impl IntoResponse {
+    match user_case.set_email_digest(user_id, model.enabled).await {
+        Ok(settings) => (StatusCode::OK, Json(settings)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -10,10 +10,14 @@ use axum::{
 use serde_json::json;
 
 use crate::{
-    application::use_cases::crew_operation::CrewOperationUseCase, 
+    application::{
+        services::mission_events::MissionEventHub,
+        use_cases::crew_operation::CrewOperationUseCase,
+    },
     domain::repositories::{
         crew_operation::CrewOperationRepository, mission_viewing::MissionViewingRepository,
         mission_submissions::MissionSubmissionsRepository,
+        notifications::NotificationRepository,
     },
     infrastructure::{
         database::{
@@ -21,14 +25,15 @@ use crate::{
             repositories::{
                 crew_operation::CrewOperationPostgres, mission_viewing::MissionViewingPostgres,
                 mission_submissions::MissionSubmissionsPostgres,
+                notifications::NotificationPostgres,
             },
         },
         http::middlewares::auth::authorization,
     },
 };
 
-pub async fn join<T1, T2, T3>(
-    State(user_case): State<Arc<CrewOperationUseCase<T1, T2, T3>>>,
+pub async fn join<T1, T2, T3, T4>(
+    State(user_case): State<Arc<CrewOperationUseCase<T1, T2, T3, T4>>>,
     Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
@@ -36,6 +41,7 @@ where
     T1: CrewOperationRepository + Send + Sync + 'static,
     T2: MissionViewingRepository + Send + Sync,
     T3: MissionSubmissionsRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     match user_case.join(mission_id, user_id).await {
         Ok(_) => (
@@ -44,15 +50,12 @@ where
         )
             .into_response(),
 
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "success": false, "message": e.to_string() })),
-        ).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn leave<T1, T2, T3>(
-    State(user_case): State<Arc<CrewOperationUseCase<T1, T2, T3>>>,   
+pub async fn leave<T1, T2, T3, T4>(
+    State(user_case): State<Arc<CrewOperationUseCase<T1, T2, T3, T4>>>,   
     Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
@@ -60,6 +63,7 @@ where
     T1: CrewOperationRepository + Send + Sync + 'static,
     T2: MissionViewingRepository + Send + Sync,
     T3: MissionSubmissionsRepository + Send + Sync,
+    T4: NotificationRepository + Send + Sync,
 {
     match user_case.leave(mission_id, user_id).await {
         Ok(_) => (
@@ -68,26 +72,26 @@ where
         )
             .into_response(),
 
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "success": false, "message": e.to_string() })),
-        ).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+pub fn routes(db_pool: Arc<PgPoolSquad>, events: Arc<MissionEventHub>) -> Router {
     let crew_operation_repository = CrewOperationPostgres::new(Arc::clone(&db_pool));
     let viewing_repositiory = MissionViewingPostgres::new(Arc::clone(&db_pool));
     let submission_repository = MissionSubmissionsPostgres::new(Arc::clone(&db_pool));
+    let notification_repository = NotificationPostgres::new(Arc::clone(&db_pool));
     let user_case = CrewOperationUseCase::new(
         Arc::new(crew_operation_repository),
         Arc::new(viewing_repositiory),
         Arc::new(submission_repository),
+        Arc::new(notification_repository),
+        events,
     );
 
     Router::new()
         .route("/join/{mission_id}", post(join))
         .route("/leave/{mission_id}", delete(leave))
-        .route_layer(axum::middleware::from_fn(authorization))    
+        .route_layer(axum::middleware::from_fn_with_state(Arc::clone(&db_pool), authorization))    
         .with_state(Arc::new(user_case))
 }
@@ -29,7 +29,7 @@ where
 {
     match use_case.get_summary(user_id).await {
         Ok(summary) => (StatusCode::OK, Json(summary)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -39,6 +39,6 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
 
     Router::new()
         .route("/summary", get(get_summary))
-        .route_layer(axum::middleware::from_fn(authorization))
+        .route_layer(axum::middleware::from_fn_with_state(Arc::clone(&db_pool), authorization))
         .with_state(Arc::new(use_case))
 }
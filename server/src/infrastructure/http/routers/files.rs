@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use serde::Deserialize;
+
+use crate::{
+    config::{config_loader::get_file_storage_env, config_model::FileStorageBackend},
+    domain::{errors::DomainError, services::file_storage::FileStorage},
+    infrastructure::storage::local::LocalFileStorage,
+};
+
+type FilesState = Arc<LocalFileStorage>;
+
+#[derive(Deserialize)]
+pub struct SignedLinkQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// Serves files of the local backend. The signed link is the credential: it is
+/// only handed out to members allowed to see the file and expires on its own.
+pub async fn download(
+    State(storage): State<FilesState>,
+    Path(key): Path<String>,
+    Query(link): Query<SignedLinkQuery>,
+) -> impl IntoResponse {
+    if !storage.verify(&key, link.expires, &link.signature) {
+        return DomainError::forbidden("Download link is invalid or has expired").into_response();
+    }
+
+    match storage.get(&key).await {
+        Ok(Some(file)) => {
+            // Only media the browser renders harmlessly is shown inline, the rest is downloaded
+            let inline = ["image/", "video/", "audio/", "application/pdf"]
+                .iter()
+                .any(|prefix| file.content_type.starts_with(prefix));
+            let disposition = if inline { "inline" } else { "attachment" };
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, file.content_type),
+                    (header::CONTENT_DISPOSITION, disposition.to_string()),
+                    (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+                    (header::CACHE_CONTROL, "private, no-store".to_string()),
+                ],
+                file.bytes,
+            )
+                .into_response()
+        }
+        Ok(None) => DomainError::not_found("File not found").into_response(),
+        Err(e) => DomainError::from(e).into_response(),
+    }
+}
+
+pub fn routes() -> Router {
+    let storage_env = get_file_storage_env().expect("FILE storage env is valid");
+
+    // Cloudinary and S3 hand out links to their own hosts
+    if storage_env.backend != FileStorageBackend::Local {
+        return Router::new();
+    }
+
+    let state: FilesState = Arc::new(LocalFileStorage::new(&storage_env));
+
+    Router::new()
+        .route("/{*key}", get(download))
+        .with_state(state)
+}
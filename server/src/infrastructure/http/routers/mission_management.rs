@@ -9,9 +9,13 @@ use axum::{
 };
 
 use crate::{
-    application::use_cases::mission_management::MissionManagementUseCase,
+    application::{
+        services::mission_events::MissionEventHub,
+        use_cases::mission_management::MissionManagementUseCase,
+    },
     domain::{
         repositories::{
+            crew_operation::CrewOperationRepository,
             mission_management::MissionManagementRepository,
             mission_viewing::MissionViewingRepository,
         },
@@ -21,6 +25,7 @@ use crate::{
         database::{
             postgresql_connection::PgPoolSquad,
             repositories::{
+                crew_operation::CrewOperationPostgres,
                 mission_management::MissionManagementPostgres,
                 mission_viewing::MissionViewingPostgres,
             },
@@ -29,14 +34,15 @@ use crate::{
     },
 };
 
-pub async fn add<T1, T2>(
-    State(user_case): State<Arc<MissionManagementUseCase<T1, T2>>>,
+pub async fn add<T1, T2, T3>(
+    State(user_case): State<Arc<MissionManagementUseCase<T1, T2, T3>>>,
     Extension(user_id): Extension<i32>,
     Json(model): Json<AddMissionModel>,
 ) -> impl IntoResponse
 where
     T1: MissionManagementRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
 {
     match user_case.add(user_id, model).await {
         Ok(mission_id) => (
@@ -45,12 +51,12 @@ where
         )
             .into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn edit<T1, T2>(
-    State(user_case): State<Arc<MissionManagementUseCase<T1, T2>>>,
+pub async fn edit<T1, T2, T3>(
+    State(user_case): State<Arc<MissionManagementUseCase<T1, T2, T3>>>,
     Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
     Json(model): Json<EditMissionModel>,
@@ -58,6 +64,7 @@ pub async fn edit<T1, T2>(
 where
     T1: MissionManagementRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
 {
     match user_case.edit(mission_id, user_id, model).await {
         Ok(mission_id) => (
@@ -66,18 +73,19 @@ where
         )
             .into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn remove<T1, T2>(
-    State(user_case): State<Arc<MissionManagementUseCase<T1, T2>>>,
+pub async fn remove<T1, T2, T3>(
+    State(user_case): State<Arc<MissionManagementUseCase<T1, T2, T3>>>,
     Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: MissionManagementRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: CrewOperationRepository + Send + Sync,
 {
     match user_case.remove(mission_id, user_id).await {
         Ok(_) => (
@@ -86,20 +94,25 @@ where
         )
             .into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+pub fn routes(db_pool: Arc<PgPoolSquad>, events: Arc<MissionEventHub>) -> Router {
     let mission_repository = MissionManagementPostgres::new(Arc::clone(&db_pool));
     let viewing_repositiory = MissionViewingPostgres::new(Arc::clone(&db_pool));
-    let user_case =
-        MissionManagementUseCase::new(Arc::new(mission_repository), Arc::new(viewing_repositiory));
+    let crew_operation_repository = CrewOperationPostgres::new(Arc::clone(&db_pool));
+    let user_case = MissionManagementUseCase::new(
+        Arc::new(mission_repository),
+        Arc::new(viewing_repositiory),
+        Arc::new(crew_operation_repository),
+        events,
+    );
 
     Router::new()
         .route("/", post(add))
         .route("/{mission_id}", patch(edit))
         .route("/{mission_id}", delete(remove))
-        .route_layer(axum::middleware::from_fn(authorization))
+        .route_layer(axum::middleware::from_fn_with_state(Arc::clone(&db_pool), authorization))
         .with_state(Arc::new(user_case))
 }
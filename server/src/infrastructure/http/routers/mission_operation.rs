@@ -1,18 +1,26 @@
 use std::sync::Arc;
 
 use axum::{
-    Extension, Router,
+    Extension, Json, Router,
     extract::{Path, State},
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::patch,
+    routing::{get, patch},
 };
 
 use crate::{
-    application::use_cases::mission_operation::MissionOperationUseCase,
-    domain::repositories::{
-        mission_operation::MissionOperationRepository, mission_viewing::MissionViewingRepository,
+    application::{
+        services::mission_events::MissionEventHub,
+        use_cases::mission_operation::MissionOperationUseCase,
+    },
+    domain::{
+        repositories::{
+            mission_operation::MissionOperationRepository,
+            mission_viewing::MissionViewingRepository,
+            notifications::NotificationRepository,
+        },
+        value_objects::mission_model::MissionTransitionModel,
     },
     infrastructure::{
         database::{
@@ -20,70 +28,119 @@ use crate::{
             repositories::{
                 mission_operation::MissionOperationPostgres,
                 mission_viewing::MissionViewingPostgres,
+                notifications::NotificationPostgres,
             },
         },
         http::middlewares::auth::authorization,
     },
 };
 
-pub async fn in_progress<T1, T2>(
-    State(user_case): State<Arc<MissionOperationUseCase<T1, T2>>>,
+pub async fn in_progress<T1, T2, T3>(
+    State(user_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
     Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     match user_case.in_progress(mission_id, user_id).await {
         Ok(mission_id) => (StatusCode::OK, mission_id.to_string()).into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn to_completed<T1, T2>(
-    State(user_case): State<Arc<MissionOperationUseCase<T1, T2>>>,
+pub async fn to_completed<T1, T2, T3>(
+    State(user_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
     Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     match user_case.to_completed(mission_id, user_id).await {
         Ok(mission_id) => (StatusCode::OK, mission_id.to_string()).into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn to_failed<T1, T2>(
-    State(user_case): State<Arc<MissionOperationUseCase<T1, T2>>>,
+pub async fn to_failed<T1, T2, T3>(
+    State(user_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
     Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T1: MissionOperationRepository + Send + Sync,
     T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
 {
     match user_case.to_failed(mission_id, user_id).await {
         Ok(mission_id) => (StatusCode::OK, mission_id.to_string()).into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn transition<T1, T2, T3>(
+    State(user_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Json(model): Json<MissionTransitionModel>,
+) -> impl IntoResponse
+where
+    T1: MissionOperationRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+{
+    match user_case
+        .transition(mission_id, user_id, model.status, model.reason)
+        .await
+    {
+        Ok(mission_id) => (StatusCode::OK, mission_id.to_string()).into_response(),
+
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn status_history<T1, T2, T3>(
+    State(user_case): State<Arc<MissionOperationUseCase<T1, T2, T3>>>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T1: MissionOperationRepository + Send + Sync,
+    T2: MissionViewingRepository + Send + Sync,
+    T3: NotificationRepository + Send + Sync,
+{
+    match user_case.status_history(mission_id, user_id).await {
+        Ok(history) => (StatusCode::OK, Json(history)).into_response(),
+
+        Err(e) => e.into_response(),
     }
 }
 
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+pub fn routes(db_pool: Arc<PgPoolSquad>, events: Arc<MissionEventHub>) -> Router {
     let mission_repository = MissionOperationPostgres::new(Arc::clone(&db_pool));
     let viewing_repositiory = MissionViewingPostgres::new(Arc::clone(&db_pool));
-    let user_case =
-        MissionOperationUseCase::new(Arc::new(mission_repository), Arc::new(viewing_repositiory));
+    let notification_repository = NotificationPostgres::new(Arc::clone(&db_pool));
+    let user_case = MissionOperationUseCase::new(
+        Arc::new(mission_repository),
+        Arc::new(viewing_repositiory),
+        Arc::new(notification_repository),
+        events,
+    );
 
     Router::new()
         .route("/in-progress/{mission_id}", patch(in_progress))
         .route("/to-completed/{mission_id}", patch(to_completed))
         .route("/to-failed/{mission_id}", patch(to_failed))
-        .route_layer(middleware::from_fn(authorization))
+        .route("/{mission_id}/status", patch(transition))
+        .route("/{mission_id}/status-history", get(status_history))
+        .route_layer(middleware::from_fn_with_state(Arc::clone(&db_pool), authorization))
         .with_state(Arc::new(user_case))
 }
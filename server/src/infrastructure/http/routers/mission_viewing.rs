@@ -31,21 +31,22 @@ where
     match user_case.get_one(mission_id, user_id).await {
         Ok(model) => (StatusCode::OK, Json(model)).into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 pub async fn get_crew<T>(
     State(user_case): State<Arc<MissionViewingUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse
 where
     T: MissionViewingRepository + Send + Sync,
 {
-    match user_case.get_crew(mission_id).await {
+    match user_case.get_crew(mission_id, user_id).await {
         Ok(model) => (StatusCode::OK, Json(model)).into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -61,7 +62,7 @@ where
     match user_case.get_all(&filter, user_id).await {
         Ok(model) => (StatusCode::OK, Json(model)).into_response(),
 
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -74,7 +75,7 @@ where
 {
     match user_case.get_joined(user_id).await {
         Ok(models) => (StatusCode::OK, Json(models)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -83,13 +84,21 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
     let user_case = MissionViewingUseCase::new(Arc::new(viewing_repositiory));
 
     Router::new()
-        .route("/{mission_id}", get(get_one))
-        .route("/filter", get(get_all))
         .route("/crew/{mission_id}", get(get_crew))
-        .route("/joined", get(get_joined))
-        .route_layer(axum::middleware::from_fn(
-            crate::infrastructure::http::middlewares::optional_auth::optional_authorization,
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&db_pool),
+            crate::infrastructure::http::middlewares::auth::authorization,
         ))
+        .merge(
+            Router::new()
+                .route("/{mission_id}", get(get_one))
+                .route("/filter", get(get_all))
+                .route("/joined", get(get_joined))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    Arc::clone(&db_pool),
+                    crate::infrastructure::http::middlewares::optional_auth::optional_authorization,
+                )),
+        )
         .with_state(Arc::new(user_case))
 }
 
@@ -1,6 +1,6 @@
 ﻿use std::sync::Arc;
 use axum::{
-    extract::{Path, State},
+    extract::{DefaultBodyLimit, Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     routing::{delete, get, patch, post},
@@ -9,24 +9,42 @@ use axum::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    application::use_cases::{
-        crew_operation::CrewOperationUseCase,
-        mission_viewing::MissionViewingUseCase
+    application::{
+        services::mission_events::MissionEventHub,
+        use_cases::{
+            comments::CommentUseCase,
+            crew_operation::CrewOperationUseCase,
+            mission_events::MissionEventUseCase,
+            mission_viewing::MissionViewingUseCase,
+            webhooks::WebhookUseCase,
+        },
     },
+    config::config_loader::{get_file_storage_env, get_webhook_env},
     domain::{
-        value_objects::brawler_model::BrawlerModel,
+        value_objects::{
+            brawler_model::BrawlerModel, chief_transfer_model::TransferChiefModel,
+            file_types::FileType,
+            mission_admission_model::{CreateInvitationModel, JoinRequestFilter, JoinRequestModel},
+            mission_visibility::MissionVisibility,
+        },
     },
     infrastructure::{
         database::{
             postgresql_connection::PgPoolSquad,
             repositories::{
+                auth_sessions::AuthSessionPostgres,
+                comments::CommentPostgres,
                 crew_operation::CrewOperationPostgres,
                 mission_submissions::MissionSubmissionsPostgres,
                 mission_viewing::MissionViewingPostgres,
+                notifications::NotificationPostgres,
                 tasks::TaskPostgres,
+                webhooks::WebhookPostgres,
             },
         },
-        http::middlewares::auth::authorization,
+        http::middlewares::auth::{authorization, stream_authorization},
+        storage::FileStorageStore,
+        webhooks::HttpWebhookSender,
     },
 };
 
@@ -45,14 +63,21 @@ pub struct UpdateRoleRequest {
 #[derive(Deserialize)]
 pub struct UpdateSettingsRequest {
     pub max_members: i32,
+    pub visibility: Option<MissionVisibility>,
+    pub max_upload_mb: Option<i32>,
+    pub allowed_file_types: Option<Vec<FileType>>,
+    pub end_date: Option<chrono::NaiveDateTime>,
 }
 
 pub struct WorkspaceState {
-    pub crew_case: Arc<CrewOperationUseCase<CrewOperationPostgres, MissionViewingPostgres, MissionSubmissionsPostgres>>,
+    pub crew_case: Arc<CrewOperationUseCase<CrewOperationPostgres, MissionViewingPostgres, MissionSubmissionsPostgres, NotificationPostgres>>,
     pub view_case: Arc<MissionViewingUseCase<MissionViewingPostgres>>,
-    pub management_case: Arc<crate::application::use_cases::mission_management::MissionManagementUseCase<crate::infrastructure::database::repositories::mission_management::MissionManagementPostgres, crate::infrastructure::database::repositories::mission_viewing::MissionViewingPostgres>>,
-    pub task_case: Arc<crate::application::use_cases::tasks::TaskUseCase<TaskPostgres, MissionViewingPostgres, MissionSubmissionsPostgres>>,
-    pub submission_case: Arc<crate::application::use_cases::mission_submissions::MissionSubmissionUseCase<MissionViewingPostgres, MissionSubmissionsPostgres, TaskPostgres>>,
+    pub management_case: Arc<crate::application::use_cases::mission_management::MissionManagementUseCase<crate::infrastructure::database::repositories::mission_management::MissionManagementPostgres, crate::infrastructure::database::repositories::mission_viewing::MissionViewingPostgres, CrewOperationPostgres>>,
+    pub task_case: Arc<crate::application::use_cases::tasks::TaskUseCase<TaskPostgres, MissionViewingPostgres, MissionSubmissionsPostgres, NotificationPostgres>>,
+    pub submission_case: Arc<crate::application::use_cases::mission_submissions::MissionSubmissionUseCase<MissionViewingPostgres, MissionSubmissionsPostgres, TaskPostgres, FileStorageStore, NotificationPostgres>>,
+    pub comment_case: Arc<CommentUseCase<CommentPostgres, MissionViewingPostgres, TaskPostgres, MissionSubmissionsPostgres>>,
+    pub event_case: Arc<MissionEventUseCase<MissionViewingPostgres, AuthSessionPostgres>>,
+    pub webhook_case: Arc<WebhookUseCase<WebhookPostgres, MissionViewingPostgres, HttpWebhookSender>>,
 }
 
 pub type AppState = Arc<WorkspaceState>;
@@ -63,7 +88,7 @@ pub async fn get_joined(
 ) -> impl IntoResponse {
     match state.view_case.get_joined(user_id).await {
         Ok(models) => (StatusCode::OK, Json(models)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -74,7 +99,7 @@ pub async fn join(
 ) -> impl IntoResponse {
     match state.crew_case.join(mission_id, user_id).await {       
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Joined successfully" }))).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -85,7 +110,7 @@ pub async fn leave(
 ) -> impl IntoResponse {
     match state.crew_case.leave(mission_id, user_id).await {      
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Left successfully" }))).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -97,7 +122,7 @@ pub async fn update_member_role(
 ) -> impl IntoResponse {
     match state.crew_case.update_role(mission_id, brawler_id, payload.role, user_id).await {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Role updated successfully" }))).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -108,7 +133,209 @@ pub async fn kick_member(
 ) -> impl IntoResponse {
     match state.crew_case.kick(mission_id, brawler_id, user_id).await {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Member kicked successfully" }))).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn transfer_chief(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Json(payload): Json<TransferChiefModel>,
+) -> impl IntoResponse {
+    match state.crew_case.transfer_chief(mission_id, user_id, payload).await {
+        Ok(transfer) => (StatusCode::OK, Json(transfer)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_chief_transfers(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.crew_case.chief_transfers(mission_id, user_id).await {
+        Ok(transfers) => (StatusCode::OK, Json(transfers)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn accept_chief_transfer(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, transfer_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.crew_case.accept_chief_transfer(mission_id, transfer_id, user_id).await {
+        Ok(transfer) => (StatusCode::OK, Json(transfer)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn decline_chief_transfer(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, transfer_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.crew_case.decline_chief_transfer(mission_id, transfer_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Transfer declined" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn cancel_chief_transfer(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, transfer_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.crew_case.cancel_chief_transfer(mission_id, transfer_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Transfer cancelled" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn create_invitation(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Json(payload): Json<CreateInvitationModel>,
+) -> impl IntoResponse {
+    match state.crew_case.create_invitation(mission_id, user_id, payload).await {
+        Ok(invitation) => (StatusCode::CREATED, Json(invitation)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_invitations(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.crew_case.invitations(mission_id, user_id).await {
+        Ok(invitations) => (StatusCode::OK, Json(invitations)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn revoke_invitation(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, invitation_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.crew_case.revoke_invitation(mission_id, invitation_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Invitation revoked" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn join_with_invitation(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(code): Path<String>,
+) -> impl IntoResponse {
+    match state.crew_case.join_with_invitation(&code, user_id).await {
+        Ok(mission_id) => (StatusCode::OK, Json(serde_json::json!({ "message": "Joined successfully", "mission_id": mission_id }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn request_to_join(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Json(payload): Json<JoinRequestModel>,
+) -> impl IntoResponse {
+    match state.crew_case.request_to_join(mission_id, user_id, payload).await {
+        Ok(request) => (StatusCode::CREATED, Json(request)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_join_requests(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+    Query(filter): Query<JoinRequestFilter>,
+) -> impl IntoResponse {
+    match state.crew_case.join_requests(mission_id, user_id, &filter).await {
+        Ok(requests) => (StatusCode::OK, Json(requests)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn approve_join_request(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, request_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.crew_case.approve_join_request(mission_id, request_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Join request approved" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn reject_join_request(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, request_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.crew_case.reject_join_request(mission_id, request_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Join request rejected" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn cancel_join_request(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, request_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.crew_case.cancel_join_request(mission_id, request_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Join request cancelled" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn join_waitlist(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.crew_case.join_waitlist(mission_id, user_id).await {
+        Ok(status) => (StatusCode::CREATED, Json(status)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn leave_waitlist(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.crew_case.leave_waitlist(mission_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Left the waitlist" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_waitlist_status(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.crew_case.waitlist_status(mission_id, user_id).await {
+        Ok(status) => (StatusCode::OK, Json(status)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_waitlist(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path(mission_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.crew_case.waitlist(mission_id, user_id).await {
+        Ok(entries) => (StatusCode::OK, Json(entries)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -123,41 +350,49 @@ pub async fn update_settings(
         name: None,
         description: None,
         max_members: Some(payload.max_members),
-        status: None,
+        visibility: payload.visibility,
+        max_upload_mb: payload.max_upload_mb,
+        allowed_file_types: payload.allowed_file_types,
+        end_date: payload.end_date,
+        tags: None,
     };
     match state.management_case.update(mission_id, edit_model, user_id).await {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Settings updated successfully" }))).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 pub async fn get_members(
     State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
     Path(mission_id): Path<i32>,
 ) -> impl IntoResponse {
-    match state.view_case.get_one(mission_id, None).await {       
+    match state.view_case.get_one(mission_id, Some(user_id)).await {
         Ok(mission) => {
-             match state.view_case.get_crew(mission_id).await {   
+             match state.view_case.get_crew(mission_id, user_id).await {
                 Ok(members) => {
                     let count = members.len();
                     (StatusCode::OK, Json(CrewListResponse { members, count, max_count: mission.max_members })).into_response()     
                 }
-                Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+                Err(e) => e.into_response(),
             }
         }
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+pub fn routes(db_pool: Arc<PgPoolSquad>, events: Arc<MissionEventHub>) -> Router {
     let crew_repo = Arc::new(CrewOperationPostgres::new(Arc::clone(&db_pool)));
     let view_repo = Arc::new(MissionViewingPostgres::new(Arc::clone(&db_pool)));
 
     let submission_repo = Arc::new(MissionSubmissionsPostgres::new(Arc::clone(&db_pool)));
+    let notification_repo = Arc::new(NotificationPostgres::new(Arc::clone(&db_pool)));
     let crew_case = Arc::new(CrewOperationUseCase::new(
         Arc::clone(&crew_repo),
         Arc::clone(&view_repo),
         Arc::clone(&submission_repo),
+        Arc::clone(&notification_repo),
+        Arc::clone(&events),
     ));
 
     let task_repo = Arc::new(TaskPostgres::new(Arc::clone(&db_pool)));
@@ -165,6 +400,8 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
         Arc::clone(&task_repo),
         Arc::clone(&view_repo),
         Arc::clone(&submission_repo),
+        Arc::clone(&notification_repo),
+        Arc::clone(&events),
     ));
     
     let view_case = Arc::new(MissionViewingUseCase::new(Arc::clone(&view_repo)));
@@ -173,6 +410,8 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
     let management_case = Arc::new(crate::application::use_cases::mission_management::MissionManagementUseCase::new(
         Arc::clone(&management_repo),
         Arc::clone(&view_repo),
+        Arc::clone(&crew_repo),
+        Arc::clone(&events),
     ));
 
     let submission_repo = Arc::new(MissionSubmissionsPostgres::new(Arc::clone(&db_pool)));
@@ -180,6 +419,30 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
         Arc::clone(&view_repo),
         Arc::clone(&submission_repo),
         Arc::clone(&task_repo),
+        Arc::new(FileStorageStore::new(get_file_storage_env().expect("FILE storage env is valid")).expect("FILE storage is valid")),
+        Arc::clone(&notification_repo),
+        Arc::clone(&events),
+    ));
+
+    let comment_case = Arc::new(CommentUseCase::new(
+        Arc::new(CommentPostgres::new(Arc::clone(&db_pool))),
+        Arc::clone(&view_repo),
+        Arc::clone(&task_repo),
+        Arc::clone(&submission_repo),
+    ));
+
+    let event_case = Arc::new(MissionEventUseCase::new(
+        Arc::clone(&view_repo),
+        Arc::new(AuthSessionPostgres::new(Arc::clone(&db_pool))),
+        events,
+    ));
+
+    let webhook_env = get_webhook_env().expect("WEBHOOK env is valid");
+    let webhook_case = Arc::new(WebhookUseCase::new(
+        Arc::new(WebhookPostgres::new(Arc::clone(&db_pool))),
+        Arc::clone(&view_repo),
+        Arc::new(HttpWebhookSender::new(&webhook_env).expect("Webhook client is valid")),
+        webhook_env.max_attempts,
     ));
 
     let state: AppState = Arc::new(WorkspaceState {
@@ -188,6 +451,9 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
         management_case,
         task_case,
         submission_case,
+        comment_case,
+        event_case,
+        webhook_case,
     });
 
     Router::new()
@@ -198,14 +464,54 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
         .route("/{mission_id}/members/{brawler_id}/role", post(update_member_role))
         .route("/{mission_id}/members/{brawler_id}/kick", delete(kick_member))
         .route("/{mission_id}/settings", post(update_settings))
-        .route("/{mission_id}/submit", post(crate::infrastructure::http::handlers::mission_submission::submit_work))
+        .route("/{mission_id}/chief-transfers", get(get_chief_transfers).post(transfer_chief))
+        .route("/{mission_id}/chief-transfers/{transfer_id}/accept", post(accept_chief_transfer))
+        .route("/{mission_id}/chief-transfers/{transfer_id}/decline", post(decline_chief_transfer))
+        .route("/{mission_id}/chief-transfers/{transfer_id}/cancel", post(cancel_chief_transfer))
+        .route("/{mission_id}/invitations", get(get_invitations).post(create_invitation))
+        .route("/{mission_id}/invitations/{invitation_id}", delete(revoke_invitation))
+        .route("/invitations/{code}/join", post(join_with_invitation))
+        .route("/{mission_id}/join-requests", get(get_join_requests).post(request_to_join))
+        .route("/{mission_id}/join-requests/{request_id}/approve", post(approve_join_request))
+        .route("/{mission_id}/join-requests/{request_id}/reject", post(reject_join_request))
+        .route("/{mission_id}/join-requests/{request_id}/cancel", post(cancel_join_request))
+        .route("/{mission_id}/waitlist", get(get_waitlist_status).post(join_waitlist).delete(leave_waitlist))
+        .route("/{mission_id}/waitlist/entries", get(get_waitlist))
+        // Uploads are streamed and capped per mission, so the request body limit does not apply
+        .route("/{mission_id}/submit", post(crate::infrastructure::http::handlers::mission_submission::submit_work).layer(DefaultBodyLimit::disable()))
         .route("/{mission_id}/submissions", get(crate::infrastructure::http::handlers::mission_submission::get_mission_submissions))
+        .route("/{mission_id}/submissions/pending-review", get(crate::infrastructure::http::handlers::mission_submission::get_pending_review))
+        .route("/{mission_id}/submissions/{submission_id}/review", post(crate::infrastructure::http::handlers::mission_submission::review_submission))
         .route("/{mission_id}/submissions/{submission_id}", delete(crate::infrastructure::http::handlers::mission_submission::delete_submission))
         .route("/{mission_id}/submissions/{submission_id}/details", patch(crate::infrastructure::http::handlers::mission_submission::update_submission_details))
         .route("/{mission_id}/tasks/{task_id}/submission", get(crate::infrastructure::http::handlers::mission_submission::get_task_submission))
+        .route("/{mission_id}/tasks/{task_id}/submissions", get(crate::infrastructure::http::handlers::mission_submission::get_task_versions))
+        .route("/{mission_id}/tasks/{task_id}/submissions/diff", get(crate::infrastructure::http::handlers::mission_submission::diff_task_versions))
+        .route("/{mission_id}/tasks/{task_id}/submissions/{version}", get(crate::infrastructure::http::handlers::mission_submission::get_task_version))
+        // Comment Routes
+        .route("/{mission_id}/tasks/{task_id}/comments", get(crate::infrastructure::http::handlers::comments::get_task_comments).post(crate::infrastructure::http::handlers::comments::add_task_comment))
+        .route("/{mission_id}/submissions/{submission_id}/comments", get(crate::infrastructure::http::handlers::comments::get_submission_comments).post(crate::infrastructure::http::handlers::comments::add_submission_comment))
+        .route("/{mission_id}/comments/{comment_id}", patch(crate::infrastructure::http::handlers::comments::edit_comment).delete(crate::infrastructure::http::handlers::comments::delete_comment))
+        .route("/{mission_id}/comments/{comment_id}/history", get(crate::infrastructure::http::handlers::comments::get_comment_history))
+        // Webhook Routes
+        .route("/{mission_id}/webhooks", get(crate::infrastructure::http::handlers::webhooks::get_webhooks).post(crate::infrastructure::http::handlers::webhooks::create_webhook))
+        .route("/{mission_id}/webhooks/{webhook_id}", patch(crate::infrastructure::http::handlers::webhooks::edit_webhook).delete(crate::infrastructure::http::handlers::webhooks::delete_webhook))
+        .route("/{mission_id}/webhooks/{webhook_id}/deliveries", get(crate::infrastructure::http::handlers::webhooks::get_webhook_deliveries))
+        .route("/{mission_id}/webhooks/{webhook_id}/test", post(crate::infrastructure::http::handlers::webhooks::send_test_event))
         // Task Routes
         .route("/{mission_id}/tasks", get(crate::infrastructure::http::routers::tasks::get_tasks).post(crate::infrastructure::http::routers::tasks::create_task))
         .route("/{mission_id}/tasks/{task_id}", patch(crate::infrastructure::http::routers::tasks::update_task).delete(crate::infrastructure::http::routers::tasks::delete_task))
-        .route_layer(axum::middleware::from_fn(authorization))
+        .route("/{mission_id}/tasks/{task_id}/status", patch(crate::infrastructure::http::routers::tasks::change_task_status))
+        .route("/{mission_id}/tasks/{task_id}/claim", post(crate::infrastructure::http::routers::tasks::claim_task))
+        .route("/{mission_id}/tasks/{task_id}/dependencies", post(crate::infrastructure::http::routers::tasks::add_dependency))
+        .route("/{mission_id}/tasks/{task_id}/dependencies/{blocked_by_task_id}", delete(crate::infrastructure::http::routers::tasks::remove_dependency))
+        .route("/{mission_id}/events/ticket", post(crate::infrastructure::http::handlers::mission_events::issue_stream_ticket))
+        .route_layer(axum::middleware::from_fn_with_state(Arc::clone(&db_pool), authorization))
+        // EventSource clients can't send headers, so streams authenticate with a ticket from the route above
+        .merge(
+            Router::new()
+                .route("/{mission_id}/events", get(crate::infrastructure::http::handlers::mission_events::stream_events))
+                .route_layer(axum::middleware::from_fn_with_state(Arc::clone(&db_pool), stream_authorization)),
+        )
         .with_state(state)
 }
@@ -3,8 +3,11 @@ pub mod brawlers;
 pub mod crew_operation;
 pub mod dashboard;
 pub mod default_router;
+pub mod files;
 pub mod mission_management;
 pub mod mission_operation;
 pub mod mission_viewing;
 pub mod mission_workspace;
+pub mod notifications;
+pub mod search;
 pub mod tasks;
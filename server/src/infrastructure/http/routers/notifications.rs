@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use serde_json::json;
+
+use crate::{
+    application::use_cases::notifications::NotificationUseCase,
+    domain::{
+        repositories::notifications::NotificationRepository,
+        value_objects::notification_model::{NotificationFilter, UpdateNotificationPreferenceModel},
+    },
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::notifications::NotificationPostgres,
+        },
+        http::middlewares::auth::authorization,
+    },
+};
+
+pub async fn list<T>(
+    State(use_case): State<Arc<NotificationUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
+    Query(filter): Query<NotificationFilter>,
+) -> impl IntoResponse
+where
+    T: NotificationRepository + Send + Sync,
+{
+    match use_case.list(user_id, filter).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn unread_count<T>(
+    State(use_case): State<Arc<NotificationUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T: NotificationRepository + Send + Sync,
+{
+    match use_case.unread_count(user_id).await {
+        Ok(count) => (StatusCode::OK, Json(count)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn mark_read<T>(
+    State(use_case): State<Arc<NotificationUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
+    Path(notification_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T: NotificationRepository + Send + Sync,
+{
+    match use_case.set_read(notification_id, user_id, true).await {
+        Ok(notification) => (StatusCode::OK, Json(notification)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn mark_unread<T>(
+    State(use_case): State<Arc<NotificationUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
+    Path(notification_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T: NotificationRepository + Send + Sync,
+{
+    match use_case.set_read(notification_id, user_id, false).await {
+        Ok(notification) => (StatusCode::OK, Json(notification)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn mark_all_read<T>(
+    State(use_case): State<Arc<NotificationUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T: NotificationRepository + Send + Sync,
+{
+    match use_case.mark_all_read(user_id).await {
+        Ok(marked) => (StatusCode::OK, Json(json!({ "marked": marked }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_preferences<T>(
+    State(use_case): State<Arc<NotificationUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse
+where
+    T: NotificationRepository + Send + Sync,
+{
+    match use_case.preferences(user_id).await {
+        Ok(preferences) => (StatusCode::OK, Json(preferences)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_preferences<T>(
+    State(use_case): State<Arc<NotificationUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
+    Json(preferences): Json<Vec<UpdateNotificationPreferenceModel>>,
+) -> impl IntoResponse
+where
+    T: NotificationRepository + Send + Sync,
+{
+    match use_case.update_preferences(user_id, preferences).await {
+        Ok(preferences) => (StatusCode::OK, Json(preferences)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+    let repository = NotificationPostgres::new(Arc::clone(&db_pool));
+    let use_case = NotificationUseCase::new(Arc::new(repository));
+
+    Router::new()
+        .route("/", get(list))
+        .route("/unread-count", get(unread_count))
+        .route("/read-all", post(mark_all_read))
+        .route("/preferences", get(get_preferences).patch(update_preferences))
+        .route("/{notification_id}/read", post(mark_read))
+        .route("/{notification_id}/unread", post(mark_unread))
+        .route_layer(axum::middleware::from_fn_with_state(Arc::clone(&db_pool), authorization))
+        .with_state(Arc::new(use_case))
+}
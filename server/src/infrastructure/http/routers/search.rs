@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+};
+
+use crate::{
+    application::use_cases::search::SearchUseCase,
+    domain::{repositories::search::SearchRepository, value_objects::search_model::SearchQuery},
+    infrastructure::{
+        database::{postgresql_connection::PgPoolSquad, repositories::search::SearchPostgres},
+        http::middlewares::auth::authorization,
+    },
+};
+
+pub async fn search<T>(
+    State(use_case): State<Arc<SearchUseCase<T>>>,
+    Extension(user_id): Extension<i32>,
+    Query(query): Query<SearchQuery>,
+) -> impl IntoResponse
+where
+    T: SearchRepository + Send + Sync,
+{
+    match use_case.search(user_id, query).await {
+        Ok(results) => (StatusCode::OK, Json(results)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+    let repository = SearchPostgres::new(Arc::clone(&db_pool));
+    let use_case = SearchUseCase::new(Arc::new(repository));
+
+    Router::new()
+        .route("/", get(search))
+        .route_layer(axum::middleware::from_fn_with_state(Arc::clone(&db_pool), authorization))
+        .with_state(Arc::new(use_case))
+}
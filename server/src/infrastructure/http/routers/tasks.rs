@@ -1,13 +1,11 @@
-use std::sync::Arc;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::IntoResponse,
-    routing::{delete, get, patch, post},
-    Extension, Json, Router,
+    Extension, Json,
 };
 use crate::infrastructure::http::routers::mission_workspace::AppState;
-use crate::domain::value_objects::task_model::{CreateTaskModel, UpdateTaskModel};
+use crate::domain::value_objects::task_model::{AddTaskDependencyModel, CreateTaskModel, TaskStatusModel, UpdateTaskModel};
 
 pub async fn create_task(
     State(state): State<AppState>,
@@ -17,7 +15,7 @@ pub async fn create_task(
 ) -> impl IntoResponse {
     match state.task_case.create(mission_id, user_id, payload).await {
         Ok(task) => (StatusCode::CREATED, Json(task)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -29,7 +27,30 @@ pub async fn update_task(
 ) -> impl IntoResponse {
     match state.task_case.update(task_id, user_id, payload).await {
         Ok(task) => (StatusCode::OK, Json(task)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn change_task_status(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id)): Path<(i32, i32)>,
+    Json(payload): Json<TaskStatusModel>,
+) -> impl IntoResponse {
+    match state.task_case.change_status(mission_id, task_id, user_id, payload.status).await {
+        Ok(task) => (StatusCode::OK, Json(task)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn claim_task(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id)): Path<(i32, i32)>,
+) -> impl IntoResponse {
+    match state.task_case.claim(mission_id, task_id, user_id).await {
+        Ok(task) => (StatusCode::OK, Json(task)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -40,7 +61,7 @@ pub async fn delete_task(
 ) -> impl IntoResponse {
     match state.task_case.delete(task_id, user_id).await {
         Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Task deleted successfully" }))).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -51,6 +72,29 @@ pub async fn get_tasks(
 ) -> impl IntoResponse {
     match state.task_case.get_by_mission(mission_id, user_id).await {
         Ok(tasks) => (StatusCode::OK, Json(tasks)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn add_dependency(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id)): Path<(i32, i32)>,
+    Json(payload): Json<AddTaskDependencyModel>,
+) -> impl IntoResponse {
+    match state.task_case.add_dependency(mission_id, task_id, user_id, payload).await {
+        Ok(_) => (StatusCode::CREATED, Json(serde_json::json!({ "message": "Dependency added" }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn remove_dependency(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i32>,
+    Path((mission_id, task_id, blocked_by_task_id)): Path<(i32, i32, i32)>,
+) -> impl IntoResponse {
+    match state.task_case.remove_dependency(mission_id, task_id, blocked_by_task_id, user_id).await {
+        Ok(_) => (StatusCode::OK, Json(serde_json::json!({ "message": "Dependency removed" }))).into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tokio::time::{Duration, Instant, MissedTickBehavior};
+
+use crate::{
+    application::use_cases::file_cleanup::FileCleanupUseCase,
+    config::config_loader::{get_cloudinary_env, get_file_cleanup_env, get_file_storage_env},
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                brawlers::BrawlerPostgres, file_deletions::FileDeletionPostgres,
+                mission_submissions::MissionSubmissionsPostgres,
+            },
+        },
+        storage::{FileStorageStore, cloudinary::CloudinaryStorage},
+    },
+};
+
+/// Starts the background loops that drain the file deletion queue and
+/// periodically sweep storage for orphaned files.
+pub fn spawn(db_pool: Arc<PgPoolSquad>) -> Result<()> {
+    let cleanup_env = get_file_cleanup_env()?;
+    let file_storage = FileStorageStore::new(get_file_storage_env()?)?;
+    let avatar_storage = get_cloudinary_env()
+        .ok()
+        .map(|cloud_env| Arc::new(CloudinaryStorage::new(cloud_env)));
+
+    let use_case = Arc::new(FileCleanupUseCase::new(
+        Arc::new(FileDeletionPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(MissionSubmissionsPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(BrawlerPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(file_storage),
+        avatar_storage,
+    ));
+
+    let deletions = Arc::clone(&use_case);
+    let poll = Duration::from_secs(cleanup_env.poll_seconds.max(1));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(poll);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match deletions.process_deletions().await {
+                Ok(0) => {}
+                Ok(removed) => tracing::info!("Deleted {} queued file(s)", removed),
+                Err(e) => tracing::error!("File deletion queue failed: {:?}", e),
+            }
+        }
+    });
+
+    if cleanup_env.reconcile_hours > 0 {
+        let period = Duration::from_secs(cleanup_env.reconcile_hours * 60 * 60);
+        let grace = chrono::Duration::minutes(cleanup_env.grace_minutes);
+        tokio::spawn(async move {
+            // Listing a whole bucket is expensive, so the first sweep waits a full period
+            let mut ticker = tokio::time::interval_at(Instant::now() + period, period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = use_case.reconcile(grace).await {
+                    tracing::error!("File reconciliation failed: {:?}", e);
+                }
+            }
+        });
+    }
+
+    Ok(())
+}
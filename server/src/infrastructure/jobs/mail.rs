@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tokio::time::{Duration, MissedTickBehavior};
+
+use crate::{
+    application::use_cases::email_notifications::EmailNotificationUseCase,
+    config::config_loader::{get_email_notification_env, get_mail_env},
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{
+                mail_outbox::MailOutboxPostgres, mission_management::MissionManagementPostgres,
+                mission_viewing::MissionViewingPostgres, notifications::NotificationPostgres,
+            },
+        },
+        mail::MailerStore,
+    },
+};
+
+/// Starts the loop that reminds crews of deadlines, queues daily digests
+/// and sends the queued mail.
+pub fn spawn(db_pool: Arc<PgPoolSquad>) -> Result<()> {
+    let env = get_email_notification_env()?;
+    let poll = Duration::from_secs(env.poll_seconds.max(1));
+    let use_case = EmailNotificationUseCase::new(
+        Arc::new(MailOutboxPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(NotificationPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(MissionManagementPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(MissionViewingPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(MailerStore::new(get_mail_env()?)?),
+        env,
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(poll);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+
+            // Reminders and digests only queue mail, so they go first and leave with this tick
+            match use_case.remind_deadlines().await {
+                Ok(0) => {}
+                Ok(reminded) => tracing::info!("Reminded {} crew(s) of their deadline", reminded),
+                Err(e) => tracing::error!("Deadline reminders failed: {:?}", e),
+            }
+            match use_case.queue_digests().await {
+                Ok(0) => {}
+                Ok(queued) => tracing::info!("Queued {} daily digest(s)", queued),
+                Err(e) => tracing::error!("Daily digests failed: {:?}", e),
+            }
+            if let Err(e) = use_case.process_outbox().await {
+                tracing::error!("Mail delivery failed: {:?}", e);
+            }
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod file_cleanup;
+pub mod mail;
+pub mod webhooks;
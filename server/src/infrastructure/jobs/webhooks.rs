@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tokio::{
+    sync::{Notify, broadcast::error::RecvError},
+    time::{Duration, MissedTickBehavior},
+};
+
+use crate::{
+    application::{services::mission_events::MissionEventHub, use_cases::webhooks::WebhookUseCase},
+    config::config_loader::get_webhook_env,
+    infrastructure::{
+        database::{
+            postgresql_connection::PgPoolSquad,
+            repositories::{mission_viewing::MissionViewingPostgres, webhooks::WebhookPostgres},
+        },
+        webhooks::HttpWebhookSender,
+    },
+};
+
+/// Starts the loops that turn published mission events into webhook
+/// deliveries and keep sending the ones that are due. Only the second loop
+/// talks to receivers, so a slow one can't hold up reading the event stream.
+pub fn spawn(db_pool: Arc<PgPoolSquad>, events: Arc<MissionEventHub>) -> Result<()> {
+    let webhook_env = get_webhook_env()?;
+    let use_case = Arc::new(WebhookUseCase::new(
+        Arc::new(WebhookPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(MissionViewingPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(HttpWebhookSender::new(&webhook_env)?),
+        webhook_env.max_attempts,
+    ));
+
+    let dispatcher = Arc::clone(&use_case);
+    let wake_poller = Arc::new(Notify::new());
+    let wake = Arc::clone(&wake_poller);
+    let mut receiver = events.subscribe_all();
+    tokio::spawn(async move {
+        loop {
+            let envelope = match receiver.recv().await {
+                Ok(envelope) => envelope,
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::error!("Webhook dispatcher fell behind and missed {} event(s)", missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+
+            match dispatcher.enqueue_event(&envelope).await {
+                Ok(0) => {}
+                // Send right away instead of waiting for the next poll
+                Ok(_) => wake.notify_one(),
+                Err(e) => tracing::error!("Failed to queue webhook deliveries: {:?}", e),
+            }
+        }
+    });
+
+    let poll = Duration::from_secs(webhook_env.poll_seconds.max(1));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(poll);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = wake_poller.notified() => {}
+            }
+            if let Err(e) = use_case.process_deliveries().await {
+                tracing::error!("Webhook delivery failed: {:?}", e);
+            }
+        }
+    });
+
+    Ok(())
+}
@@ -4,14 +4,15 @@ use chrono::Duration;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
-use crate::config::config_loader::get_jwt_env;
+use crate::config::config_loader::{get_jwt_env, get_totp_env};
 use crate::infrastructure::jwt::generate_token;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Passport {
-    // pub token_type: String,
+    pub token_type: String,
     pub token: String,
-    // pub expires_in: usize,
+    pub expires_in: usize,
+    pub refresh_token: String,
     pub display_name: String,
     pub avatar_url: Option<String>,
     pub id: i32,
@@ -22,20 +23,27 @@ pub struct Passport {
 impl Passport {
     pub fn new(
         user_id: i32,
+        session_id: i32,
+        refresh_token: String,
         display_name: String,
         avatar_url: Option<String>,
         bio: Option<String>,
         created_at: Option<String>,
     ) -> Result<Self> {
         let jwt_env = get_jwt_env()?;
+        let expires_in = Duration::minutes(jwt_env.access_ttl);
         let claims = Claims {
             sub: user_id.to_string(),
-            exp: (Utc::now() + Duration::days(jwt_env.ttl)).timestamp() as usize,
+            sid: session_id,
+            exp: (Utc::now() + expires_in).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
         };
         let token = generate_token(jwt_env.secret, &claims)?;
         Ok(Self {
+            token_type: "Bearer".to_string(),
             token,
+            expires_in: expires_in.num_seconds() as usize,
+            refresh_token,
             display_name,
             avatar_url,
             id: user_id,
@@ -45,9 +53,102 @@ impl Passport {
     }
 }
 
+/// Unknown fields are refused so a purpose-bound token, which also has a
+/// `sub` and a `sid`, can't pass as an access token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Claims {
     pub sub: String,
+    pub sid: i32,
     pub exp: usize,
     pub iat: usize,
 }
+
+pub const TWO_FACTOR_PURPOSE: &str = "two_factor";
+
+/// Handed out after the password check when the account has 2FA enabled.
+/// It has no `sid`, so it can never pass as an access token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeClaims {
+    pub sub: String,
+    pub purpose: String,
+    pub exp: usize,
+    pub iat: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactorChallenge {
+    pub two_factor_required: bool,
+    pub challenge_token: String,
+    pub expires_in: usize,
+}
+
+impl TwoFactorChallenge {
+    pub fn new(user_id: i32) -> Result<Self> {
+        let jwt_env = get_jwt_env()?;
+        let expires_in = Duration::seconds(get_totp_env()?.challenge_ttl);
+        let claims = ChallengeClaims {
+            sub: user_id.to_string(),
+            purpose: TWO_FACTOR_PURPOSE.to_string(),
+            exp: (Utc::now() + expires_in).timestamp() as usize,
+            iat: Utc::now().timestamp() as usize,
+        };
+        let challenge_token = generate_token(jwt_env.secret, &claims)?;
+        Ok(Self {
+            two_factor_required: true,
+            challenge_token,
+            expires_in: expires_in.num_seconds() as usize,
+        })
+    }
+}
+
+pub const STREAM_PURPOSE: &str = "mission_stream";
+
+/// Opens one mission's event stream. `EventSource` can't send headers, so this
+/// goes in the URL instead of the access token: it is short-lived, bound to the
+/// mission, and carries the access token's expiry as `session_exp` so the
+/// stream ends when the token it was issued for would.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamClaims {
+    pub sub: String,
+    pub sid: i32,
+    pub mission_id: i32,
+    pub purpose: String,
+    pub session_exp: usize,
+    pub exp: usize,
+    pub iat: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamTicket {
+    pub ticket: String,
+    pub expires_in: usize,
+}
+
+impl StreamTicket {
+    pub fn new(user_id: i32, session_id: i32, mission_id: i32, session_exp: usize) -> Result<Self> {
+        let jwt_env = get_jwt_env()?;
+        let expires_in = Duration::seconds(jwt_env.stream_ticket_ttl);
+        let claims = StreamClaims {
+            sub: user_id.to_string(),
+            sid: session_id,
+            mission_id,
+            purpose: STREAM_PURPOSE.to_string(),
+            session_exp,
+            exp: (Utc::now() + expires_in).timestamp() as usize,
+            iat: Utc::now().timestamp() as usize,
+        };
+        let ticket = generate_token(jwt_env.secret, &claims)?;
+        Ok(Self {
+            ticket,
+            expires_in: expires_in.num_seconds() as usize,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    Passport(Passport),
+    Challenge(TwoFactorChallenge),
+}
@@ -2,9 +2,13 @@ pub mod authentication_model;
 pub mod jwt_model;
 
 use anyhow::{Ok, Result};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{Engine, engine::general_purpose};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 
-pub fn generate_token(secret: String, claims: &jwt_model::Claims) -> Result<String> {
+pub fn generate_token<T: Serialize>(secret: String, claims: &T) -> Result<String> {
     let token = encode(
         &Header::default(),
         claims,
@@ -23,3 +27,43 @@ pub fn verify_token(secret: String, token: String) -> Result<jwt_model::Claims>
 
     Ok(token.claims)
 }
+
+pub fn verify_challenge_token(secret: String, token: String) -> Result<jwt_model::ChallengeClaims> {
+    let token = decode::<jwt_model::ChallengeClaims>(
+        &token,
+        &DecodingKey::from_secret(secret.as_ref()),
+        &Validation::default(),
+    )?;
+
+    if token.claims.purpose != jwt_model::TWO_FACTOR_PURPOSE {
+        anyhow::bail!("Token is not a two-factor challenge");
+    }
+
+    Ok(token.claims)
+}
+
+pub fn verify_stream_ticket(secret: String, token: String) -> Result<jwt_model::StreamClaims> {
+    let token = decode::<jwt_model::StreamClaims>(
+        &token,
+        &DecodingKey::from_secret(secret.as_ref()),
+        &Validation::default(),
+    )?;
+
+    if token.claims.purpose != jwt_model::STREAM_PURPOSE {
+        anyhow::bail!("Token is not a stream ticket");
+    }
+
+    Ok(token.claims)
+}
+
+pub fn generate_opaque_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// Refresh and reset tokens are high-entropy random values, so a plain digest
+// is enough to keep them unusable if the table leaks.
+pub fn hash_opaque_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
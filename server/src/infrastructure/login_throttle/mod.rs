@@ -0,0 +1,88 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::{
+    domain::services::login_throttle::{FailureRecord, LoginThrottle},
+    infrastructure::database::repositories::login_throttles::LoginThrottlePostgres,
+};
+
+/// Keeps failure counters in process memory. Counters are lost on restart and
+/// are not shared between instances; use the Postgres store for that.
+#[derive(Default)]
+pub struct InMemoryLoginThrottle {
+    records: Mutex<HashMap<String, FailureRecord>>,
+}
+
+impl InMemoryLoginThrottle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LoginThrottle for InMemoryLoginThrottle {
+    async fn get(&self, key: &str) -> Result<Option<FailureRecord>> {
+        let records = self.records.lock().map_err(|_| anyhow!("login throttle lock poisoned"))?;
+        Ok(records.get(key).cloned())
+    }
+
+    async fn register_failure(&self, key: &str, window: Duration) -> Result<FailureRecord> {
+        let now = Utc::now().naive_utc();
+        let mut records = self.records.lock().map_err(|_| anyhow!("login throttle lock poisoned"))?;
+
+        // Drop stale counters so spraying random usernames can't grow the map forever
+        records.retain(|_, record| now - record.last_failure_at <= window);
+
+        let record = records
+            .entry(key.to_string())
+            .and_modify(|record| {
+                record.failures += 1;
+                record.last_failure_at = now;
+            })
+            .or_insert(FailureRecord {
+                failures: 1,
+                last_failure_at: now,
+            });
+
+        Ok(record.clone())
+    }
+
+    async fn reset(&self, key: &str) -> Result<()> {
+        let mut records = self.records.lock().map_err(|_| anyhow!("login throttle lock poisoned"))?;
+        records.remove(key);
+        Ok(())
+    }
+}
+
+/// The backend picked by `LOGIN_THROTTLE_STORE`.
+pub enum LoginThrottleStore {
+    Memory(InMemoryLoginThrottle),
+    Postgres(LoginThrottlePostgres),
+}
+
+#[async_trait]
+impl LoginThrottle for LoginThrottleStore {
+    async fn get(&self, key: &str) -> Result<Option<FailureRecord>> {
+        match self {
+            LoginThrottleStore::Memory(store) => store.get(key).await,
+            LoginThrottleStore::Postgres(store) => store.get(key).await,
+        }
+    }
+
+    async fn register_failure(&self, key: &str, window: Duration) -> Result<FailureRecord> {
+        match self {
+            LoginThrottleStore::Memory(store) => store.register_failure(key, window).await,
+            LoginThrottleStore::Postgres(store) => store.register_failure(key, window).await,
+        }
+    }
+
+    async fn reset(&self, key: &str) -> Result<()> {
+        match self {
+            LoginThrottleStore::Memory(store) => store.reset(key).await,
+            LoginThrottleStore::Postgres(store) => store.reset(key).await,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    config::config_model::MailEnv,
+    domain::services::mailer::{MailMessage, Mailer},
+};
+
+/// Drops every message into a local outbox directory instead of sending it.
+/// Meant for local runs and tests where no mail server is available.
+pub struct FileMailer {
+    from: String,
+    outbox_dir: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(mail_env: MailEnv) -> Self {
+        Self {
+            from: mail_env.from,
+            outbox_dir: PathBuf::from(mail_env.outbox_dir),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        tokio::fs::create_dir_all(&self.outbox_dir)
+            .await
+            .context(format!("create outbox {}", self.outbox_dir.display()))?;
+
+        let recipient: String = message
+            .to
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let path = self.outbox_dir.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%d%H%M%S%3f"),
+            recipient
+        ));
+
+        let content = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\n\r\n{}\r\n",
+            self.from, message.to, message.subject, message.body
+        );
+        tokio::fs::write(&path, content)
+            .await
+            .context(format!("write mail to {}", path.display()))?;
+
+        tracing::info!("Mail to {} dropped in {}", message.to, path.display());
+        Ok(())
+    }
+}
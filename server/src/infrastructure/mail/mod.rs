@@ -0,0 +1,41 @@
+pub mod file;
+pub mod smtp;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use crate::{
+    config::config_model::{MailEnv, MailTransport},
+    domain::services::mailer::{MailMessage, Mailer},
+};
+
+use self::{file::FileMailer, smtp::SmtpMailer};
+
+/// The transport picked by `MAIL_TRANSPORT`.
+pub enum MailerStore {
+    File(FileMailer),
+    Smtp(Box<SmtpMailer>),
+}
+
+impl MailerStore {
+    pub fn new(mail_env: MailEnv) -> Result<Self> {
+        let store = match mail_env.transport {
+            MailTransport::File => MailerStore::File(FileMailer::new(mail_env)),
+            MailTransport::Smtp => MailerStore::Smtp(Box::new(SmtpMailer::new(
+                mail_env.from,
+                mail_env.smtp.context("SMTP env is missing")?,
+            )?)),
+        };
+        Ok(store)
+    }
+}
+
+#[async_trait]
+impl Mailer for MailerStore {
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        match self {
+            MailerStore::File(mailer) => mailer.send(message).await,
+            MailerStore::Smtp(mailer) => mailer.send(message).await,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::header::ContentType,
+    transport::smtp::{authentication::Credentials, extension::ClientId},
+};
+
+use crate::{
+    config::config_model::{SmtpEnv, SmtpSecurity},
+    domain::services::mailer::{MailMessage, Mailer},
+};
+
+static MESSAGE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Hands every message to an SMTP relay. Credentials are only ever sent over
+/// TLS; the config loader refuses a username without it.
+pub struct SmtpMailer {
+    from: String,
+    host: String,
+    timeout: Duration,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailer {
+    pub fn new(from: String, smtp_env: SmtpEnv) -> Result<Self> {
+        let host = smtp_env.host.as_str();
+        let mut builder = match smtp_env.security {
+            SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                .context("create STARTTLS transport")?,
+            SmtpSecurity::Tls => {
+                AsyncSmtpTransport::<Tokio1Executor>::relay(host).context("create TLS transport")?
+            }
+        };
+
+        let timeout = Duration::from_secs(smtp_env.timeout_seconds);
+        builder = builder
+            .port(smtp_env.port)
+            .timeout(Some(timeout))
+            .hello_name(ClientId::Domain(smtp_env.hello_name.clone()));
+        if let Some(username) = &smtp_env.username {
+            let password = smtp_env.password.clone().unwrap_or_default();
+            builder = builder.credentials(Credentials::new(username.clone(), password));
+        }
+
+        Ok(Self {
+            from,
+            host: smtp_env.host.clone(),
+            timeout,
+            transport: builder.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        let email = build_message(&self.from, &message)?;
+
+        tokio::time::timeout(self.timeout, self.transport.send(email))
+            .await
+            .context(format!("SMTP delivery to {} timed out", message.to))?
+            .context(format!("SMTP delivery to {}", message.to))?;
+
+        tracing::info!("Mail to {} handed to {}", message.to, self.host);
+        Ok(())
+    }
+}
+
+/// Builds a plain-text message; the builder takes care of header encoding and dot-stuffing.
+fn build_message(from: &str, message: &MailMessage) -> Result<Message> {
+    let domain = from.rsplit('@').next().unwrap_or("localhost");
+    let message_id = format!(
+        "<{}.{}@{}>",
+        Utc::now().timestamp_nanos_opt().unwrap_or_default(),
+        MESSAGE_COUNTER.fetch_add(1, Ordering::Relaxed),
+        domain
+    );
+
+    Message::builder()
+        .from(from.parse().context("invalid sender address")?)
+        .to(message.to.parse().context("invalid recipient address")?)
+        .subject(message.subject.clone())
+        .message_id(Some(message_id))
+        .header(ContentType::TEXT_PLAIN)
+        .body(message.body.clone())
+        .context("build mail message")
+}
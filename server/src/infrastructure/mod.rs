@@ -2,4 +2,10 @@ pub mod argon2;
 pub mod cloudinary;
 pub mod database;
 pub mod http;
+pub mod jobs;
 pub mod jwt;
+pub mod login_throttle;
+pub mod mail;
+pub mod storage;
+pub mod totp;
+pub mod webhooks;
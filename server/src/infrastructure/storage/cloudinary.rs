@@ -0,0 +1,363 @@
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+
+use crate::{
+    config::config_model::CloudinaryEnv,
+    domain::services::file_storage::{ByteSource, FileObject, FileStorage, StoredFile, StoredObject},
+    infrastructure::{
+        cloudinary::{UploadImageOptions, form_builder},
+        jwt::generate_opaque_token,
+    },
+};
+
+use super::{fill_buffer, validate_key};
+
+// Cloudinary wants every chunk but the last to be at least 5 MB
+const CHUNK_SIZE: usize = 6 * 1024 * 1024;
+// The most the Admin API hands back per page
+const LIST_PAGE_SIZE: &str = "500";
+const RESOURCE_TYPES: [&str; 3] = ["image", "video", "raw"];
+// Delivered only through signed, expiring links
+const AUTHENTICATED: &str = "authenticated";
+// Avatars and files stored before authenticated uploads are public
+const PUBLIC: &str = "upload";
+const DELIVERY_TYPES: [&str; 2] = [PUBLIC, AUTHENTICATED];
+// Lifetime of the links `get` downloads through
+const FETCH_LINK_MINUTES: i64 = 5;
+
+#[derive(Debug, Deserialize)]
+struct CloudinaryUpload {
+    secure_url: String,
+    public_id: String,
+    resource_type: String,
+    format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CloudinaryResource {
+    public_id: String,
+    format: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// What a storage key names on Cloudinary.
+struct CloudinaryAsset<'a> {
+    delivery_type: &'a str,
+    resource_type: &'a str,
+    public_id: &'a str,
+    format: Option<&'a str>,
+}
+
+impl<'a> CloudinaryAsset<'a> {
+    /// Public assets are keyed `<resource_type>/<public_id>`, authenticated ones
+    /// `authenticated/<resource_type>/<public_id>[.<format>]`, since their download
+    /// links need the format and raw public ids already carry it.
+    fn parse(key: &'a str) -> Result<Self> {
+        validate_key(key)?;
+        let (delivery_type, rest) = match key.strip_prefix("authenticated/") {
+            Some(rest) => (AUTHENTICATED, rest),
+            None => (PUBLIC, key),
+        };
+        let (resource_type, id) = rest
+            .split_once('/')
+            .context(format!("Not a Cloudinary key: {}", key))?;
+
+        let (public_id, format) = match id.rsplit_once('.') {
+            Some((public_id, format))
+                if delivery_type == AUTHENTICATED && resource_type != "raw" && !format.contains('/') =>
+            {
+                (public_id, Some(format))
+            }
+            _ => (id, None),
+        };
+        Ok(Self {
+            delivery_type,
+            resource_type,
+            public_id,
+            format,
+        })
+    }
+
+    fn key(delivery_type: &str, resource_type: &str, public_id: &str, format: Option<&str>) -> String {
+        if delivery_type != AUTHENTICATED {
+            return format!("{}/{}", resource_type, public_id);
+        }
+        match format.filter(|_| resource_type != "raw") {
+            Some(format) => format!("{}/{}/{}.{}", AUTHENTICATED, resource_type, public_id, format),
+            None => format!("{}/{}/{}", AUTHENTICATED, resource_type, public_id),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CloudinaryResources {
+    resources: Vec<CloudinaryResource>,
+    next_cursor: Option<String>,
+}
+
+/// Stores files as Cloudinary assets. Cloudinary picks the public id, so the
+/// returned key is `<resource_type>/<public_id>` rather than the requested one.
+pub struct CloudinaryStorage {
+    cloud_env: CloudinaryEnv,
+    client: reqwest::Client,
+}
+
+impl CloudinaryStorage {
+    pub fn new(cloud_env: CloudinaryEnv) -> Self {
+        Self {
+            cloud_env,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Signs API parameters the way Cloudinary expects: sorted `key=value` pairs
+    /// joined by `&`, followed by the API secret, hashed with SHA-1.
+    fn sign(&self, params: &[(&str, &str)]) -> String {
+        let mut params = params.to_vec();
+        params.sort();
+        let joined: Vec<String> = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+
+        let mut hasher = Sha1::new();
+        hasher.update(joined.join("&"));
+        hasher.update(&self.cloud_env.api_secret);
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn delivery_url(&self, asset: &CloudinaryAsset) -> String {
+        format!(
+            "https://res.cloudinary.com/{}/{}/upload/{}",
+            self.cloud_env.cloud_name, asset.resource_type, asset.public_id
+        )
+    }
+
+    /// A Download API link that stops working at `expires_at`.
+    fn download_url(&self, asset: &CloudinaryAsset, expires_at: DateTime<Utc>) -> Result<String> {
+        let expires_at = expires_at.timestamp().to_string();
+        let timestamp = Utc::now().timestamp().to_string();
+        let mut params = vec![
+            ("expires_at", expires_at.as_str()),
+            ("public_id", asset.public_id),
+            ("timestamp", timestamp.as_str()),
+            ("type", asset.delivery_type),
+        ];
+        if let Some(format) = asset.format {
+            params.push(("format", format));
+        }
+        let signature = self.sign(&params);
+        params.push(("api_key", &self.cloud_env.api_key));
+        params.push(("signature", &signature));
+
+        let url = reqwest::Url::parse_with_params(
+            &format!(
+                "https://api.cloudinary.com/v1_1/{}/{}/download",
+                self.cloud_env.cloud_name, asset.resource_type
+            ),
+            &params,
+        )?;
+        Ok(url.to_string())
+    }
+}
+
+#[async_trait]
+impl FileStorage for CloudinaryStorage {
+    /// Sends the file in chunks sharing one upload id; Cloudinary answers the
+    /// last chunk with the finished asset.
+    async fn put(&self, key: &str, source: &mut dyn ByteSource, content_type: &str) -> Result<StoredFile> {
+        validate_key(key)?;
+        let (folder, file_name) = match key.rsplit_once('/') {
+            Some((folder, file_name)) => (Some(folder.to_string()), file_name.to_string()),
+            None => (None, key.to_string()),
+        };
+        let upload_id = generate_opaque_token();
+        let url = format!(
+            "https://api.cloudinary.com/v1_1/{}/auto/upload",
+            self.cloud_env.cloud_name
+        );
+
+        let mut buffer = Vec::new();
+        let mut offset = 0;
+        loop {
+            let more = fill_buffer(source, &mut buffer, CHUNK_SIZE + 1).await?;
+            let rest = if more { buffer.split_off(CHUNK_SIZE) } else { Vec::new() };
+            let chunk = std::mem::replace(&mut buffer, rest);
+
+            let end = offset + chunk.len();
+            // The total is only known once the source runs dry
+            let total = if more { "-1".to_string() } else { end.to_string() };
+            let content_range = format!("bytes {}-{}/{}", offset, end.saturating_sub(1), total);
+            offset = end;
+
+            let form = form_builder(
+                UploadImageOptions {
+                    folder: folder.clone(),
+                    public_id: None,
+                    transformation: None,
+                    delivery_type: Some(AUTHENTICATED.to_string()),
+                },
+                &self.cloud_env,
+                "auto",
+            )?
+            .part(
+                "file",
+                Part::bytes(chunk)
+                    .file_name(file_name.clone())
+                    .mime_str(content_type)?,
+            );
+
+            let response = self
+                .client
+                .post(&url)
+                .header("X-Unique-Upload-Id", &upload_id)
+                .header(reqwest::header::CONTENT_RANGE, content_range)
+                .multipart(form)
+                .send()
+                .await
+                .context(format!("upload to {}", url))?;
+            let text = response.error_for_status()?.text().await?;
+
+            if !more {
+                let uploaded: CloudinaryUpload =
+                    serde_json::from_str(&text).context(format!("failed to parse:\n\n {}", text))?;
+                return Ok(StoredFile {
+                    key: CloudinaryAsset::key(
+                        AUTHENTICATED,
+                        &uploaded.resource_type,
+                        &uploaded.public_id,
+                        uploaded.format.as_deref(),
+                    ),
+                    url: uploaded.secure_url,
+                });
+            }
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<FileObject>> {
+        let asset = CloudinaryAsset::parse(key)?;
+        let url = if asset.delivery_type == AUTHENTICATED {
+            self.download_url(&asset, Utc::now() + Duration::minutes(FETCH_LINK_MINUTES))?
+        } else {
+            self.delivery_url(&asset)
+        };
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context(format!("download {}", url))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let bytes = response.bytes().await?.to_vec();
+
+        Ok(Some(FileObject {
+            bytes,
+            content_type,
+        }))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let asset = CloudinaryAsset::parse(key)?;
+        let timestamp = Utc::now().timestamp().to_string();
+        let signature = self.sign(&[
+            ("public_id", asset.public_id),
+            ("timestamp", &timestamp),
+            ("type", asset.delivery_type),
+        ]);
+
+        let form = Form::new()
+            .text("public_id", asset.public_id.to_string())
+            .text("timestamp", timestamp)
+            .text("type", asset.delivery_type.to_string())
+            .text("api_key", self.cloud_env.api_key.clone())
+            .text("signature", signature);
+
+        let url = format!(
+            "https://api.cloudinary.com/v1_1/{}/{}/destroy",
+            self.cloud_env.cloud_name, asset.resource_type
+        );
+        self.client
+            .post(&url)
+            .multipart(form)
+            .send()
+            .await
+            .context(format!("destroy via {}", url))?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Files are uploaded as authenticated assets, so only the signed Download API
+    /// link reaches them. Public assets have no link that expires.
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String> {
+        let asset = CloudinaryAsset::parse(key)?;
+        if asset.delivery_type != AUTHENTICATED {
+            bail!("{} is a public Cloudinary asset, it has no expiring link", key);
+        }
+        self.download_url(&asset, Utc::now() + expires_in)
+    }
+
+    /// Asks the Admin API for each delivery and resource type in turn; `prefix`
+    /// is matched against public ids, which is what keys look like without the types.
+    async fn list(&self, prefix: &str) -> Result<Vec<StoredObject>> {
+        let mut objects = Vec::new();
+        for delivery_type in DELIVERY_TYPES {
+            for resource_type in RESOURCE_TYPES {
+                let url = format!(
+                    "https://api.cloudinary.com/v1_1/{}/resources/{}/{}",
+                    self.cloud_env.cloud_name, resource_type, delivery_type
+                );
+                let mut cursor: Option<String> = None;
+                loop {
+                    let mut query = vec![("prefix", prefix), ("max_results", LIST_PAGE_SIZE)];
+                    if let Some(cursor) = &cursor {
+                        query.push(("next_cursor", cursor));
+                    }
+                    let text = self
+                        .client
+                        .get(&url)
+                        .basic_auth(&self.cloud_env.api_key, Some(&self.cloud_env.api_secret))
+                        .query(&query)
+                        .send()
+                        .await
+                        .context(format!("list {}", url))?
+                        .error_for_status()?
+                        .text()
+                        .await?;
+                    let page: CloudinaryResources =
+                        serde_json::from_str(&text).context(format!("failed to parse:\n\n {}", text))?;
+
+                    objects.extend(page.resources.into_iter().map(|resource| StoredObject {
+                        key: CloudinaryAsset::key(
+                            delivery_type,
+                            resource_type,
+                            &resource.public_id,
+                            resource.format.as_deref(),
+                        ),
+                        last_modified: resource.created_at,
+                    }));
+
+                    cursor = page.next_cursor;
+                    if cursor.is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(objects)
+    }
+}
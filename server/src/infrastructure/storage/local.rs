@@ -0,0 +1,193 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    config::config_model::FileStorageEnv,
+    domain::services::file_storage::{ByteSource, FileObject, FileStorage, StoredFile, StoredObject},
+};
+
+use super::{encode_key, validate_key};
+
+/// Keeps files on the local disk and serves them through the `/files` route.
+/// Meant for local runs and setups without an object store.
+pub struct LocalFileStorage {
+    root: PathBuf,
+    public_base_url: String,
+    signing_secret: String,
+}
+
+impl LocalFileStorage {
+    pub fn new(storage_env: &FileStorageEnv) -> Self {
+        Self {
+            root: PathBuf::from(&storage_env.local_dir),
+            public_base_url: storage_env.public_base_url.clone(),
+            signing_secret: storage_env.signing_secret.clone(),
+        }
+    }
+
+    fn path_of(&self, key: &str) -> Result<PathBuf> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    fn link_mac(&self, key: &str, expires: i64) -> Result<Hmac<Sha256>> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.signing_secret.as_bytes())
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        mac.update(format!("{}:{}", key, expires).as_bytes());
+        Ok(mac)
+    }
+
+    /// Checks a link produced by `signed_url`.
+    pub fn verify(&self, key: &str, expires: i64, signature: &str) -> bool {
+        if expires < Utc::now().timestamp() {
+            return false;
+        }
+        match (self.link_mac(key, expires), decode_hex(signature)) {
+            // Constant time, so the signature can't be guessed byte by byte
+            (Ok(mac), Some(signature)) => mac.verify_slice(&signature).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+async fn write_file(path: &std::path::Path, source: &mut dyn ByteSource) -> Result<()> {
+    let mut file = tokio::fs::File::create(path)
+        .await
+        .context(format!("create {}", path.display()))?;
+    while let Some(chunk) = source.next_chunk().await? {
+        file.write_all(&chunk)
+            .await
+            .context(format!("write {}", path.display()))?;
+    }
+    file.flush().await?;
+    Ok(())
+}
+
+/// Walks the tree under `root` and returns every file, keyed relative to it.
+async fn walk(root: &std::path::Path) -> Result<Vec<StoredObject>> {
+    let mut objects = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).context(format!("list {}", dir.display())),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if metadata.is_dir() {
+                pending.push(entry.path());
+                continue;
+            }
+            let path = entry.path();
+            let Some(key) = path
+                .strip_prefix(root)
+                .ok()
+                .and_then(|relative| relative.to_str())
+            else {
+                continue;
+            };
+            objects.push(StoredObject {
+                key: key.replace(std::path::MAIN_SEPARATOR, "/"),
+                last_modified: DateTime::<Utc>::from(metadata.modified()?),
+            });
+        }
+    }
+    Ok(objects)
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[async_trait]
+impl FileStorage for LocalFileStorage {
+    async fn put(&self, key: &str, source: &mut dyn ByteSource, _content_type: &str) -> Result<StoredFile> {
+        let path = self.path_of(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .context(format!("create {}", parent.display()))?;
+        }
+
+        // Written next to the target first so a broken upload never shows up under its key
+        let partial = path.with_file_name(format!(
+            "{}.part",
+            path.file_name().and_then(|name| name.to_str()).unwrap_or_default()
+        ));
+        if let Err(e) = write_file(&partial, source).await {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(e);
+        }
+        tokio::fs::rename(&partial, &path)
+            .await
+            .context(format!("move {} into place", path.display()))?;
+
+        Ok(StoredFile {
+            key: key.to_string(),
+            url: format!("{}/{}", self.public_base_url, encode_key(key)),
+        })
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<FileObject>> {
+        let path = self.path_of(key)?;
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).context(format!("read {}", path.display())),
+        };
+
+        // Nothing is stored next to the file, so sniff the type back from its content
+        let content_type = infer::get(&bytes)
+            .map(|kind| kind.mime_type().to_string())
+            .unwrap_or("application/octet-stream".to_string());
+
+        Ok(Some(FileObject {
+            bytes,
+            content_type,
+        }))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.path_of(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e).context(format!("delete {}", path.display())),
+        }
+    }
+
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String> {
+        validate_key(key)?;
+        let expires = (Utc::now() + expires_in).timestamp();
+        let signature = self.link_mac(key, expires)?.finalize().into_bytes();
+
+        Ok(format!(
+            "{}/{}?expires={}&signature={:x}",
+            self.public_base_url,
+            encode_key(key),
+            expires,
+            signature
+        ))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<StoredObject>> {
+        let objects = walk(&self.root).await?;
+        Ok(objects
+            .into_iter()
+            .filter(|object| object.key.starts_with(prefix))
+            .collect())
+    }
+}
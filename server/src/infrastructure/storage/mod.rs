@@ -0,0 +1,115 @@
+pub mod cloudinary;
+pub mod local;
+pub mod s3;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Duration;
+
+use crate::{
+    config::config_model::{FileStorageBackend, FileStorageEnv},
+    domain::services::file_storage::{ByteSource, FileObject, FileStorage, StoredFile, StoredObject},
+};
+
+use self::{cloudinary::CloudinaryStorage, local::LocalFileStorage, s3::S3FileStorage};
+
+/// The backend picked by `FILE_STORAGE`.
+pub enum FileStorageStore {
+    Cloudinary(CloudinaryStorage),
+    Local(LocalFileStorage),
+    S3(S3FileStorage),
+}
+
+impl FileStorageStore {
+    pub fn new(storage_env: FileStorageEnv) -> Result<Self> {
+        let store = match storage_env.backend {
+            FileStorageBackend::Cloudinary => FileStorageStore::Cloudinary(CloudinaryStorage::new(
+                storage_env.cloudinary.context("Cloudinary env is missing")?,
+            )),
+            FileStorageBackend::Local => FileStorageStore::Local(LocalFileStorage::new(&storage_env)),
+            FileStorageBackend::S3 => FileStorageStore::S3(S3FileStorage::new(
+                storage_env.s3.context("S3 env is missing")?,
+            )?),
+        };
+        Ok(store)
+    }
+}
+
+#[async_trait]
+impl FileStorage for FileStorageStore {
+    async fn put(&self, key: &str, source: &mut dyn ByteSource, content_type: &str) -> Result<StoredFile> {
+        match self {
+            FileStorageStore::Cloudinary(store) => store.put(key, source, content_type).await,
+            FileStorageStore::Local(store) => store.put(key, source, content_type).await,
+            FileStorageStore::S3(store) => store.put(key, source, content_type).await,
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<FileObject>> {
+        match self {
+            FileStorageStore::Cloudinary(store) => store.get(key).await,
+            FileStorageStore::Local(store) => store.get(key).await,
+            FileStorageStore::S3(store) => store.get(key).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self {
+            FileStorageStore::Cloudinary(store) => store.delete(key).await,
+            FileStorageStore::Local(store) => store.delete(key).await,
+            FileStorageStore::S3(store) => store.delete(key).await,
+        }
+    }
+
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String> {
+        match self {
+            FileStorageStore::Cloudinary(store) => store.signed_url(key, expires_in).await,
+            FileStorageStore::Local(store) => store.signed_url(key, expires_in).await,
+            FileStorageStore::S3(store) => store.signed_url(key, expires_in).await,
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<StoredObject>> {
+        match self {
+            FileStorageStore::Cloudinary(store) => store.list(prefix).await,
+            FileStorageStore::Local(store) => store.list(prefix).await,
+            FileStorageStore::S3(store) => store.list(prefix).await,
+        }
+    }
+}
+
+/// Keys end up in file paths and URLs, so only plain relative segments are accepted.
+pub fn validate_key(key: &str) -> Result<()> {
+    let valid = !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && !segment.contains('\\')
+                && !segment.chars().any(char::is_control)
+        });
+    if !valid {
+        anyhow::bail!("Invalid storage key: {}", key);
+    }
+    Ok(())
+}
+
+/// Pulls from `source` until `buffer` holds at least `size` bytes or the source runs dry.
+/// Returns `false` once the source is exhausted.
+pub async fn fill_buffer(source: &mut dyn ByteSource, buffer: &mut Vec<u8>, size: usize) -> Result<bool> {
+    while buffer.len() < size {
+        match source.next_chunk().await? {
+            Some(chunk) => buffer.extend_from_slice(&chunk),
+            None => return Ok(false),
+        }
+    }
+    Ok(true)
+}
+
+/// Percent-encodes every segment of a key while keeping the `/` separators.
+pub fn encode_key(key: &str) -> String {
+    key.split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
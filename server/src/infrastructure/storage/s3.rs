@@ -0,0 +1,429 @@
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::config_model::S3Env,
+    domain::services::file_storage::{ByteSource, FileObject, FileStorage, StoredFile, StoredObject},
+};
+
+use super::{encode_key, fill_buffer, validate_key};
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+// Presigned links may not outlive a week
+const MAX_PRESIGN_SECONDS: i64 = 7 * 24 * 60 * 60;
+// S3 wants every part but the last to be at least 5 MiB
+const PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Talks to any S3-compatible object store (AWS, MinIO, ...) with
+/// Signature Version 4 signed requests.
+pub struct S3FileStorage {
+    s3_env: S3Env,
+    endpoint: Url,
+    client: reqwest::Client,
+}
+
+impl S3FileStorage {
+    pub fn new(s3_env: S3Env) -> Result<Self> {
+        let endpoint = Url::parse(&s3_env.endpoint).context("S3_ENDPOINT is not a URL")?;
+        if endpoint.host_str().is_none() {
+            anyhow::bail!("S3_ENDPOINT has no host");
+        }
+
+        Ok(Self {
+            s3_env,
+            endpoint,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    fn host(&self) -> String {
+        let host = self.endpoint.host_str().unwrap_or_default();
+        let host = if self.s3_env.path_style {
+            host.to_string()
+        } else {
+            format!("{}.{}", self.s3_env.bucket, host)
+        };
+        match self.endpoint.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host,
+        }
+    }
+
+    fn canonical_uri(&self, key: &str) -> String {
+        if self.s3_env.path_style {
+            format!("/{}/{}", urlencoding::encode(&self.s3_env.bucket), encode_key(key))
+        } else {
+            format!("/{}", encode_key(key))
+        }
+    }
+
+    fn object_url(&self, key: &str) -> String {
+        format!("{}://{}{}", self.endpoint.scheme(), self.host(), self.canonical_uri(key))
+    }
+
+    fn scope(&self, now: DateTime<Utc>) -> String {
+        format!("{}/{}/s3/aws4_request", now.format("%Y%m%d"), self.s3_env.region)
+    }
+
+    fn signature(&self, now: DateTime<Utc>, canonical_request: &str) -> Result<String> {
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{:x}",
+            ALGORITHM,
+            now.format("%Y%m%dT%H%M%SZ"),
+            self.scope(now),
+            Sha256::digest(canonical_request.as_bytes())
+        );
+
+        let mut key = hmac_sha256(
+            format!("AWS4{}", self.s3_env.secret_key).as_bytes(),
+            now.format("%Y%m%d").to_string().as_bytes(),
+        )?;
+        for part in [self.s3_env.region.as_str(), "s3", "aws4_request"] {
+            key = hmac_sha256(&key, part.as_bytes())?;
+        }
+
+        Ok(hex(&hmac_sha256(&key, string_to_sign.as_bytes())?))
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, &str)],
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response> {
+        validate_key(key)?;
+        self.send_signed(method, key, query, body, content_type).await
+    }
+
+    /// Signs and sends a request for `key`, or for the bucket itself when `key` is empty.
+    async fn send_signed(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, &str)],
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response> {
+        let query = canonical_query(query);
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = format!("{:x}", Sha256::digest(&body));
+        let host = self.host();
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method,
+            self.canonical_uri(key),
+            query,
+            host,
+            payload_hash,
+            amz_date,
+            payload_hash
+        );
+        let authorization = format!(
+            "{} Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+            ALGORITHM,
+            self.s3_env.access_key,
+            self.scope(now),
+            self.signature(now, &canonical_request)?
+        );
+
+        let url = match query.is_empty() {
+            true => self.object_url(key),
+            false => format!("{}?{}", self.object_url(key), query),
+        };
+        let mut request = self
+            .client
+            .request(method.clone(), &url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header(reqwest::header::AUTHORIZATION, authorization);
+        if let Some(content_type) = content_type {
+            request = request.header(reqwest::header::CONTENT_TYPE, content_type);
+        }
+
+        request
+            .body(body)
+            .send()
+            .await
+            .context(format!("{} {}", method, url))
+    }
+}
+
+/// Parameters sorted by name and percent-encoded, as SigV4 signs them.
+fn canonical_query(query: &[(&str, &str)]) -> String {
+    let mut pairs: Vec<String> = query
+        .iter()
+        .map(|(name, value)| format!("{}={}", urlencoding::encode(name), urlencoding::encode(value)))
+        .collect();
+    pairs.sort();
+    pairs.join("&")
+}
+
+/// Pulls a single element's text out of the small XML documents S3 answers with.
+fn xml_value(xml: &str, element: &str) -> Option<String> {
+    xml_values(xml, element).into_iter().next()
+}
+
+/// Every occurrence of `element`, in document order, with entities decoded.
+fn xml_values(xml: &str, element: &str) -> Vec<String> {
+    let open = format!("<{}>", element);
+    let close = format!("</{}>", element);
+    let mut values = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        let Some(end) = rest.find(&close) else {
+            break;
+        };
+        values.push(
+            rest[..end]
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&"),
+        );
+        rest = &rest[end + close.len()..];
+    }
+    values
+}
+
+impl S3FileStorage {
+    async fn put_object(&self, key: &str, body: Vec<u8>, content_type: &str) -> Result<()> {
+        let response = self.send(Method::PUT, key, &[], body, Some(content_type)).await?;
+        ensure_success(response).await?;
+        Ok(())
+    }
+
+    async fn put_multipart(
+        &self,
+        key: &str,
+        first_part: Vec<u8>,
+        source: &mut dyn ByteSource,
+        content_type: &str,
+    ) -> Result<()> {
+        let response = self
+            .send(Method::POST, key, &[("uploads", "")], Vec::new(), Some(content_type))
+            .await?;
+        let xml = ensure_success(response).await?.text().await?;
+        let upload_id = xml_value(&xml, "UploadId").context("S3 did not return an UploadId")?;
+
+        match self.upload_parts(key, &upload_id, first_part, source).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // Parts of an abandoned upload are billed until aborted
+                if let Err(abort) = self
+                    .send(Method::DELETE, key, &[("uploadId", &upload_id)], Vec::new(), None)
+                    .await
+                {
+                    tracing::warn!("Failed to abort S3 upload {}: {}", upload_id, abort);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn upload_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+        first_part: Vec<u8>,
+        source: &mut dyn ByteSource,
+    ) -> Result<()> {
+        let mut etags = Vec::new();
+        let mut part = first_part;
+        let mut more = true;
+        loop {
+            let mut next = part.split_off(part.len().min(PART_SIZE));
+            let part_number = (etags.len() + 1).to_string();
+            let response = self
+                .send(
+                    Method::PUT,
+                    key,
+                    &[("partNumber", &part_number), ("uploadId", upload_id)],
+                    part,
+                    None,
+                )
+                .await?;
+            let response = ensure_success(response).await?;
+            let etag = response
+                .headers()
+                .get(reqwest::header::ETAG)
+                .and_then(|value| value.to_str().ok())
+                .context("S3 did not return an ETag")?
+                .to_string();
+            etags.push(etag);
+
+            if more {
+                more = fill_buffer(source, &mut next, PART_SIZE).await?;
+            }
+            if next.is_empty() {
+                break;
+            }
+            part = next;
+        }
+
+        let parts: String = etags
+            .iter()
+            .enumerate()
+            .map(|(i, etag)| format!("<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>", i + 1, etag))
+            .collect();
+        let body = format!("<CompleteMultipartUpload>{}</CompleteMultipartUpload>", parts);
+        let response = self
+            .send(Method::POST, key, &[("uploadId", upload_id)], body.into_bytes(), Some("application/xml"))
+            .await?;
+        let xml = ensure_success(response).await?.text().await?;
+        // A completion can still fail after a 200, with the error in the body
+        if xml.contains("<Error>") {
+            anyhow::bail!("S3 could not complete the upload: {}", xml);
+        }
+        Ok(())
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|e| anyhow!(e.to_string()))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+async fn ensure_success(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(anyhow!("S3 answered {}: {}", status, body))
+}
+
+#[async_trait]
+impl FileStorage for S3FileStorage {
+    async fn put(&self, key: &str, source: &mut dyn ByteSource, content_type: &str) -> Result<StoredFile> {
+        validate_key(key)?;
+        let mut first_part = Vec::new();
+        // Anything that fits in one part goes up in a single request
+        if fill_buffer(source, &mut first_part, PART_SIZE + 1).await? {
+            self.put_multipart(key, first_part, source, content_type).await?;
+        } else {
+            self.put_object(key, first_part, content_type).await?;
+        }
+
+        Ok(StoredFile {
+            key: key.to_string(),
+            url: self.object_url(key),
+        })
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<FileObject>> {
+        let response = self.send(Method::GET, key, &[], Vec::new(), None).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = ensure_success(response).await?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let bytes = response.bytes().await?.to_vec();
+
+        Ok(Some(FileObject {
+            bytes,
+            content_type,
+        }))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let response = self.send(Method::DELETE, key, &[], Vec::new(), None).await?;
+        // S3 answers 204 whether or not the object existed
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        ensure_success(response).await?;
+        Ok(())
+    }
+
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String> {
+        validate_key(key)?;
+        let now = Utc::now();
+        let expires = expires_in.num_seconds().clamp(1, MAX_PRESIGN_SECONDS);
+
+        // Already sorted by name, as the canonical query string requires
+        let query = [
+            ("X-Amz-Algorithm", ALGORITHM.to_string()),
+            (
+                "X-Amz-Credential",
+                format!("{}/{}", self.s3_env.access_key, self.scope(now)),
+            ),
+            ("X-Amz-Date", now.format("%Y%m%dT%H%M%SZ").to_string()),
+            ("X-Amz-Expires", expires.to_string()),
+            ("X-Amz-SignedHeaders", "host".to_string()),
+        ]
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+        let canonical_request = format!(
+            "GET\n{}\n{}\nhost:{}\n\nhost\n{}",
+            self.canonical_uri(key),
+            query,
+            self.host(),
+            UNSIGNED_PAYLOAD
+        );
+
+        Ok(format!(
+            "{}?{}&X-Amz-Signature={}",
+            self.object_url(key),
+            query,
+            self.signature(now, &canonical_request)?
+        ))
+    }
+
+    /// Pages through ListObjectsV2 until S3 says the listing is complete.
+    async fn list(&self, prefix: &str) -> Result<Vec<StoredObject>> {
+        let mut objects = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", prefix)];
+            if let Some(token) = &continuation {
+                query.push(("continuation-token", token));
+            }
+            let response = self.send_signed(Method::GET, "", &query, Vec::new(), None).await?;
+            let xml = ensure_success(response).await?.text().await?;
+
+            for contents in xml_values(&xml, "Contents") {
+                let key = xml_value(&contents, "Key").context("S3 listed an object without a Key")?;
+                let last_modified = xml_value(&contents, "LastModified")
+                    .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+                    .context(format!("S3 listed {} without a LastModified", key))?;
+                objects.push(StoredObject {
+                    key,
+                    last_modified: last_modified.with_timezone(&Utc),
+                });
+            }
+
+            continuation = match xml_value(&xml, "IsTruncated").as_deref() {
+                Some("true") => xml_value(&xml, "NextContinuationToken"),
+                _ => None,
+            };
+            if continuation.is_none() {
+                return Ok(objects);
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use reqwest::{
+    Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect::Policy,
+};
+
+use crate::{
+    config::config_model::WebhookEnv,
+    domain::services::webhook_sender::{WebhookRequest, WebhookResponse, WebhookSender},
+};
+
+/// Posts webhook payloads over plain HTTP(S). Redirects are not followed, so a
+/// receiver can't bounce a signed payload somewhere else.
+///
+/// Unless `allow_private_targets` is set, receivers on loopback, private and
+/// link-local addresses are refused, both when a URL is saved and when it is
+/// connected to, so a webhook can't be used to probe the server's own network.
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+    allow_private_targets: bool,
+}
+
+impl HttpWebhookSender {
+    pub fn new(webhook_env: &WebhookEnv) -> Result<Self> {
+        let resolver = PublicResolver {
+            allow_private_targets: webhook_env.allow_private_targets,
+        };
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(webhook_env.timeout_seconds.max(1)))
+            .redirect(Policy::none())
+            // A proxy would resolve the host itself and skip the address check
+            .no_proxy()
+            .dns_resolver(Arc::new(resolver))
+            .build()?;
+        Ok(Self {
+            client,
+            allow_private_targets: webhook_env.allow_private_targets,
+        })
+    }
+
+    /// Host names are checked by the resolver while connecting, addresses have to be checked here.
+    fn check_literal(&self, url: &Url) -> Result<()> {
+        let Some(ip) = literal_ip(url)? else {
+            return Ok(());
+        };
+        if !self.allow_private_targets && !is_public(ip) {
+            bail!("Webhook URL points at a private address");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, request: WebhookRequest) -> Result<WebhookResponse> {
+        self.check_literal(&Url::parse(&request.url)?)?;
+
+        let mut builder = self
+            .client
+            .post(&request.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder.body(request.body).send().await?;
+
+        Ok(WebhookResponse {
+            status: response.status().as_u16(),
+        })
+    }
+
+    async fn check_target(&self, url: &str) -> Result<()> {
+        let url = Url::parse(url)?;
+        self.check_literal(&url)?;
+        if self.allow_private_targets {
+            return Ok(());
+        }
+
+        if let (None, Some(host)) = (literal_ip(&url)?, url.host_str()) {
+            let port = url.port_or_known_default().unwrap_or(443);
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port)).await?.collect();
+            if addrs.is_empty() {
+                bail!("Webhook host {} does not resolve", host);
+            }
+            if addrs.iter().any(|addr| !is_public(addr.ip())) {
+                bail!("Webhook host {} resolves to a private address", host);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resolves like the system does, but leaves out addresses a webhook must not reach.
+struct PublicResolver {
+    allow_private_targets: bool,
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allow_private_targets = self.allow_private_targets;
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| allow_private_targets || is_public(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// The address of a URL whose host is written as an IP rather than a name.
+fn literal_ip(url: &Url) -> Result<Option<IpAddr>> {
+    let Some(host) = url.host_str() else {
+        bail!("Webhook URL has no host");
+    };
+    Ok(host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .ok())
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match embedded_v4(ip) {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // "This network" and carrier-grade NAT
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments, benchmarking and reserved
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b))
+        || a >= 240)
+}
+
+/// The IPv4 address an IPv6 one hands traffic to: IPv4-mapped and -compatible
+/// (`::ffff:a.b.c.d`, `::a.b.c.d`), NAT64 (`64:ff9b::/96`) and 6to4 (`2002::/16`).
+fn embedded_v4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = ip.segments();
+    let octets = ip.octets();
+    match segments {
+        [0x2002, ..] => Some(Ipv4Addr::new(octets[2], octets[3], octets[4], octets[5])),
+        [0x64, 0xff9b, 0, 0, 0, 0, _, _] => {
+            Some(Ipv4Addr::new(octets[12], octets[13], octets[14], octets[15]))
+        }
+        _ => ip.to_ipv4(),
+    }
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local()
+        // Local-use NAT64, Teredo and documentation, which can all lead to private hosts
+        || (segments[0] == 0x64 && segments[1] == 0xff9b && segments[2] == 1)
+        || (segments[0] == 0x2001 && segments[1] == 0)
+        || (segments[0] == 0x2001 && segments[1] == 0xdb8))
+}
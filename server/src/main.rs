@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
 use server::{
+    application::services::mission_events::MissionEventHub,
     config::config_loader,
-    infrastructure::{database::postgresql_connection, http::http_serv::start},
+    infrastructure::{database::postgresql_connection, http::http_serv::start, jobs},
 };
 use tracing::{error, info};
 
@@ -32,7 +33,26 @@ async fn main() {
     };
     info!("Connected DB");
 
-    start(Arc::new(dotenvy_env), Arc::new(postgres_pool))
+    let postgres_pool = Arc::new(postgres_pool);
+
+    if let Err(e) = jobs::file_cleanup::spawn(Arc::clone(&postgres_pool)) {
+        error!("Failed to start file cleanup: {}", e);
+        std::process::exit(1);
+    }
+
+    if let Err(e) = jobs::mail::spawn(Arc::clone(&postgres_pool)) {
+        error!("Failed to start mail delivery: {}", e);
+        std::process::exit(1);
+    }
+
+    let events = Arc::new(MissionEventHub::new());
+
+    if let Err(e) = jobs::webhooks::spawn(Arc::clone(&postgres_pool), Arc::clone(&events)) {
+        error!("Failed to start webhook delivery: {}", e);
+        std::process::exit(1);
+    }
+
+    start(Arc::new(dotenvy_env), postgres_pool, events)
         .await
         .expect("Failed to start server");
 }